@@ -0,0 +1,207 @@
+use crate::clippy_project_root;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+
+/// What the output of Clippy should look like on the good commits
+pub struct Expectation<'a> {
+    /// The text to look for in the output, e.g. `clippy::needless_return`
+    pub diagnostic: &'a str,
+    /// Whether the diagnostic is emitted on the good commits
+    pub emitted: bool,
+}
+
+enum Outcome {
+    Good,
+    Bad,
+    /// Clippy couldn't be built at this commit
+    Skip,
+}
+
+/// Runs `git bisect` between the `good` and `bad` revisions to find the first commit where the
+/// output of Clippy on the file at `path` stopped meeting `expectation`.
+///
+/// # Panics
+///
+/// Panics if git or cargo could not be run
+pub fn run<'a>(
+    path: &str,
+    good: &str,
+    bad: &str,
+    expectation: &Expectation<'_>,
+    edition: &str,
+    args: impl Iterator<Item = &'a String>,
+) {
+    let root = clippy_project_root();
+    let args: Vec<&String> = args.collect();
+
+    let status = git(&root, &["status", "--porcelain", "--untracked-files=no"]);
+    if !status.is_empty() {
+        eprintln!("error: the working directory has uncommitted changes, which `git bisect` would lose");
+        process::exit(1);
+    }
+
+    // the file may not exist at every commit, or be changed by them
+    let test_path = match copy_test_file(&root, Path::new(path)) {
+        Ok(test_path) => test_path,
+        Err(e) => {
+            eprintln!("Failed to read {path}: {e:?}");
+            process::exit(1);
+        },
+    };
+
+    git(&root, &["bisect", "start"]);
+    let mut outputs = HashMap::new();
+    let mut bisect_output = String::new();
+    for (rev, should_be_good) in [(bad, false), (good, true)] {
+        git(&root, &["-c", "advice.detachedHead=false", "checkout", "--quiet", rev]);
+        let commit = git(&root, &["rev-parse", "HEAD"]);
+        let (outcome, output) = test_commit(&root, &test_path, expectation, edition, &args);
+        let is_good = match outcome {
+            Outcome::Good => true,
+            Outcome::Bad => false,
+            Outcome::Skip => {
+                end_bisect(&root);
+                eprintln!("error: Clippy can't be built at {rev}");
+                process::exit(1);
+            },
+        };
+        if is_good != should_be_good {
+            end_bisect(&root);
+            eprintln!(
+                "error: the output of Clippy at the {} revision {rev} {} the diagnostic:\n{output}",
+                if should_be_good { "good" } else { "bad" },
+                if expectation.emitted == is_good {
+                    "contains"
+                } else {
+                    "doesn't contain"
+                },
+            );
+            process::exit(1);
+        }
+        outputs.insert(commit.clone(), output);
+        bisect_output = git(&root, &["bisect", if is_good { "good" } else { "bad" }, &commit]);
+    }
+
+    let first_bad_commit = loop {
+        if let Some(line) = bisect_output
+            .lines()
+            .find(|line| line.ends_with("is the first bad commit"))
+        {
+            break line.split_whitespace().next().map(ToString::to_string);
+        }
+        if bisect_output.contains("only 'skip'ped commits left to test") {
+            println!("{bisect_output}");
+            break None;
+        }
+
+        let commit = git(&root, &["rev-parse", "HEAD"]);
+        let (outcome, output) = test_commit(&root, &test_path, expectation, edition, &args);
+        let term = match outcome {
+            Outcome::Good => "good",
+            Outcome::Bad => "bad",
+            Outcome::Skip => "skip",
+        };
+        println!("{commit} is {term}");
+        outputs.insert(commit, output);
+        bisect_output = mark_commit(&root, term);
+    };
+
+    end_bisect(&root);
+    if let Some(commit) = first_bad_commit {
+        println!("\nThe output of Clippy changed in:\n");
+        println!("{}", git(&root, &["show", "--no-patch", "--format=medium", &commit]));
+        if let Some(output) = outputs.get(&commit) {
+            println!("\nThe output of Clippy at that commit was:\n{output}");
+        }
+    }
+}
+
+fn copy_test_file(root: &Path, path: &Path) -> std::io::Result<PathBuf> {
+    let dir = root.join("target/bisect_lint");
+    fs::create_dir_all(&dir)?;
+    let test_path = dir.join(path.file_name().unwrap_or_else(|| "test.rs".as_ref()));
+    fs::copy(path, &test_path)?;
+    Ok(test_path)
+}
+
+/// Builds Clippy at the checked out commit and runs it on the file at `test_path`, returning the
+/// output of Clippy along with the outcome
+fn test_commit(
+    root: &Path,
+    test_path: &Path,
+    expectation: &Expectation<'_>,
+    edition: &str,
+    args: &[&String],
+) -> (Outcome, String) {
+    let build = Command::new("cargo")
+        .args(["build", "--quiet", "--bin", "clippy-driver"])
+        .current_dir(root)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .expect("failed to run cargo");
+    if !build.success() {
+        return (Outcome::Skip, String::new());
+    }
+
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--bin", "clippy-driver", "--"])
+        .args(["-L", "./target/debug"])
+        .args(["-Z", "no-codegen"])
+        .args(["--edition", edition])
+        .arg(test_path)
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("failed to run cargo");
+    let output = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    if output.contains(expectation.diagnostic) == expectation.emitted {
+        (Outcome::Good, output)
+    } else {
+        (Outcome::Bad, output)
+    }
+}
+
+/// Runs git with `args` in `root` and returns its stdout, exiting if git fails
+fn git(root: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("failed to run git");
+    if !output.status.success() {
+        eprintln!(
+            "error: `git {}` failed:\n{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        end_bisect(root);
+        process::exit(1);
+    }
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Marks the checked out commit as `good`, `bad` or `skip`, returning the output of `git bisect`.
+/// Unlike [`git`] this doesn't exit when only skipped commits are left, which git treats as an
+/// error.
+fn mark_commit(root: &Path, term: &str) -> String {
+    let output = Command::new("git")
+        .args(["bisect", term])
+        .current_dir(root)
+        .output()
+        .expect("failed to run git");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Stops bisecting, checking out the commit that was checked out before
+fn end_bisect(root: &Path) {
+    let _ = Command::new("git")
+        .args(["bisect", "reset"])
+        .current_dir(root)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
@@ -12,6 +12,7 @@ extern crate rustc_lexer;
 
 use std::path::PathBuf;
 
+pub mod bisect_lint;
 pub mod bless;
 pub mod dogfood;
 pub mod fmt;
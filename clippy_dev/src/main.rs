@@ -3,7 +3,7 @@
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use clippy_dev::{bless, dogfood, fmt, lint, new_lint, serve, setup, update_lints};
+use clippy_dev::{bisect_lint, bless, dogfood, fmt, lint, new_lint, serve, setup, update_lints};
 use indoc::indoc;
 
 fn main() {
@@ -39,6 +39,12 @@ fn main() {
                 matches.get_one::<String>("category").map(String::as_str),
                 matches.get_one::<String>("type").map(String::as_str),
                 matches.contains_id("msrv"),
+                &matches
+                    .get_many::<String>("config")
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>(),
             ) {
                 Ok(_) => update_lints::update(update_lints::UpdateMode::Change),
                 Err(e) => eprintln!("Unable to create lint: {e}"),
@@ -88,6 +94,20 @@ fn main() {
             let args = matches.get_many::<String>("args").into_iter().flatten();
             lint::run(path, args);
         },
+        Some(("bisect_lint", matches)) => {
+            let (diagnostic, emitted) = match matches.get_one::<String>("expected") {
+                Some(diagnostic) => (diagnostic, true),
+                None => (matches.get_one::<String>("unexpected").unwrap(), false),
+            };
+            bisect_lint::run(
+                matches.get_one::<String>("path").unwrap(),
+                matches.get_one::<String>("good").unwrap(),
+                matches.get_one::<String>("bad").unwrap(),
+                &bisect_lint::Expectation { diagnostic, emitted },
+                matches.get_one::<String>("edition").unwrap(),
+                matches.get_many::<String>("args").into_iter().flatten(),
+            );
+        },
         Some(("rename_lint", matches)) => {
             let old_name = matches.get_one::<String>("old_name").unwrap();
             let new_name = matches.get_one::<String>("new_name").unwrap_or(old_name);
@@ -150,7 +170,8 @@ fn get_clap_config() -> ArgMatches {
                     * the changelog contains markdown link references at the bottom\n \
                     * all lint groups include the correct lints\n \
                     * lint modules in `clippy_lints/*` are visible in `src/lib.rs` via `pub mod`\n \
-                    * all lints are registered in the lint store",
+                    * all lints are registered in the lint store\n \
+                    * the Rust versions the lints depend on are listed in `src/lint_msrvs.rs`",
                 )
                 .args([
                     Arg::new("print-only")
@@ -204,6 +225,12 @@ fn get_clap_config() -> ArgMatches {
                         .long("msrv")
                         .action(ArgAction::SetTrue)
                         .help("Add MSRV config code to the lint"),
+                    Arg::new("config")
+                        .long("config")
+                        .action(ArgAction::Append)
+                        .value_name("NAME:TYPE=DEFAULT")
+                        .conflicts_with("type")
+                        .help("Add a `clippy.toml` option to the lint, ex: max_fn_params:u64=3"),
                 ]),
             Command::new("setup")
                 .about("Support for setting up your personal development environment")
@@ -296,6 +323,55 @@ fn get_clap_config() -> ArgMatches {
                         .action(ArgAction::Append)
                         .help("Pass extra arguments to cargo/clippy-driver"),
                 ]),
+            Command::new("bisect_lint")
+                .alias("bisect-lint")
+                .about("Find the commit that changed the output of Clippy on a file with `git bisect`")
+                .after_help(indoc! {"
+                    The output of Clippy on the file meets the expectation at the good revision, and
+                    doesn't at the bad one. Clippy is built at each commit that is tested.
+
+                    EXAMPLES
+                        Find the commit that made a lint fire on a file:
+                            cargo dev bisect_lint file.rs --good rust-1.66.0 --unexpected clippy::needless_return
+
+                        Find the commit that made a warning go away:
+                            cargo dev bisect_lint file.rs --good 3a2b1c0 --expected 'unneeded `return`'
+
+                        Set lint levels:
+                            cargo dev bisect_lint file.rs --good 3a2b1c0 --unexpected unwrap -- -W clippy::unwrap_used
+                "})
+                .args([
+                    Arg::new("path")
+                        .required(true)
+                        .help("The path to the file to run Clippy on"),
+                    Arg::new("good")
+                        .long("good")
+                        .value_name("REV")
+                        .required(true)
+                        .help("A revision where the output of Clippy meets the expectation"),
+                    Arg::new("bad")
+                        .long("bad")
+                        .value_name("REV")
+                        .default_value("HEAD")
+                        .help("A revision where the output of Clippy doesn't meet the expectation"),
+                    Arg::new("expected")
+                        .long("expected")
+                        .value_name("DIAGNOSTIC")
+                        .conflicts_with("unexpected")
+                        .required_unless_present("unexpected")
+                        .help("Text the output of Clippy contains at the good revision, e.g. the name of a lint"),
+                    Arg::new("unexpected")
+                        .long("unexpected")
+                        .value_name("DIAGNOSTIC")
+                        .help("Text the output of Clippy doesn't contain at the good revision"),
+                    Arg::new("edition")
+                        .long("edition")
+                        .default_value("2021")
+                        .help("The edition of the file"),
+                    Arg::new("args")
+                        .action(ArgAction::Append)
+                        .help("Pass extra arguments to clippy-driver"),
+                ]),
             Command::new("rename_lint").about("Renames the given lint").args([
                 Arg::new("old_name")
                     .index(1)
@@ -12,6 +12,64 @@ struct LintData<'a> {
     category: &'a str,
     ty: Option<&'a str>,
     project_root: PathBuf,
+    config: Vec<ConfigOption<'a>>,
+}
+
+/// A `clippy.toml` option of the lint, given as `name:type=default`
+#[derive(Debug, PartialEq, Eq)]
+struct ConfigOption<'a> {
+    /// The name of the option in `snake_case`
+    name: String,
+    ty: &'a str,
+    default: &'a str,
+}
+
+impl<'a> ConfigOption<'a> {
+    fn parse(option: &'a str) -> Option<Self> {
+        let (name, rest) = option.split_once(':')?;
+        let (ty, default) = rest.split_once('=')?;
+        let name = name.trim().replace('-', "_");
+        let (ty, default) = (ty.trim(), default.trim());
+        let is_ident = name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        (is_ident && !ty.is_empty() && !default.is_empty()).then_some(Self { name, ty, default })
+    }
+
+    /// Whether the value can be copied out of the `Conf` rather than cloned
+    fn is_copy(&self) -> bool {
+        matches!(
+            self.ty,
+            "bool"
+                | "char"
+                | "u8"
+                | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "isize"
+                | "f32"
+                | "f64"
+        )
+    }
+
+    /// The default value as it would be written in `clippy.toml`, if it's a literal that is
+    /// written the same way in Rust and TOML
+    fn toml_default(&self) -> Option<&str> {
+        let is_literal = matches!(self.default, "true" | "false")
+            || (self.default.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+                && self.default[1..]
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || matches!(c, '_' | '.')));
+        is_literal.then_some(self.default)
+    }
 }
 
 trait Context {
@@ -32,27 +90,47 @@ impl<T> Context for io::Result<T> {
 
 /// Creates the files required to implement and test a new lint and runs `update_lints`.
 ///
+/// Each of `config` is a `clippy.toml` option of the lint, given as `name:type=default`, which is
+/// added to `define_Conf!` and passed to the constructor of the lint pass.
+///
 /// # Errors
 ///
-/// This function errors out if the files couldn't be created or written to.
+/// This function errors out if the files couldn't be created or written to, or if an option is
+/// malformed.
 pub fn create(
     pass: Option<&String>,
     lint_name: Option<&String>,
     category: Option<&str>,
     mut ty: Option<&str>,
     msrv: bool,
+    config: &[&str],
 ) -> io::Result<()> {
     if category == Some("cargo") && ty.is_none() {
         // `cargo` is a special category, these lints should always be in `clippy_lints/src/cargo`
         ty = Some("cargo");
     }
 
+    let config = config
+        .iter()
+        .map(|option| {
+            ConfigOption::parse(option).ok_or_else(|| {
+                let message = format!("invalid configuration option `{option}`, expected `name:type=default`");
+                io::Error::new(ErrorKind::Other, message)
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    if !config.is_empty() && ty.is_some() {
+        let message = "configuration options can only be added to lints with their own lint pass";
+        return Err(io::Error::new(ErrorKind::Other, message));
+    }
+
     let lint = LintData {
         pass: pass.map_or("", String::as_str),
         name: lint_name.expect("`name` argument is validated by clap"),
         category: category.expect("`category` argument is validated by clap"),
         ty,
         project_root: clippy_project_root(),
+        config,
     };
 
     create_lint(&lint, msrv).context("Unable to create lint implementation")?;
@@ -62,6 +140,11 @@ pub fn create(
         add_lint(&lint, msrv).context("Unable to add lint to clippy_lints/src/lib.rs")?;
     }
 
+    if !lint.config.is_empty() {
+        add_config(&lint).context("Unable to add the configuration to clippy_lints/src/utils/conf.rs")?;
+        create_config_test(&lint).context("Unable to create a test for the configuration")?;
+    }
+
     Ok(())
 }
 
@@ -112,35 +195,117 @@ fn create_test(lint: &LintData<'_>) -> io::Result<()> {
     Ok(())
 }
 
+fn create_config_test(lint: &LintData<'_>) -> io::Result<()> {
+    let relative_test_dir = format!("tests/ui-toml/{}", lint.name);
+    let test_dir = lint.project_root.join(&relative_test_dir);
+    fs::create_dir(&test_dir)?;
+
+    let mut clippy_toml = String::from("# TODO: Set the options to values other than their default\n");
+    for option in &lint.config {
+        let key = option.name.replace('_', "-");
+        match option.toml_default() {
+            Some(default) => {
+                let _ = writeln!(clippy_toml, "{key} = {default}");
+            },
+            None => {
+                let _ = writeln!(clippy_toml, "# {key} = ");
+            },
+        }
+    }
+    write_file(test_dir.join("clippy.toml"), clippy_toml)?;
+    write_file(
+        test_dir.join(format!("{}.rs", lint.name)),
+        get_test_file_contents(lint.name, None),
+    )?;
+
+    println!("Generated configuration test directory: `{relative_test_dir}`");
+
+    Ok(())
+}
+
 fn add_lint(lint: &LintData<'_>, enable_msrv: bool) -> io::Result<()> {
     let path = "clippy_lints/src/lib.rs";
     let mut lib_rs = fs::read_to_string(path).context("reading")?;
 
     let comment_start = lib_rs.find("// add lints here,").expect("Couldn't find comment");
 
-    let new_lint = if enable_msrv {
-        format!(
-            "store.register_{lint_pass}_pass(move |{ctor_arg}| Box::new({module_name}::{camel_name}::new(msrv())));\n    ",
-            lint_pass = lint.pass,
-            ctor_arg = if lint.pass == "late" { "_" } else { "" },
-            module_name = lint.name,
-            camel_name = to_camel_case(lint.name),
-        )
-    } else {
-        format!(
-            "store.register_{lint_pass}_pass(|{ctor_arg}| Box::new({module_name}::{camel_name}));\n    ",
-            lint_pass = lint.pass,
-            ctor_arg = if lint.pass == "late" { "_" } else { "" },
-            module_name = lint.name,
-            camel_name = to_camel_case(lint.name),
-        )
-    };
+    let mut new_lint = String::new();
+    let mut ctor_args = Vec::new();
+    if enable_msrv {
+        ctor_args.push(String::from("msrv()"));
+    }
+    for option in &lint.config {
+        let name = &option.name;
+        if option.is_copy() {
+            let _ = write!(new_lint, "let {name} = conf.{name};\n    ");
+            ctor_args.push(name.clone());
+        } else {
+            let _ = write!(new_lint, "let {name} = conf.{name}.clone();\n    ");
+            ctor_args.push(format!("{name}.clone()"));
+        }
+    }
+
+    let camel_name = to_camel_case(lint.name);
+    let _ = write!(
+        new_lint,
+        "store.register_{lint_pass}_pass({capture}|{ctor_arg}| Box::new({module_name}::{ctor}));\n    ",
+        lint_pass = lint.pass,
+        capture = if ctor_args.is_empty() { "" } else { "move " },
+        ctor_arg = if lint.pass == "late" { "_" } else { "" },
+        module_name = lint.name,
+        ctor = if ctor_args.is_empty() {
+            camel_name
+        } else {
+            format!("{camel_name}::new({})", ctor_args.join(", "))
+        },
+    );
 
     lib_rs.insert_str(comment_start, &new_lint);
 
     fs::write(path, lib_rs).context("writing")
 }
 
+/// Adds the configuration options of the lint at the end of `define_Conf!`
+fn add_config(lint: &LintData<'_>) -> io::Result<()> {
+    let path = lint.project_root.join("clippy_lints/src/utils/conf.rs");
+    let mut conf_rs = fs::read_to_string(&path).context("reading")?;
+
+    let define_conf_start = conf_rs.find("\ndefine_Conf! {").expect("Couldn't find `define_Conf!`");
+    let define_conf_end = define_conf_start
+        + conf_rs[define_conf_start..]
+            .find("\n}\n")
+            .expect("Couldn't find the end of `define_Conf!`")
+        + 1;
+
+    let mut options = String::new();
+    for option in &lint.config {
+        let _ = writedoc!(
+            options,
+            "
+                /// Lint: {name_upper}.
+                ///
+                /// TODO: Describe the configuration
+                ({name}: {ty} = {default}),
+            ",
+            name_upper = lint.name.to_uppercase(),
+            name = option.name,
+            ty = option.ty,
+            default = option.default,
+        );
+    }
+    // indent the entries like the other ones
+    let options: String = options.lines().map(|line| format!("    {line}\n")).collect();
+    conf_rs.insert_str(define_conf_end, &options);
+
+    fs::write(&path, conf_rs).context("writing")?;
+    println!(
+        "Added the configuration to `clippy_lints/src/utils/conf.rs`, run `cargo collect-metadata` \
+         after describing it"
+    );
+
+    Ok(())
+}
+
 fn write_file<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
     fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
         OpenOptions::new()
@@ -219,6 +384,7 @@ fn get_manifest_contents(lint_name: &str, hint: &str) -> String {
     )
 }
 
+#[allow(clippy::too_many_lines)]
 fn get_lint_file_contents(lint: &LintData<'_>, enable_msrv: bool) -> String {
     let mut result = String::new();
 
@@ -235,6 +401,7 @@ fn get_lint_file_contents(lint: &LintData<'_>, enable_msrv: bool) -> String {
     let name_camel = to_camel_case(lint.name);
     let name_upper = lint_name.to_uppercase();
 
+    let has_fields = enable_msrv || !lint.config.is_empty();
     result.push_str(&if enable_msrv {
         formatdoc!(
             r#"
@@ -243,6 +410,15 @@ fn get_lint_file_contents(lint: &LintData<'_>, enable_msrv: bool) -> String {
             use rustc_lint::{{{context_import}, {pass_type}, LintContext}};
             use rustc_session::{{declare_tool_lint, impl_lint_pass}};
 
+        "#
+        )
+    } else if has_fields {
+        formatdoc!(
+            r#"
+            {pass_import}
+            use rustc_lint::{{{context_import}, {pass_type}}};
+            use rustc_session::{{declare_tool_lint, impl_lint_pass}};
+
         "#
         )
     } else {
@@ -258,21 +434,47 @@ fn get_lint_file_contents(lint: &LintData<'_>, enable_msrv: bool) -> String {
 
     let _ = write!(result, "{}", get_lint_declaration(&name_upper, category));
 
-    result.push_str(&if enable_msrv {
-        formatdoc!(
+    if has_fields {
+        let mut fields = Vec::new();
+        if enable_msrv {
+            fields.push((String::from("msrv"), "Msrv"));
+        }
+        fields.extend(lint.config.iter().map(|option| (option.name.clone(), option.ty)));
+
+        let _ = writeln!(result, "pub struct {name_camel} {{");
+        for (name, ty) in &fields {
+            let _ = writeln!(result, "    {name}: {ty},");
+        }
+        let params = fields
+            .iter()
+            .map(|(name, ty)| format!("{name}: {ty}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let names = fields
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writedoc!(
+            result,
             r#"
-            pub struct {name_camel} {{
-                msrv: Msrv,
             }}
 
             impl {name_camel} {{
                 #[must_use]
-                pub fn new(msrv: Msrv) -> Self {{
-                    Self {{ msrv }}
+                pub fn new({params}) -> Self {{
+                    Self {{ {names} }}
                 }}
             }}
 
             impl_lint_pass!({name_camel} => [{name_upper}]);
+        "#
+        );
+    }
+
+    result.push_str(&if enable_msrv {
+        formatdoc!(
+            r#"
 
             impl {pass_type}{pass_lifetimes} for {name_camel} {{
                 extract_msrv_attr!({context_import});
@@ -283,6 +485,13 @@ fn get_lint_file_contents(lint: &LintData<'_>, enable_msrv: bool) -> String {
             // TODO: Update msrv config comment in `clippy_lints/src/utils/conf.rs`
         "#
         )
+    } else if has_fields {
+        formatdoc!(
+            r#"
+
+            impl {pass_type}{pass_lifetimes} for {name_camel} {{}}
+        "#
+        )
     } else {
         formatdoc!(
             r#"
@@ -541,6 +750,30 @@ fn setup_mod_file(path: &Path, lint: &LintData<'_>) -> io::Result<&'static str>
     Ok(lint_context)
 }
 
+#[test]
+fn test_parse_config_option() {
+    let option = ConfigOption::parse("max-fn-params:u64=3").unwrap();
+    assert_eq!(
+        option,
+        ConfigOption {
+            name: String::from("max_fn_params"),
+            ty: "u64",
+            default: "3",
+        }
+    );
+    assert!(option.is_copy());
+    assert_eq!(option.toml_default(), Some("3"));
+
+    let option = ConfigOption::parse("allowed_paths:Vec<String>=Vec::new()").unwrap();
+    assert_eq!(option.ty, "Vec<String>");
+    assert_eq!(option.default, "Vec::new()");
+    assert!(!option.is_copy());
+    assert_eq!(option.toml_default(), None);
+
+    assert_eq!(ConfigOption::parse("threshold=3"), None);
+    assert_eq!(ConfigOption::parse("Threshold:u64=3"), None);
+}
+
 #[test]
 fn test_camel_case() {
     let s = "a_lint";
@@ -3,7 +3,7 @@ use aho_corasick::AhoCorasickBuilder;
 use indoc::writedoc;
 use itertools::Itertools;
 use rustc_lexer::{tokenize, unescape, LiteralKind, TokenKind};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fmt::Write;
 use std::fs::{self, OpenOptions};
@@ -173,6 +173,8 @@ fn generate_lint_files(
 
     let content = gen_renamed_lints_test(renamed_lints);
     process_file("tests/ui/rename.rs", update_mode, &content);
+
+    process_file("src/lint_msrvs.rs", update_mode, &gen_lint_msrvs(lints));
 }
 
 pub fn print_lints() {
@@ -775,6 +777,378 @@ fn gen_renamed_lints_list(lints: &[RenamedLint]) -> String {
     res
 }
 
+/// Generates the Rust versions each lint depends on, read from the uses of the
+/// `clippy_utils::msrvs` constants in the lint sources.
+///
+/// A constant is attributed to the lints emitted by the code its check guards:
+/// * the rest of the condition containing the check, as in `msrv.meets(..) && module::check(..)`,
+/// * else the block of the `if`, or the rest of the enclosing block when the `if` only returns,
+/// * for `let name = .. msrv.meets(..) ..;`, the expressions using `name`,
+/// * for any other check, the rest of the enclosing block.
+///
+/// The lints emitted by some code are the lint names in it, in the functions of the same file it
+/// calls and in the `module::check*` functions of submodules it calls. When none is found, e.g. for
+/// a check in a helper returning a value, the constant is attributed to all the lints of the file.
+fn gen_lint_msrvs(lints: &[Lint]) -> String {
+    let versions = msrv_versions();
+    let lint_names: HashSet<String> = lints.iter().map(|l| l.name.to_uppercase()).collect();
+    let files: HashMap<PathBuf, Vec<String>> = clippy_lints_src_files()
+        .filter(|(rel_path, _)| !rel_path.starts_with("utils"))
+        .map(|(_, file)| {
+            let path = file.path();
+            let contents =
+                fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
+            (path.to_path_buf(), msrv_tokens(&contents))
+        })
+        .collect();
+    let sources = MsrvSources {
+        files: &files,
+        lint_names: &lint_names,
+    };
+
+    // The first constant in name order of each version, for each lint
+    let mut lint_msrvs: BTreeMap<String, BTreeMap<(u32, u32, u32), &str>> = BTreeMap::new();
+    for (path, tokens) in &files {
+        for pos in 0..tokens.len().saturating_sub(2) {
+            if tokens[pos] != "msrvs" || tokens[pos + 1] != "::" {
+                continue;
+            }
+            let constant = tokens[pos + 2].as_str();
+            let version = *versions
+                .get(constant)
+                .unwrap_or_else(|| panic!("`msrvs::{constant}` in `{}` is not defined", path.display()));
+
+            let mut emitted = BTreeSet::new();
+            for range in sources.guarded_regions(path, tokens, pos) {
+                sources.emitted_lints(path, &tokens[range], &mut HashSet::new(), &mut emitted);
+            }
+            if emitted.is_empty() {
+                sources.emitted_lints(path, tokens, &mut HashSet::new(), &mut emitted);
+            }
+
+            for lint in emitted {
+                let constants = lint_msrvs.entry(lint).or_default();
+                let first = constants.entry(version).or_insert(constant);
+                if constant < *first {
+                    *first = constant;
+                }
+            }
+        }
+    }
+
+    let mut output = GENERATED_FILE_COMMENT.to_string();
+    output.push_str(
+        "use clippy_utils::msrvs;\n\
+         use rustc_semver::RustcVersion;\n\
+         \n\
+         /// The Rust versions stabilizing the features that the MSRV-aware lints rely on. A lint with\n\
+         /// several versions is partially disabled below the highest one, e.g. for some types or only in\n\
+         /// its suggestion.\n\
+         #[rustfmt::skip]\n\
+         pub const LINT_MSRVS: &[(&str, &[RustcVersion])] = &[\n",
+    );
+    for (lint, constants) in lint_msrvs {
+        let constants = constants
+            .values()
+            .map(|constant| format!("msrvs::{constant}"))
+            .join(", ");
+        writeln!(output, "    (\"{lint}\", &[{constants}]),").unwrap();
+    }
+    output.push_str("];\n");
+    output
+}
+
+/// Reads the version of each constant from the `msrv_aliases!` of `clippy_utils/src/msrvs.rs`.
+fn msrv_versions() -> HashMap<String, (u32, u32, u32)> {
+    let path = clippy_project_root().join("clippy_utils/src/msrvs.rs");
+    let contents = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
+    let aliases = contents
+        .split_once("msrv_aliases! {")
+        .and_then(|(_, rest)| rest.split_once("\n}"))
+        .unwrap_or_else(|| panic!("Couldn't find `msrv_aliases!` in `{}`", path.display()))
+        .0;
+
+    let mut versions = HashMap::new();
+    for (version, constants) in aliases.lines().filter_map(|line| line.split_once('{')) {
+        let Some((major, minor, patch)) = version
+            .split(',')
+            .map(|n| n.trim().parse::<u32>().unwrap())
+            .collect_tuple()
+        else {
+            continue;
+        };
+        for constant in constants.trim_end_matches([' ', '}']).split(',') {
+            versions.insert(constant.trim().to_string(), (major, minor, patch));
+        }
+    }
+    versions
+}
+
+/// Tokenizes a lint source for `gen_lint_msrvs`, leaving out the comments, the `use` items and the
+/// lint declarations, with every literal replaced by `""`.
+fn msrv_tokens(contents: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut offset = 0usize;
+    let mut last_end = usize::MAX;
+    for token in tokenize(contents) {
+        let range = offset..offset + token.len as usize;
+        offset = range.end;
+        let content = &contents[range.clone()];
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. } => continue,
+            TokenKind::Literal { .. } => tokens.push(String::from("\"\"")),
+            // Joins `::` and `=>`, which are lexed as two tokens
+            TokenKind::Colon | TokenKind::Gt
+                if last_end == range.start
+                    && tokens.last().map(String::as_str)
+                        == Some(if token.kind == TokenKind::Colon { ":" } else { "=" }) =>
+            {
+                tokens.last_mut().unwrap().push_str(content);
+            },
+            _ => tokens.push(content.to_string()),
+        }
+        last_end = range.end;
+    }
+
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut pos = 0;
+    while pos < tokens.len() {
+        match tokens[pos].as_str() {
+            "declare_clippy_lint" | "declare_lint_pass" | "impl_lint_pass"
+                if tokens.get(pos + 1).map(String::as_str) == Some("!") =>
+            {
+                pos = closing_token(&tokens, pos + 2) + 1;
+            },
+            "use" => {
+                pos += tokens[pos..]
+                    .iter()
+                    .position(|t| t == ";")
+                    .unwrap_or(tokens.len() - pos)
+                    + 1;
+            },
+            _ => {
+                result.push(std::mem::take(&mut tokens[pos]));
+                pos += 1;
+            },
+        }
+    }
+    result
+}
+
+/// Returns the position of the bracket closing the one at `open`, or of the last token if it's not
+/// closed.
+fn closing_token(tokens: &[String], open: usize) -> usize {
+    let close = match tokens.get(open).map(String::as_str) {
+        Some("{") => "}",
+        Some("(") => ")",
+        Some("[") => "]",
+        _ => return open,
+    };
+    let mut depth = 0;
+    for (pos, token) in tokens.iter().enumerate().skip(open) {
+        if *token == tokens[open] {
+            depth += 1;
+        } else if token == close {
+            depth -= 1;
+            if depth == 0 {
+                return pos;
+            }
+        }
+    }
+    tokens.len() - 1
+}
+
+fn is_open(token: &str) -> bool {
+    matches!(token, "(" | "[" | "{")
+}
+
+fn is_close(token: &str) -> bool {
+    matches!(token, ")" | "]" | "}")
+}
+
+struct MsrvSources<'a> {
+    files: &'a HashMap<PathBuf, Vec<String>>,
+    lint_names: &'a HashSet<String>,
+}
+
+impl MsrvSources<'_> {
+    /// Returns the regions of `tokens` guarded by the check of the constant at `pos`, see
+    /// `gen_lint_msrvs`.
+    fn guarded_regions(&self, path: &Path, tokens: &[String], pos: usize) -> Vec<Range<usize>> {
+        let block_end = enclosing_block_end(tokens, pos);
+        let statement = &tokens[statement_start(tokens, pos)..pos];
+
+        // The block following the check in the same statement
+        let mut depth = 0usize;
+        let mut block = None;
+        for (i, token) in tokens.iter().enumerate().skip(pos) {
+            match token.as_str() {
+                "{" if depth == 0 => {
+                    block = Some(i);
+                    break;
+                },
+                "}" => break,
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth = depth.saturating_sub(1),
+                ";" | "," if depth == 0 => break,
+                _ => {},
+            }
+        }
+
+        if let Some(block) = block && statement.iter().any(|t| t == "if") {
+            let mut condition = BTreeSet::new();
+            self.emitted_lints(path, &tokens[pos..block], &mut HashSet::new(), &mut condition);
+            if !condition.is_empty() {
+                return vec![pos..block];
+            }
+            let block_close = closing_token(tokens, block);
+            if tokens[block + 1] == "return" {
+                return vec![block_close + 1..block_end];
+            }
+            return vec![block..block_close + 1];
+        }
+
+        if let [first, name, eq, ..] = statement && first == "let" && eq == "=" {
+            let mut regions = Vec::new();
+            let mut i = pos + tokens[pos..block_end].iter().position(|t| t == ";").unwrap_or(block_end - pos);
+            while i < block_end {
+                if tokens[i] == *name {
+                    let mut depth = 0isize;
+                    let mut end = i;
+                    while end < block_end {
+                        let token = tokens[end].as_str();
+                        if is_open(token) {
+                            depth += 1;
+                        } else if is_close(token) {
+                            depth -= 1;
+                            if depth < 0 {
+                                break;
+                            }
+                            if depth == 0 && token == "}" && tokens.get(end + 1).map(String::as_str) != Some(",") {
+                                end += 1;
+                                break;
+                            }
+                        } else if depth == 0 && (token == "," || token == ";") {
+                            break;
+                        }
+                        end += 1;
+                    }
+                    regions.push(i..end);
+                    i = end;
+                }
+                i += 1;
+            }
+            return regions;
+        }
+
+        vec![pos..block_end]
+    }
+
+    /// Collects the lints emitted by `tokens` of the file at `path` into `lints`. `seen` holds the
+    /// functions and files already visited.
+    fn emitted_lints(
+        &self,
+        path: &Path,
+        tokens: &[String],
+        seen: &mut HashSet<(PathBuf, String)>,
+        lints: &mut BTreeSet<String>,
+    ) {
+        lints.extend(tokens.iter().filter(|t| self.lint_names.contains(*t)).cloned());
+
+        let submodules = if path.file_name() == Some(OsStr::new("mod.rs")) {
+            path.parent().unwrap().to_path_buf()
+        } else {
+            path.with_extension("")
+        };
+        for (i, window) in tokens.windows(2).enumerate() {
+            let [name, paren] = window else { unreachable!() };
+            if paren != "(" {
+                continue;
+            }
+
+            // `module::check(..)`
+            if name.starts_with("check") && i >= 2 && tokens[i - 1] == "::" {
+                let module = &tokens[i - 2];
+                for file in [
+                    submodules.join(format!("{module}.rs")),
+                    submodules.join(module).join("mod.rs"),
+                ] {
+                    if let Some(file_tokens) = self.files.get(&file) && seen.insert((file.clone(), String::new())) {
+                        self.emitted_lints(&file, file_tokens, seen, lints);
+                    }
+                }
+            }
+
+            // `function(..)` of the same file
+            let is_fn_name = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if is_fn_name
+                && !(i > 0 && matches!(tokens[i - 1].as_str(), "fn" | "." | "::"))
+                && seen.insert((path.to_path_buf(), name.clone()))
+            {
+                let file_tokens = &self.files[path];
+                if let Some(body) = fn_body(file_tokens, name) {
+                    self.emitted_lints(path, &file_tokens[body], seen, lints);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the end of the block enclosing the token at `pos`.
+fn enclosing_block_end(tokens: &[String], pos: usize) -> usize {
+    let mut depth = 0usize;
+    for i in (0..pos).rev() {
+        let token = tokens[i].as_str();
+        if is_close(token) {
+            depth += 1;
+        } else if is_open(token) {
+            if depth == 0 {
+                if token == "{" {
+                    return closing_token(tokens, i);
+                }
+            } else {
+                depth -= 1;
+            }
+        }
+    }
+    tokens.len()
+}
+
+/// Returns the start of the statement, match arm or argument containing the token at `pos`.
+fn statement_start(tokens: &[String], pos: usize) -> usize {
+    let mut depth = 0usize;
+    for i in (0..pos).rev() {
+        let token = tokens[i].as_str();
+        if is_close(token) {
+            if token == "}" && depth == 0 {
+                return i + 1;
+            }
+            depth += 1;
+        } else if is_open(token) {
+            if depth == 0 {
+                if token == "{" {
+                    return i + 1;
+                }
+            } else {
+                depth -= 1;
+            }
+        } else if depth == 0 && matches!(token, ";" | "," | "=>") {
+            return i + 1;
+        }
+    }
+    0
+}
+
+/// Returns the range of the body of the function `name` in `tokens`.
+fn fn_body(tokens: &[String], name: &str) -> Option<Range<usize>> {
+    let def = tokens.windows(2).position(|w| w[0] == "fn" && w[1] == name)?;
+    let open = def + tokens[def..].iter().position(|t| t == "{" || t == ";")?;
+    (tokens[open] == "{").then(|| open..closing_token(tokens, open) + 1)
+}
+
 /// Gathers all lints defined in `clippy_lints/src`
 fn gather_all() -> (Vec<Lint>, Vec<DeprecatedLint>, Vec<RenamedLint>) {
     let mut lints = Vec::with_capacity(1000);
@@ -1262,4 +1636,57 @@ mod tests {
 
         assert_eq!(expected, gen_deprecated(&lints));
     }
+
+    #[test]
+    fn test_msrv_guarded_lints() {
+        static CONTENTS: &str = r#"
+            use super::{BAR, FOO};
+
+            fn check(cx: &LateContext<'_>, msrv: &Msrv) {
+                // BAZ
+                if msrv.meets(msrvs::FIRST) && other::check(cx) {
+                    span_lint(cx, FOO, "");
+                }
+                if !msrv.meets(msrvs::SECOND) {
+                    return;
+                }
+                lint_bar(cx);
+                let third = msrv.meets(msrvs::THIRD);
+                match x {
+                    Some(_) if third => span_lint(cx, BAZ, "FOO"),
+                    _ => span_lint(cx, FOO, ""),
+                }
+            }
+
+            fn lint_bar(cx: &LateContext<'_>) {
+                span_lint(cx, BAR, "");
+            }
+        "#;
+        let files = HashMap::from([
+            (PathBuf::from("lints/foo.rs"), msrv_tokens(CONTENTS)),
+            (
+                PathBuf::from("lints/foo/other.rs"),
+                msrv_tokens("fn check() { span_lint(cx, OTHER, \"\"); }"),
+            ),
+        ]);
+        let lint_names = ["FOO", "BAR", "BAZ", "OTHER"].into_iter().map(String::from).collect();
+        let sources = MsrvSources {
+            files: &files,
+            lint_names: &lint_names,
+        };
+
+        let path = Path::new("lints/foo.rs");
+        let tokens = &files[path];
+        let guarded = |constant: &str| {
+            let pos = tokens.iter().position(|t| t == constant).unwrap() - 2;
+            let mut lints = BTreeSet::new();
+            for range in sources.guarded_regions(path, tokens, pos) {
+                sources.emitted_lints(path, &tokens[range], &mut HashSet::new(), &mut lints);
+            }
+            lints.into_iter().collect::<Vec<_>>()
+        };
+        assert_eq!(guarded("FIRST"), ["OTHER"]);
+        assert_eq!(guarded("SECOND"), ["BAR", "BAZ", "FOO"]);
+        assert_eq!(guarded("THIRD"), ["BAZ"]);
+    }
 }
@@ -2,7 +2,7 @@ use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::{match_def_path, paths};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def_id::DefId;
-use rustc_hir::{AsyncGeneratorKind, Body, BodyId, GeneratorKind};
+use rustc_hir::{AsyncGeneratorKind, Body, BodyId, GeneratorKind, HirId};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::GeneratorInteriorTypeCause;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
@@ -145,9 +145,9 @@ declare_clippy_lint! {
     /// ```toml
     /// await-holding-invalid-types = [
     ///   # You can specify a type name
-    ///   "CustomLockType",
+    ///   "crate::CustomLockType",
     ///   # You can (optionally) specify a reason
-    ///   { path = "OtherCustomLockType", reason = "Relies on a thread local" }
+    ///   { path = "crate::OtherCustomLockType", reason = "Relies on a thread local" }
     /// ]
     /// ```
     ///
@@ -171,12 +171,12 @@ impl_lint_pass!(AwaitHolding => [AWAIT_HOLDING_LOCK, AWAIT_HOLDING_REFCELL_REF,
 
 #[derive(Debug)]
 pub struct AwaitHolding {
-    conf_invalid_types: Vec<DisallowedPath>,
-    def_ids: FxHashMap<DefId, DisallowedPath>,
+    conf_invalid_types: Vec<DisallowedPath<false>>,
+    def_ids: FxHashMap<DefId, DisallowedPath<false>>,
 }
 
 impl AwaitHolding {
-    pub(crate) fn new(conf_invalid_types: Vec<DisallowedPath>) -> Self {
+    pub(crate) fn new(conf_invalid_types: Vec<DisallowedPath<false>>) -> Self {
         Self {
             conf_invalid_types,
             def_ids: FxHashMap::default(),
@@ -204,6 +204,7 @@ impl LateLintPass<'_> for AwaitHolding {
             self.check_interior_types(
                 cx,
                 typeck_results.generator_interior_types.as_ref().skip_binder(),
+                body.value.hir_id,
                 body.value.span,
             );
         }
@@ -211,7 +212,13 @@ impl LateLintPass<'_> for AwaitHolding {
 }
 
 impl AwaitHolding {
-    fn check_interior_types(&self, cx: &LateContext<'_>, ty_causes: &[GeneratorInteriorTypeCause<'_>], span: Span) {
+    fn check_interior_types(
+        &self,
+        cx: &LateContext<'_>,
+        ty_causes: &[GeneratorInteriorTypeCause<'_>],
+        hir_id: HirId,
+        span: Span,
+    ) {
         for ty_cause in ty_causes {
             if let rustc_middle::ty::Adt(adt, _) = ty_cause.ty.kind() {
                 if is_mutex_guard(cx, adt.did()) {
@@ -245,7 +252,9 @@ impl AwaitHolding {
                             );
                         },
                     );
-                } else if let Some(disallowed) = self.def_ids.get(&adt.did()) {
+                } else if let Some(disallowed) = self.def_ids.get(&adt.did())
+                    && !disallowed.is_allowed_at(cx, hir_id, ty_cause.span)
+                {
                     emit_invalid_type(cx, ty_cause.span, disallowed);
                 }
             }
@@ -253,7 +262,7 @@ impl AwaitHolding {
     }
 }
 
-fn emit_invalid_type(cx: &LateContext<'_>, span: Span, disallowed: &DisallowedPath) {
+fn emit_invalid_type(cx: &LateContext<'_>, span: Span, disallowed: &DisallowedPath<false>) {
     span_lint_and_then(
         cx,
         AWAIT_HOLDING_INVALID_TYPE,
@@ -26,7 +26,7 @@ declare_clippy_lint! {
     /// # fn somefunc() -> bool { true };
     /// if { true } { /* ... */ }
     ///
-    /// if { let x = somefunc(); x } { /* ... */ }
+    /// if { let x = somefunc(); !x } { /* ... */ }
     /// ```
     ///
     /// Use instead:
@@ -34,7 +34,7 @@ declare_clippy_lint! {
     /// # fn somefunc() -> bool { true };
     /// if true { /* ... */ }
     ///
-    /// let res = { let x = somefunc(); x };
+    /// let res = { let x = somefunc(); !x };
     /// if res { /* ... */ }
     /// ```
     #[clippy::version = "1.45.0"]
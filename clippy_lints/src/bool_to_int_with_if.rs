@@ -30,12 +30,12 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust
     /// # let condition = false;
-    /// i64::from(condition);
+    /// let _ = i64::from(condition);
     /// ```
     /// or
     /// ```rust
     /// # let condition = false;
-    /// condition as i64;
+    /// let _ = condition as i64;
     /// ```
     #[clippy::version = "1.65.0"]
     pub BOOL_TO_INT_WITH_IF,
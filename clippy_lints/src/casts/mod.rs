@@ -46,7 +46,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// let x = u64::MAX;
-    /// x as f64;
+    /// let _ = x as f64;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub CAST_PRECISION_LOSS,
@@ -68,7 +68,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// let y: i8 = -1;
-    /// y as u128; // will return 18446744073709551615
+    /// let _ = y as u128; // will return 18446744073709551615
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub CAST_SIGN_LOSS,
@@ -131,7 +131,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// u32::MAX as i32; // will yield a value of `-1`
+    /// let _ = u32::MAX as i32; // will yield a value of `-1`
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub CAST_POSSIBLE_WRAP,
@@ -159,7 +159,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Using `::from` would look like this:
+    /// Use instead:
     ///
     /// ```rust
     /// fn as_u64(x: u8) -> u64 {
@@ -186,7 +186,7 @@ declare_clippy_lint! {
     /// let _ = 0.5 as f32;
     /// ```
     ///
-    /// Better:
+    /// Use instead:
     ///
     /// ```rust
     /// let _ = 2_i32;
@@ -469,7 +469,9 @@ declare_clippy_lint! {
     ///     println!("{:?}", &*p);
     /// }
     /// ```
-    /// Instead use `ptr::slice_from_raw_parts` to construct a slice from a data pointer and the correct length
+    /// Construct a slice from a data pointer and the correct length with `ptr::slice_from_raw_parts`.
+    ///
+    /// Use instead:
     /// ```rust
     /// let a = [1_i32, 2, 3, 4];
     /// let old_ptr = &a as *const [i32];
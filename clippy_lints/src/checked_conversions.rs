@@ -20,15 +20,15 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let foo: u32 = 5;
-    /// foo <= i32::MAX as u32;
+    /// # let value: u32 = 5;
+    /// let _ = value <= i32::MAX as u32;
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # let foo = 1;
+    /// # let value: u32 = 1;
     /// # #[allow(unused)]
-    /// i32::try_from(foo).is_ok();
+    /// i32::try_from(value).is_ok();
     /// ```
     #[clippy::version = "1.37.0"]
     pub CHECKED_CONVERSIONS,
@@ -1,5 +1,6 @@
 //! calculate cognitive complexity and warn about overly complex functions
 
+use crate::utils::conf::PathOverrides;
 use clippy_utils::diagnostics::span_lint_and_help;
 use clippy_utils::source::snippet_opt;
 use clippy_utils::ty::is_type_diagnostic_item;
@@ -36,13 +37,27 @@ declare_clippy_lint! {
 
 pub struct CognitiveComplexity {
     limit: LimitStack,
+    overrides: PathOverrides,
 }
 
 impl CognitiveComplexity {
     #[must_use]
-    pub fn new(limit: u64) -> Self {
+    pub fn new(limit: u64, overrides: PathOverrides) -> Self {
         Self {
             limit: LimitStack::new(limit),
+            overrides,
+        }
+    }
+
+    /// The limit set by a `#[clippy::cognitive_complexity]` attribute or, failing that, by an
+    /// `[[overrides]]` section matching the file.
+    fn limit(&self, cx: &LateContext<'_>, span: Span) -> u64 {
+        if self.limit.is_set_by_attr() {
+            self.limit.limit()
+        } else {
+            self.overrides
+                .cognitive_complexity_threshold(cx.sess(), span)
+                .unwrap_or_else(|| self.limit.limit())
         }
     }
 }
@@ -95,7 +110,8 @@ impl CognitiveComplexity {
             cc -= ret_adjust;
         }
 
-        if cc > self.limit.limit() {
+        let limit = self.limit(cx, body_span);
+        if cc > limit {
             let fn_span = match kind {
                 FnKind::ItemFn(ident, _, _) | FnKind::Method(ident, _) => ident.span,
                 FnKind::Closure => {
@@ -121,10 +137,7 @@ impl CognitiveComplexity {
                 cx,
                 COGNITIVE_COMPLEXITY,
                 fn_span,
-                &format!(
-                    "the function has a cognitive complexity of ({cc}/{})",
-                    self.limit.limit()
-                ),
+                &format!("the function has a cognitive complexity of ({cc}/{limit})"),
                 None,
                 "you could split it up into multiple smaller functions",
             );
@@ -0,0 +1,187 @@
+//! The lints declared in the `[[custom-lints]]` sections of the configuration. Unlike the other
+//! lints, they are declared when the configuration is read, each with the name, level and message
+//! of its section.
+
+use crate::utils::conf::{CustomLint, CustomLintPattern};
+use clippy_utils::diagnostics::span_lint;
+use clippy_utils::ty::implements_trait;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{def_path_def_ids, fn_def_id, get_trait_def_id, peel_hir_expr_while};
+use core::ops::ControlFlow;
+use rustc_hir::def_id::{DefId, DefIdSet};
+use rustc_hir::{Body, Expr, ExprKind, GeneratorKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintContext, LintPass, LintStore};
+use rustc_session::Session;
+
+/// Declares the lints of `custom_lints` in `store`, returning them along with their section.
+pub fn declare_lints(
+    store: &mut LintStore,
+    sess: &Session,
+    custom_lints: &[CustomLint],
+) -> Vec<(&'static Lint, CustomLint)> {
+    let mut declared = Vec::new();
+    for custom_lint in custom_lints {
+        let name = custom_lint.name.get_ref();
+        let name = name.strip_prefix("clippy::").unwrap_or(name);
+        let name = format!("clippy::{}", name.replace('-', "_").to_ascii_uppercase());
+        // declaring a lint twice is a bug in rustc, the names are checked when reading the
+        // configuration but another tool could declare one of them first
+        if store.find_lints(&name.to_ascii_lowercase()).is_ok() {
+            sess.err(format!(
+                "error reading Clippy's configuration file: the custom lint `{}` is already declared",
+                custom_lint.name.get_ref()
+            ));
+            continue;
+        }
+
+        // the lint store only takes `'static` lints
+        let lint: &'static Lint = Box::leak(Box::new(Lint {
+            name: Box::leak(name.into_boxed_str()),
+            default_level: custom_lint.level.as_level(),
+            desc: Box::leak(custom_lint.message.clone().into_boxed_str()),
+            report_in_external_macro: true,
+            is_plugin: true,
+            ..Lint::default_fields_for_macro()
+        }));
+        store.register_lints(&[lint]);
+        declared.push((lint, custom_lint.clone()));
+    }
+    declared
+}
+
+struct Rule {
+    lint: &'static Lint,
+    message: String,
+    pattern: CustomLintPattern,
+    /// The functions and methods at the path of `pattern.call`
+    callees: DefIdSet,
+    /// The trait at the path of `pattern.receiver_implements`
+    receiver_trait: Option<DefId>,
+}
+
+pub struct CustomLints {
+    rules: Vec<Rule>,
+}
+
+impl CustomLints {
+    pub fn new(custom_lints: Vec<(&'static Lint, CustomLint)>) -> Self {
+        Self {
+            rules: custom_lints
+                .into_iter()
+                .map(|(lint, custom_lint)| Rule {
+                    lint,
+                    message: custom_lint.message,
+                    pattern: custom_lint.pattern,
+                    callees: DefIdSet::default(),
+                    receiver_trait: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Lints `expr` if it's a call matching the patterns of the rules, `in_async` telling whether
+    /// it's directly in the body of an `async` function, block or closure. Only the rules with an
+    /// `in-async` pattern are checked in that case, the other rules are checked for every
+    /// expression by `check_expr`.
+    fn check_call<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, in_async: bool) {
+        let (receiver, args) = match expr.kind {
+            ExprKind::Call(_, args) => (args.first(), args),
+            ExprKind::MethodCall(_, receiver, args, _) => (Some(receiver), args),
+            _ => return,
+        };
+        for rule in self.rules.iter().filter(|rule| rule.pattern.in_async == in_async) {
+            if rule.matches(cx, expr, receiver, args) {
+                span_lint(cx, rule.lint, expr.span, &rule.message);
+            }
+        }
+    }
+}
+
+impl LintPass for CustomLints {
+    fn name(&self) -> &'static str {
+        "CustomLints"
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for CustomLints {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        for rule in &mut self.rules {
+            if let Some(path) = &rule.pattern.call {
+                let segs: Vec<_> = path.split("::").collect();
+                rule.callees = def_path_def_ids(cx, &segs).collect();
+            }
+            if let Some(path) = &rule.pattern.receiver_implements {
+                let segs: Vec<_> = path.split("::").collect();
+                rule.receiver_trait = get_trait_def_id(cx, &segs);
+                // the generic parameters of the trait can't be given in the pattern, `Self` is
+                // one of them
+                if let Some(trait_id) = rule.receiver_trait
+                    && cx.tcx.generics_of(trait_id).count() != 1
+                {
+                    cx.sess().err(format!(
+                        "error reading Clippy's configuration file: the trait `{path}` of the custom lint `{}` \
+                         has generic parameters, which `receiver-implements` doesn't support",
+                        rule.lint.name_lower().trim_start_matches("clippy::"),
+                    ));
+                    rule.receiver_trait = None;
+                }
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        self.check_call(cx, expr, false);
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>) {
+        if !matches!(body.generator_kind, Some(GeneratorKind::Async(_)))
+            || !self.rules.iter().any(|rule| rule.pattern.in_async)
+        {
+            return;
+        }
+        // the closures and items nested in the body have their own body, an `async` closure
+        // nested in a non-`async` one is checked when its body is
+        let _: Option<!> = for_each_expr(body.value, |e| {
+            self.check_call(cx, e, true);
+            ControlFlow::Continue(())
+        });
+    }
+}
+
+impl Rule {
+    fn matches<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        expr: &'tcx Expr<'tcx>,
+        receiver: Option<&Expr<'_>>,
+        args: &'tcx [Expr<'tcx>],
+    ) -> bool {
+        let pattern = &self.pattern;
+        let callee_matches = match (&pattern.method, expr.kind) {
+            (Some(method), ExprKind::MethodCall(path, ..)) => path.ident.as_str() == method,
+            (Some(_), _) => false,
+            (None, _) => fn_def_id(cx, expr).map_or(false, |def_id| self.callees.contains(&def_id)),
+        };
+        callee_matches
+            && (pattern.receiver_implements.is_none() || self.receiver_matches(cx, receiver))
+            && (!pattern.literal_argument || args.iter().any(is_literal))
+    }
+
+    /// Checks if `receiver` implements the trait of `pattern.receiver_implements`.
+    fn receiver_matches(&self, cx: &LateContext<'_>, receiver: Option<&Expr<'_>>) -> bool {
+        let (Some(receiver), Some(trait_id)) = (receiver, self.receiver_trait) else {
+            return false;
+        };
+        let ty = cx.typeck_results().expr_ty(receiver);
+        implements_trait(cx, ty, trait_id, &[]) || implements_trait(cx, ty.peel_refs(), trait_id, &[])
+    }
+}
+
+/// Checks if `expr` is a literal, possibly negated or borrowed.
+fn is_literal<'tcx>(expr: &'tcx Expr<'tcx>) -> bool {
+    let expr = peel_hir_expr_while(expr, |e| match e.kind {
+        ExprKind::Unary(UnOp::Neg, inner) | ExprKind::AddrOf(_, _, inner) => Some(inner),
+        _ => None,
+    });
+    matches!(expr.kind, ExprKind::Lit(_))
+}
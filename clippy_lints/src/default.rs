@@ -53,7 +53,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```
     /// # #[derive(Default)]
-    /// # struct A { i: i32 }
+    /// # struct A { i: i32, j: i32 }
     /// let mut a: A = Default::default();
     /// a.i = 42;
     /// ```
@@ -61,7 +61,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```
     /// # #[derive(Default)]
-    /// # struct A { i: i32 }
+    /// # struct A { i: i32, j: i32 }
     /// let a = A {
     ///     i: 42,
     ///     .. Default::default()
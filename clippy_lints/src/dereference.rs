@@ -106,7 +106,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let x = Some("");
     /// if let Some(ref x) = x {
-    ///     // use `x` here
+    ///     let y: &&str = x;
     /// }
     /// ```
     ///
@@ -114,7 +114,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let x = Some("");
     /// if let Some(x) = x {
-    ///     // use `&x` here
+    ///     let y: &&str = &x;
     /// }
     /// ```
     #[clippy::version = "1.54.0"]
@@ -176,7 +176,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// #[derive(PartialEq)]
-    /// struct Foo {
+    /// pub struct Foo {
     ///     i_am_eq: i32,
     ///     i_am_eq_too: Vec<String>,
     /// }
@@ -184,7 +184,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust
     /// #[derive(PartialEq, Eq)]
-    /// struct Foo {
+    /// pub struct Foo {
     ///     i_am_eq: i32,
     ///     i_am_eq_too: Vec<String>,
     /// }
@@ -0,0 +1,203 @@
+use clippy_utils::def_path_def_ids;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::path_patterns::glob_matches;
+
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefIdMap, DefIdSet, LOCAL_CRATE};
+use rustc_hir::{Impl, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, GenericArgKind, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+use std::iter;
+
+use crate::utils::conf;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the configured trait implementations in clippy.toml, whether
+    /// they are written by hand or derived.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// trait implementations are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some traits should not be implemented for some types, e.g. `Clone` or
+    /// `Debug` for types holding secrets.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-impls = [
+    ///     # Disallows implementing the trait for any type.
+    ///     { path = "std::ops::Drop" },
+    ///     # Can restrict the rule to the types matching `self-type`, here every
+    ///     # type of the `secrets` module, and add a `reason`.
+    ///     { path = "std::clone::Clone", self-type = "crate::secrets", reason = "secrets must not be copied" },
+    ///     # Can restrict the rule to the types with a field of `field-type`.
+    ///     { path = "std::fmt::Debug", field-type = "crate::secrets::SecretString" },
+    ///     # Can suggest a trait to implement instead with `replacement`.
+    ///     { path = "std::hash::Hash", replacement = "crate::hashing::StableHash" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust
+    /// # mod secrets {
+    /// #     #[derive(Debug)]
+    /// #     pub struct SecretString(String);
+    /// # }
+    /// # use secrets::SecretString;
+    /// #[derive(Debug)]
+    /// struct Credentials {
+    ///     user: String,
+    ///     password: SecretString,
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// # mod secrets {
+    /// #     #[derive(Debug)]
+    /// #     pub struct SecretString(String);
+    /// # }
+    /// # use secrets::SecretString;
+    /// struct Credentials {
+    ///     user: String,
+    ///     password: SecretString,
+    /// }
+    /// ```
+    #[clippy::version = "1.69.0"]
+    pub DISALLOWED_IMPLS,
+    style,
+    "implementation of a disallowed trait"
+}
+
+#[derive(Clone, Debug)]
+pub struct DisallowedImpls {
+    conf_disallowed: Vec<conf::DisallowedImpl>,
+    /// The indices in `conf_disallowed` of the rules of each trait
+    disallowed: DefIdMap<Vec<usize>>,
+    /// The types at the `field-type` paths, by index in `conf_disallowed`
+    field_types: Vec<DefIdSet>,
+}
+
+impl DisallowedImpls {
+    pub fn new(conf_disallowed: Vec<conf::DisallowedImpl>) -> Self {
+        Self {
+            conf_disallowed,
+            disallowed: DefIdMap::default(),
+            field_types: Vec::new(),
+        }
+    }
+
+    /// Checks if the rule at `index` applies to the implementations for `self_ty`.
+    fn applies_to(&self, cx: &LateContext<'_>, index: usize, self_ty: Ty<'_>) -> bool {
+        let conf = &self.conf_disallowed[index];
+        conf.self_type
+            .as_ref()
+            .map_or(true, |pattern| self_type_matches(cx, pattern, self_ty))
+            && (conf.field_type.is_none() || has_field_of_type(cx, self_ty, &self.field_types[index]))
+    }
+}
+
+impl_lint_pass!(DisallowedImpls => [DISALLOWED_IMPLS]);
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedImpls {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        for (index, conf) in self.conf_disallowed.iter().enumerate() {
+            let segs: Vec<_> = conf.path.split("::").collect();
+            // the paths of derivable traits also resolve to their derive macro
+            for id in def_path_def_ids(cx, &segs).filter(|&id| cx.tcx.def_kind(id) == DefKind::Trait) {
+                self.disallowed.entry(id).or_default().push(index);
+            }
+            let field_types = match &conf.field_type {
+                Some(path) => {
+                    let segs: Vec<_> = path.split("::").collect();
+                    def_path_def_ids(cx, &segs).collect()
+                },
+                None => DefIdSet::default(),
+            };
+            self.field_types.push(field_types);
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        let ItemKind::Impl(Impl { of_trait: Some(trait_ref), .. }) = item.kind else {
+            return
+        };
+        let Some(trait_id) = trait_ref.trait_def_id() else {
+            return
+        };
+        let Some(indices) = self.disallowed.get(&trait_id) else {
+            return
+        };
+        let self_ty = cx.tcx.type_of(item.owner_id);
+        let Some(conf) = indices
+            .iter()
+            .find(|&&index| self.applies_to(cx, index, self_ty))
+            .map(|&index| &self.conf_disallowed[index]) else {
+            return
+        };
+        if conf.is_allowed_at(cx, item.hir_id(), item.span) {
+            return;
+        }
+
+        let trait_name = cx.tcx.def_path_str(trait_id);
+        let self_name = match self_ty.kind() {
+            ty::Adt(adt, _) => cx.tcx.def_path_str(adt.did()),
+            _ => self_ty.to_string(),
+        };
+        let msg = if cx.tcx.has_attr(item.owner_id.to_def_id(), sym::automatically_derived) {
+            format!("deriving `{trait_name}` for `{self_name}` is not allowed according to config")
+        } else {
+            format!("implementing `{trait_name}` for `{self_name}` is not allowed according to config")
+        };
+        span_lint_and_then(cx, DISALLOWED_IMPLS, item.span, &msg, |diag| {
+            if let Some(reason) = conf.reason() {
+                diag.note(reason);
+            }
+            if let Some(replacement) = &conf.replacement {
+                diag.help(format!("implement `{replacement}` instead"));
+            }
+        });
+    }
+}
+
+/// Checks if `ty` is a type defined at a path matching `pattern`, like `crate::secrets` or
+/// `crate::*::Secret*`. Each segment of the pattern is a glob matched against a segment of the
+/// path, and a pattern matching a module matches every type in it.
+fn self_type_matches(cx: &LateContext<'_>, pattern: &str, ty: Ty<'_>) -> bool {
+    let ty::Adt(adt, _) = ty.peel_refs().kind() else {
+        return false
+    };
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    let path = cx.get_def_path(adt.did());
+    let pattern: Vec<_> = pattern.split("::").collect();
+    pattern.len() <= path.len()
+        && iter::zip(&pattern, &path).enumerate().all(|(i, (pattern, segment))| {
+            if i == 0 && *pattern == "crate" {
+                *segment == crate_name
+            } else {
+                glob_matches(pattern, segment.as_str())
+            }
+        })
+}
+
+/// Checks if `ty`, or the type it references, has a field whose type contains one of the types of
+/// `field_types`, e.g. a field of type `Option<T>` or `&T` for the type `T`.
+fn has_field_of_type(cx: &LateContext<'_>, ty: Ty<'_>, field_types: &DefIdSet) -> bool {
+    let ty::Adt(adt, _) = ty.peel_refs().kind() else {
+        return false
+    };
+    adt.all_fields().any(|field| {
+        cx.tcx
+            .type_of(field.did)
+            .walk()
+            .any(|arg| matches!(arg.unpack(), GenericArgKind::Type(ty) if is_one_of(ty, field_types)))
+    })
+}
+
+fn is_one_of(ty: Ty<'_>, types: &DefIdSet) -> bool {
+    matches!(ty.kind(), ty::Adt(adt, _) if types.contains(&adt.did()))
+}
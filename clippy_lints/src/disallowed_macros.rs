@@ -31,21 +31,15 @@ declare_clippy_lint! {
     ///     { path = "std::println" },
     ///     # When using an inline table, can add a `reason` for why the macro
     ///     # is disallowed.
-    ///     { path = "serde::Serialize", reason = "no serializing" },
+    ///     { path = "std::eprintln", reason = "no printing to stderr" },
     /// ]
     /// ```
-    /// ```
-    /// use serde::Serialize;
-    ///
+    /// ```rust
     /// // Example code where clippy issues a warning
     /// println!("warns");
     ///
-    /// // The diagnostic will contain the message "no serializing"
-    /// #[derive(Serialize)]
-    /// struct Data {
-    ///     name: String,
-    ///     value: usize,
-    /// }
+    /// // The diagnostic will contain the message "no printing to stderr"
+    /// eprintln!("warns too");
     /// ```
     #[clippy::version = "1.66.0"]
     pub DISALLOWED_MACROS,
@@ -54,13 +48,13 @@ declare_clippy_lint! {
 }
 
 pub struct DisallowedMacros {
-    conf_disallowed: Vec<conf::DisallowedPath>,
+    conf_disallowed: Vec<conf::DisallowedPath<false>>,
     disallowed: DefIdMap<usize>,
     seen: FxHashSet<ExpnId>,
 }
 
 impl DisallowedMacros {
-    pub fn new(conf_disallowed: Vec<conf::DisallowedPath>) -> Self {
+    pub fn new(conf_disallowed: Vec<conf::DisallowedPath<false>>) -> Self {
         Self {
             conf_disallowed,
             disallowed: DefIdMap::default(),
@@ -68,7 +62,7 @@ impl DisallowedMacros {
         }
     }
 
-    fn check(&mut self, cx: &LateContext<'_>, span: Span) {
+    fn check(&mut self, cx: &LateContext<'_>, hir_id: HirId, span: Span) {
         if self.conf_disallowed.is_empty() {
             return;
         }
@@ -80,6 +74,9 @@ impl DisallowedMacros {
 
             if let Some(&index) = self.disallowed.get(&mac.def_id) {
                 let conf = &self.conf_disallowed[index];
+                if conf.is_allowed_at(cx, hir_id, mac.span) {
+                    continue;
+                }
 
                 span_lint_and_then(
                     cx,
@@ -110,41 +107,41 @@ impl LateLintPass<'_> for DisallowedMacros {
     }
 
     fn check_expr(&mut self, cx: &LateContext<'_>, expr: &Expr<'_>) {
-        self.check(cx, expr.span);
+        self.check(cx, expr.hir_id, expr.span);
     }
 
     fn check_stmt(&mut self, cx: &LateContext<'_>, stmt: &Stmt<'_>) {
-        self.check(cx, stmt.span);
+        self.check(cx, stmt.hir_id, stmt.span);
     }
 
     fn check_ty(&mut self, cx: &LateContext<'_>, ty: &Ty<'_>) {
-        self.check(cx, ty.span);
+        self.check(cx, ty.hir_id, ty.span);
     }
 
     fn check_pat(&mut self, cx: &LateContext<'_>, pat: &Pat<'_>) {
-        self.check(cx, pat.span);
+        self.check(cx, pat.hir_id, pat.span);
     }
 
     fn check_item(&mut self, cx: &LateContext<'_>, item: &Item<'_>) {
-        self.check(cx, item.span);
-        self.check(cx, item.vis_span);
+        self.check(cx, item.hir_id(), item.span);
+        self.check(cx, item.hir_id(), item.vis_span);
     }
 
     fn check_foreign_item(&mut self, cx: &LateContext<'_>, item: &ForeignItem<'_>) {
-        self.check(cx, item.span);
-        self.check(cx, item.vis_span);
+        self.check(cx, item.hir_id(), item.span);
+        self.check(cx, item.hir_id(), item.vis_span);
     }
 
     fn check_impl_item(&mut self, cx: &LateContext<'_>, item: &ImplItem<'_>) {
-        self.check(cx, item.span);
-        self.check(cx, item.vis_span);
+        self.check(cx, item.hir_id(), item.span);
+        self.check(cx, item.hir_id(), item.vis_span);
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'_>, item: &TraitItem<'_>) {
-        self.check(cx, item.span);
+        self.check(cx, item.hir_id(), item.span);
     }
 
-    fn check_path(&mut self, cx: &LateContext<'_>, path: &Path<'_>, _: HirId) {
-        self.check(cx, path.span);
+    fn check_path(&mut self, cx: &LateContext<'_>, path: &Path<'_>, hir_id: HirId) {
+        self.check(cx, hir_id, path.span);
     }
 }
@@ -1,9 +1,13 @@
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::{fn_def_id, get_parent_expr, path_def_id};
+use clippy_utils::{def_path_def_ids, fn_def_id, get_parent_expr, path_def_id};
 
-use rustc_hir::def_id::DefIdMap;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Applicability;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, DefIdMap};
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::PolyFnSig;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 
 use crate::utils::conf;
@@ -31,6 +35,9 @@ declare_clippy_lint! {
     ///     # When using an inline table, can add a `reason` for why the method
     ///     # is disallowed.
     ///     { path = "std::vec::Vec::leak", reason = "no leaking memory" },
+    ///     # Can allow the method in some modules or files with `allowed-in`, and
+    ///     # suggest a function to call instead with `replacement`.
+    ///     { path = "std::env::var", allowed-in = ["crate::config"], replacement = "crate::config::env_var" },
     /// ]
     /// ```
     ///
@@ -61,6 +68,8 @@ declare_clippy_lint! {
 pub struct DisallowedMethods {
     conf_disallowed: Vec<conf::DisallowedPath>,
     disallowed: DefIdMap<usize>,
+    /// The functions at the `replacement` paths, by index in `conf_disallowed`
+    replacements: FxHashMap<usize, DefId>,
 }
 
 impl DisallowedMethods {
@@ -68,6 +77,7 @@ impl DisallowedMethods {
         Self {
             conf_disallowed,
             disallowed: DefIdMap::default(),
+            replacements: FxHashMap::default(),
         }
     }
 }
@@ -78,9 +88,17 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
     fn check_crate(&mut self, cx: &LateContext<'_>) {
         for (index, conf) in self.conf_disallowed.iter().enumerate() {
             let segs: Vec<_> = conf.path().split("::").collect();
-            for id in clippy_utils::def_path_def_ids(cx, &segs) {
+            for id in def_path_def_ids(cx, &segs) {
                 self.disallowed.insert(id, index);
             }
+            if let Some(replacement) = conf.replacement() {
+                let segs: Vec<_> = replacement.split("::").collect();
+                if let Some(id) = def_path_def_ids(cx, &segs)
+                    .find(|&id| matches!(cx.tcx.def_kind(id), DefKind::Fn | DefKind::AssocFn))
+                {
+                    self.replacements.insert(index, id);
+                }
+            }
         }
     }
 
@@ -96,15 +114,48 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
         let Some(def_id) = uncalled_path.or_else(|| fn_def_id(cx, expr)) else {
             return
         };
-        let conf = match self.disallowed.get(&def_id) {
-            Some(&index) => &self.conf_disallowed[index],
-            None => return,
+        let Some(&index) = self.disallowed.get(&def_id) else {
+            return
+        };
+        let conf = &self.conf_disallowed[index];
+        if conf.is_allowed_at(cx, expr.hir_id, expr.span) {
+            return;
+        }
+        // the path to replace with the one of `replacement`, `None` for method calls
+        let replaced_path = match expr.kind {
+            ExprKind::Call(callee, _) if matches!(callee.kind, ExprKind::Path(_)) => Some(callee),
+            ExprKind::Path(_) => Some(expr),
+            _ => None,
         };
         let msg = format!("use of a disallowed method `{}`", conf.path());
         span_lint_and_then(cx, DISALLOWED_METHODS, expr.span, &msg, |diag| {
             if let Some(reason) = conf.reason() {
                 diag.note(reason);
             }
+            let Some(replacement) = conf.replacement() else {
+                return
+            };
+            // a path written in a macro can't be replaced without changing the macro
+            if let Some(path) = replaced_path.filter(|path| !path.span.from_expansion()) {
+                let applicability = match self.replacements.get(&index) {
+                    Some(&id) if same_signature(cx, path, id) => Applicability::MachineApplicable,
+                    _ => Applicability::MaybeIncorrect,
+                };
+                diag.span_suggestion_verbose(path.span, "use instead", replacement, applicability);
+            } else {
+                diag.help(format!("use `{replacement}` instead"));
+            }
         });
     }
 }
+
+/// Checks if the function `replacement` has the same signature as the function at `path`, with
+/// the generic arguments it was given. Generic replacements are never considered the same.
+fn same_signature<'tcx>(cx: &LateContext<'tcx>, path: &Expr<'_>, replacement: DefId) -> bool {
+    let ty = cx.typeck_results().expr_ty(path);
+    if !ty.is_fn() || cx.tcx.generics_of(replacement).count() != 0 {
+        return false;
+    }
+    let erase = |sig: PolyFnSig<'tcx>| cx.tcx.erase_regions(cx.tcx.erase_late_bound_regions(sig));
+    erase(ty.fn_sig(cx.tcx)) == erase(cx.tcx.fn_sig(replacement))
+}
@@ -15,7 +15,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// let foo = 3.14;
+    /// let foo = 42;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub DISALLOWED_NAMES,
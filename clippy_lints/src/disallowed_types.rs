@@ -3,7 +3,7 @@ use clippy_utils::diagnostics::span_lint_and_then;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def::Res;
 use rustc_hir::def_id::DefId;
-use rustc_hir::{Item, ItemKind, PolyTraitRef, PrimTy, Ty, TyKind, UseKind};
+use rustc_hir::{HirId, Item, ItemKind, PolyTraitRef, PrimTy, Ty, TyKind, UseKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::Span;
@@ -66,16 +66,22 @@ impl DisallowedTypes {
         }
     }
 
-    fn check_res_emit(&self, cx: &LateContext<'_>, res: &Res, span: Span) {
+    fn check_res_emit(&self, cx: &LateContext<'_>, res: &Res, hir_id: HirId, span: Span) {
         match res {
             Res::Def(_, did) => {
                 if let Some(&index) = self.def_ids.get(did) {
-                    emit(cx, &cx.tcx.def_path_str(*did), span, &self.conf_disallowed[index]);
+                    emit(
+                        cx,
+                        &cx.tcx.def_path_str(*did),
+                        hir_id,
+                        span,
+                        &self.conf_disallowed[index],
+                    );
                 }
             },
             Res::PrimTy(prim) => {
                 if let Some(&index) = self.prim_tys.get(prim) {
-                    emit(cx, prim.name_str(), span, &self.conf_disallowed[index]);
+                    emit(cx, prim.name_str(), hir_id, span, &self.conf_disallowed[index]);
                 }
             },
             _ => {},
@@ -107,23 +113,31 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedTypes {
     fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
         if let ItemKind::Use(path, UseKind::Single) = &item.kind {
             for res in &path.res {
-                self.check_res_emit(cx, res, item.span);
+                self.check_res_emit(cx, res, item.hir_id(), item.span);
             }
         }
     }
 
     fn check_ty(&mut self, cx: &LateContext<'tcx>, ty: &'tcx Ty<'tcx>) {
         if let TyKind::Path(path) = &ty.kind {
-            self.check_res_emit(cx, &cx.qpath_res(path, ty.hir_id), ty.span);
+            self.check_res_emit(cx, &cx.qpath_res(path, ty.hir_id), ty.hir_id, ty.span);
         }
     }
 
     fn check_poly_trait_ref(&mut self, cx: &LateContext<'tcx>, poly: &'tcx PolyTraitRef<'tcx>) {
-        self.check_res_emit(cx, &poly.trait_ref.path.res, poly.trait_ref.path.span);
+        self.check_res_emit(
+            cx,
+            &poly.trait_ref.path.res,
+            poly.trait_ref.hir_ref_id,
+            poly.trait_ref.path.span,
+        );
     }
 }
 
-fn emit(cx: &LateContext<'_>, name: &str, span: Span, conf: &conf::DisallowedPath) {
+fn emit(cx: &LateContext<'_>, name: &str, hir_id: HirId, span: Span, conf: &conf::DisallowedPath) {
+    if conf.is_allowed_at(cx, hir_id, span) {
+        return;
+    }
     span_lint_and_then(
         cx,
         DISALLOWED_TYPES,
@@ -133,6 +147,11 @@ fn emit(cx: &LateContext<'_>, name: &str, span: Span, conf: &conf::DisallowedPat
             if let Some(reason) = conf.reason() {
                 diag.note(reason);
             }
+            // the span can cover a whole `use` item or generic arguments, so the replacement is
+            // only mentioned
+            if let Some(replacement) = conf.replacement() {
+                diag.help(format!("use `{replacement}` instead"));
+            }
         },
     );
 }
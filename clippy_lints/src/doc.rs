@@ -92,7 +92,9 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// At least write a line about safety:
+    /// At least write a line about safety.
+    ///
+    /// Use instead:
     ///
     /// ```rust
     ///# type Universe = ();
@@ -119,11 +121,21 @@ declare_clippy_lint! {
     /// function can help callers write code to handle the errors appropriately.
     ///
     /// ### Examples
-    /// Since the following function returns a `Result` it has an `# Errors` section in
-    /// its doc comment:
+    /// ```rust
+    ///# use std::io;
+    /// /// Reads the file at `filename` into a string.
+    /// pub fn read(filename: String) -> io::Result<String> {
+    ///     unimplemented!();
+    /// }
+    /// ```
+    ///
+    /// Since the function returns a `Result`, its doc comment should have an `# Errors` section.
     ///
+    /// Use instead:
     /// ```rust
     ///# use std::io;
+    /// /// Reads the file at `filename` into a string.
+    /// ///
     /// /// # Errors
     /// ///
     /// /// Will return `Err` if `filename` does not exist or the user does not have
@@ -148,10 +160,23 @@ declare_clippy_lint! {
     /// can help callers who do not want to panic to avoid those situations.
     ///
     /// ### Examples
-    /// Since the following function may panic it has a `# Panics` section in
-    /// its doc comment:
+    /// ```rust
+    /// /// Divides `x` by `y`.
+    /// pub fn divide_by(x: i32, y: i32) -> i32 {
+    ///     if y == 0 {
+    ///         panic!("Cannot divide by 0")
+    ///     } else {
+    ///         x / y
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Since the function may panic, its doc comment should have a `# Panics` section.
     ///
+    /// Use instead:
     /// ```rust
+    /// /// Divides `x` by `y`.
+    /// ///
     /// /// # Panics
     /// ///
     /// /// Will panic if y is 0
@@ -244,6 +269,8 @@ declare_clippy_lint! {
     /// The function is safe, so there shouldn't be any preconditions
     /// that have to be explained for safety reasons.
     ///
+    /// Use instead:
+    ///
     /// ```rust
     ///# type Universe = ();
     /// /// This function should really be documented
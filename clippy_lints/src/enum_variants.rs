@@ -57,14 +57,14 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// mod cake {
-    ///     struct BlackForestCake;
+    ///     pub struct BlackForestCake;
     /// }
     /// ```
     ///
     /// Use instead:
     /// ```rust
     /// mod cake {
-    ///     struct BlackForest;
+    ///     pub struct BlackForest;
     /// }
     /// ```
     #[clippy::version = "1.33.0"]
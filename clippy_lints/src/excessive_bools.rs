@@ -26,6 +26,7 @@ declare_clippy_lint! {
     ///     is_pending: bool,
     ///     is_processing: bool,
     ///     is_finished: bool,
+    ///     is_cancelled: bool,
     /// }
     /// ```
     ///
@@ -35,6 +36,7 @@ declare_clippy_lint! {
     ///     Pending,
     ///     Processing,
     ///     Finished,
+    ///     Cancelled,
     /// }
     /// ```
     #[clippy::version = "1.43.0"]
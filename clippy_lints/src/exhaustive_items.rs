@@ -18,7 +18,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// enum Foo {
+    /// pub enum Foo {
     ///     Bar,
     ///     Baz
     /// }
@@ -26,7 +26,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust
     /// #[non_exhaustive]
-    /// enum Foo {
+    /// pub enum Foo {
     ///     Bar,
     ///     Baz
     /// }
@@ -48,17 +48,17 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// struct Foo {
-    ///     bar: u8,
-    ///     baz: String,
+    /// pub struct Foo {
+    ///     pub bar: u8,
+    ///     pub baz: String,
     /// }
     /// ```
     /// Use instead:
     /// ```rust
     /// #[non_exhaustive]
-    /// struct Foo {
-    ///     bar: u8,
-    ///     baz: String,
+    /// pub struct Foo {
+    ///     pub bar: u8,
+    ///     pub baz: String,
     /// }
     /// ```
     #[clippy::version = "1.51.0"]
@@ -17,7 +17,9 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```
-    /// std::process::exit(0)
+    /// fn quit() {
+    ///     std::process::exit(0)
+    /// }
     /// ```
     ///
     /// Use instead:
@@ -22,8 +22,8 @@ declare_clippy_lint! {
     /// ```rust
     /// # use std::io::Write;
     /// # let bar = "furchtbar";
-    /// writeln!(&mut std::io::stderr(), "foo: {:?}", bar).unwrap();
-    /// writeln!(&mut std::io::stdout(), "foo: {:?}", bar).unwrap();
+    /// writeln!(std::io::stderr(), "foo: {:?}", bar).unwrap();
+    /// writeln!(std::io::stdout(), "foo: {:?}", bar).unwrap();
     /// ```
     ///
     /// Use instead:
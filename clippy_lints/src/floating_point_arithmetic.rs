@@ -82,7 +82,7 @@ declare_clippy_lint! {
     /// };
     /// ```
     ///
-    /// is better expressed as
+    /// Use instead:
     ///
     /// ```rust
     /// use std::f32::consts::E;
@@ -25,14 +25,14 @@ declare_clippy_lint! {
     ///
     /// ### Examples
     /// ```rust
-    /// let foo = "foo";
-    /// format!("{}", foo);
+    /// let value = "foo";
+    /// format!("{}", value);
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// let foo = "foo";
-    /// foo.to_owned();
+    /// let value = "foo";
+    /// value.to_owned();
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub USELESS_FORMAT,
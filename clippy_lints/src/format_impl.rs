@@ -20,6 +20,7 @@ declare_clippy_lint! {
     /// ### Example
     ///
     /// ```rust
+    /// # #![allow(clippy::to_string_in_format_args)]
     /// use std::fmt;
     ///
     /// struct Structure(i32);
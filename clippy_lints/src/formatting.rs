@@ -38,17 +38,17 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let foo = true;
-    /// # let bar = false;
+    /// # let a = true;
+    /// # let b = false;
     /// // &&! looks like a different operator
-    /// if foo &&! bar {}
+    /// if a &&! b {}
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # let foo = true;
-    /// # let bar = false;
-    /// if foo && !bar {}
+    /// # let a = true;
+    /// # let b = false;
+    /// if a && !b {}
     /// ```
     #[clippy::version = "1.40.0"]
     pub SUSPICIOUS_UNARY_OP_FORMATTING,
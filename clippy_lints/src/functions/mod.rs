@@ -5,9 +5,10 @@ mod result;
 mod too_many_arguments;
 mod too_many_lines;
 
+use crate::utils::conf::PathOverrides;
 use rustc_hir as hir;
 use rustc_hir::intravisit;
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::Span;
 
@@ -183,7 +184,7 @@ declare_clippy_lint! {
     /// ### Examples
     /// ```rust
     /// // this could be annotated with `#[must_use]`.
-    /// fn id<T>(t: T) -> T { t }
+    /// pub fn id<T>(t: T) -> T { t }
     /// ```
     #[clippy::version = "1.40.0"]
     pub MUST_USE_CANDIDATE,
@@ -212,7 +213,7 @@ declare_clippy_lint! {
     /// ```rust
     /// pub fn read_u8() -> Result<u8, ()> { Err(()) }
     /// ```
-    /// should become
+    /// Use instead:
     /// ```rust,should_panic
     /// use std::fmt;
     ///
@@ -256,6 +257,7 @@ declare_clippy_lint! {
     ///
     /// ### Examples
     /// ```rust
+    /// # #![allow(clippy::large_enum_variant)]
     /// pub enum ParseError {
     ///     UnparsedBytes([u8; 512]),
     ///     UnexpectedEof,
@@ -266,7 +268,7 @@ declare_clippy_lint! {
     ///     Ok(())
     /// }
     /// ```
-    /// should be
+    /// Use instead:
     /// ```
     /// pub enum ParseError {
     ///     UnparsedBytes(Box<[u8; 512]>),
@@ -326,21 +328,40 @@ declare_clippy_lint! {
     "getter method returning the wrong field"
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct Functions {
     too_many_arguments_threshold: u64,
     too_many_lines_threshold: u64,
     large_error_threshold: u64,
+    overrides: PathOverrides,
 }
 
 impl Functions {
-    pub fn new(too_many_arguments_threshold: u64, too_many_lines_threshold: u64, large_error_threshold: u64) -> Self {
+    pub fn new(
+        too_many_arguments_threshold: u64,
+        too_many_lines_threshold: u64,
+        large_error_threshold: u64,
+        overrides: PathOverrides,
+    ) -> Self {
         Self {
             too_many_arguments_threshold,
             too_many_lines_threshold,
             large_error_threshold,
+            overrides,
         }
     }
+
+    fn too_many_arguments_threshold(&self, cx: &LateContext<'_>, span: Span) -> u64 {
+        self.overrides
+            .too_many_arguments_threshold(cx.sess(), span)
+            .unwrap_or(self.too_many_arguments_threshold)
+    }
+
+    fn too_many_lines_threshold(&self, cx: &LateContext<'_>, span: Span) -> u64 {
+        self.overrides
+            .too_many_lines_threshold(cx.sess(), span)
+            .unwrap_or(self.too_many_lines_threshold)
+    }
 }
 
 impl_lint_pass!(Functions => [
@@ -365,8 +386,10 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
         span: Span,
         hir_id: hir::HirId,
     ) {
-        too_many_arguments::check_fn(cx, kind, decl, span, hir_id, self.too_many_arguments_threshold);
-        too_many_lines::check_fn(cx, kind, span, body, self.too_many_lines_threshold);
+        let too_many_arguments_threshold = self.too_many_arguments_threshold(cx, span);
+        too_many_arguments::check_fn(cx, kind, decl, span, hir_id, too_many_arguments_threshold);
+        let too_many_lines_threshold = self.too_many_lines_threshold(cx, span);
+        too_many_lines::check_fn(cx, kind, span, body, too_many_lines_threshold);
         not_unsafe_ptr_arg_deref::check_fn(cx, kind, decl, body, hir_id);
         misnamed_getters::check_fn(cx, kind, decl, body, span, hir_id);
     }
@@ -382,7 +405,8 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'_>) {
-        too_many_arguments::check_trait_item(cx, item, self.too_many_arguments_threshold);
+        let too_many_arguments_threshold = self.too_many_arguments_threshold(cx, item.span);
+        too_many_arguments::check_trait_item(cx, item, too_many_arguments_threshold);
         not_unsafe_ptr_arg_deref::check_trait_item(cx, item);
         must_use::check_trait_item(cx, item);
         result::check_trait_item(cx, item, self.large_error_threshold);
@@ -29,7 +29,7 @@ declare_clippy_lint! {
     /// };
     /// ```
     ///
-    /// Could be written:
+    /// Use instead:
     ///
     /// ```rust
     /// # let v = vec![0];
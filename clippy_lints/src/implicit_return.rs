@@ -30,8 +30,9 @@ declare_clippy_lint! {
     ///     x
     /// }
     /// ```
-    /// add return
+    /// Use instead:
     /// ```rust
+    /// # #![allow(clippy::needless_return)]
     /// fn foo(x: usize) -> usize {
     ///     return x;
     /// }
@@ -43,7 +43,7 @@ declare_clippy_lint! {
     /// let x = 1;
     /// let y = 2;
     ///
-    /// Foo { y, x };
+    /// let _ = Foo { y, x };
     /// ```
     ///
     /// Use instead:
@@ -54,7 +54,7 @@ declare_clippy_lint! {
     /// # }
     /// # let x = 1;
     /// # let y = 2;
-    /// Foo { x, y };
+    /// let _ = Foo { x, y };
     /// ```
     #[clippy::version = "1.52.0"]
     pub INCONSISTENT_STRUCT_CONSTRUCTOR,
@@ -21,8 +21,8 @@ declare_clippy_lint! {
     /// ```rust,no_run
     /// let x = [1, 2, 3, 4];
     ///
-    /// x[9];
-    /// &x[2..9];
+    /// let _ = x[9];
+    /// let _ = &x[2..9];
     /// ```
     ///
     /// Use instead:
@@ -30,8 +30,8 @@ declare_clippy_lint! {
     /// # let x = [1, 2, 3, 4];
     /// // Index within bounds
     ///
-    /// x[0];
-    /// x[3];
+    /// let _ = x[0];
+    /// let _ = x[3];
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub OUT_OF_BOUNDS_INDEXING,
@@ -51,17 +51,18 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust,no_run
+    /// # #![allow(clippy::out_of_bounds_indexing)]
     /// // Vector
     /// let x = vec![0; 5];
     ///
-    /// x[2];
-    /// &x[2..100];
+    /// let _ = x[2];
+    /// let _ = &x[2..100];
     ///
     /// // Array
     /// let y = [0, 1, 2, 3];
     ///
-    /// &y[10..100];
-    /// &y[10..];
+    /// let _ = &y[10..100];
+    /// let _ = &y[10..];
     /// ```
     ///
     /// Use instead:
@@ -40,9 +40,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// let infinite_iter = 0..;
-    /// # #[allow(unused)]
-    /// [0..].iter().zip(infinite_iter.take_while(|x| *x > 5));
+    /// let count = (0..).take_while(|x| *x < 5).count();
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub MAYBE_INFINITE_ITER,
@@ -28,7 +28,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// let x: u8 = 1;
-    /// (x as u32) > 300;
+    /// let _ = (x as u32) > 300;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub INVALID_UPCAST_COMPARISONS,
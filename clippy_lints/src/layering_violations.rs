@@ -0,0 +1,228 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::Res;
+use rustc_hir::def_id::{DefId, LocalDefId, LOCAL_CRATE};
+use rustc_hir::{Expr, ExprKind, HirId, Path, QPath, TyKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+use crate::utils::conf::LayeringRule;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the uses of the crates and modules the `layering` configuration
+    /// disallows in some modules, whether by a path, a `use` or a method call.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// layers are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// It breaks the layers of the architecture of the crate, e.g. the domain
+    /// logic depending on the database.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// layering = [
+    ///     # The domain logic can't use the `sqlx` crate or the `infra` module.
+    ///     { module = "crate::domain", disallowed = ["sqlx", "crate::infra"] },
+    ///     # Can add a `reason` for why the crates and modules are disallowed.
+    ///     { module = "crate::infra", disallowed = ["crate::api"], reason = "the api uses the infra" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust
+    /// # mod infra {
+    /// #     pub mod users {
+    /// #         pub fn fetch(_id: i64) -> crate::domain::User {
+    /// #             crate::domain::User
+    /// #         }
+    /// #     }
+    /// # }
+    /// mod domain {
+    /// #   pub struct User;
+    ///     pub fn load_user(id: i64) -> User {
+    ///         crate::infra::users::fetch(id)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// mod domain {
+    /// #   pub struct User;
+    ///     pub trait UserRepository {
+    ///         fn fetch(&self, id: i64) -> User;
+    ///     }
+    ///
+    ///     pub fn load_user(users: &impl UserRepository, id: i64) -> User {
+    ///         users.fetch(id)
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.69.0"]
+    pub LAYERING_VIOLATIONS,
+    style,
+    "use of a crate or module disallowed in the module by the `layering` configuration"
+}
+
+/// A module, with the indices of the rules applying to it
+struct Module {
+    path: Vec<String>,
+    rules: Vec<usize>,
+}
+
+pub struct LayeringViolations {
+    rules: Vec<LayeringRule>,
+    modules: FxHashMap<LocalDefId, Module>,
+    /// The spans already linted, a `use` of a name of several namespaces resolves to several items
+    linted: FxHashSet<Span>,
+}
+
+impl LayeringViolations {
+    pub fn new(rules: Vec<LayeringRule>) -> Self {
+        Self {
+            rules,
+            modules: FxHashMap::default(),
+            linted: FxHashSet::default(),
+        }
+    }
+
+    /// Returns the indices of the rule disallowing the use of `def_id` at the node `hir_id`, and of
+    /// the path in its `disallowed` list matching `def_id`.
+    fn violated_rule(&mut self, cx: &LateContext<'_>, def_id: DefId, hir_id: HirId) -> Option<(usize, usize)> {
+        if self.rules.is_empty() {
+            return None;
+        }
+
+        let rules = &self.rules;
+        let module = self
+            .modules
+            .entry(cx.tcx.parent_module(hir_id))
+            .or_insert_with_key(|&module| {
+                let path = def_path(cx, module.to_def_id());
+                Module {
+                    rules: (0..rules.len())
+                        .filter(|&index| starts_with(cx, &path, &rules[index].module))
+                        .collect(),
+                    path,
+                }
+            });
+        if module.rules.is_empty() {
+            return None;
+        }
+
+        // the item can be disallowed by the path it's visible at, e.g. through a reexport, or
+        // by the path of its definition
+        let visible_path: Vec<_> = visible_path(cx, def_id).split("::").map(ToString::to_string).collect();
+        let def_path = def_path(cx, def_id);
+
+        module.rules.iter().find_map(|&index| {
+            let position = rules[index].disallowed.iter().position(|prefix| {
+                (starts_with(cx, &visible_path, prefix) || starts_with(cx, &def_path, prefix))
+                    // a module may use itself
+                    && !starts_with(cx, &module.path, prefix)
+            })?;
+            Some((index, position))
+        })
+    }
+
+    fn check_def(&mut self, cx: &LateContext<'_>, def_id: DefId, hir_id: HirId, span: Span) {
+        let Some((index, position)) = self.violated_rule(cx, def_id, hir_id) else {
+            return
+        };
+        if !self.linted.insert(span) {
+            return;
+        }
+
+        let rule = &self.rules[index];
+        let msg = format!(
+            "use of `{}` in `{}`, which may not use `{}` according to config",
+            visible_path(cx, def_id),
+            rule.module,
+            rule.disallowed[position],
+        );
+        span_lint_and_then(cx, LAYERING_VIOLATIONS, span, &msg, |diag| {
+            if let Some(reason) = &rule.reason {
+                diag.note(format!("{reason} (from clippy.toml)"));
+            }
+        });
+    }
+}
+
+impl_lint_pass!(LayeringViolations => [LAYERING_VIOLATIONS]);
+
+impl<'tcx> LateLintPass<'tcx> for LayeringViolations {
+    fn check_path(&mut self, cx: &LateContext<'tcx>, path: &Path<'tcx>, hir_id: HirId) {
+        if let Res::Def(_, def_id) = path.res {
+            self.check_def(cx, def_id, hir_id, path.span);
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        // resolved paths are checked by `check_path`, only the items resolved by type checking
+        // are left
+        let span = match expr.kind {
+            ExprKind::MethodCall(segment, ..) => segment.ident.span,
+            ExprKind::Path(QPath::TypeRelative(self_ty, _)) => {
+                // don't lint `Type::function` again when `Type` is disallowed
+                if let TyKind::Path(QPath::Resolved(None, path)) = self_ty.kind
+                    && let Res::Def(_, self_def_id) = path.res
+                    && self.violated_rule(cx, self_def_id, expr.hir_id).is_some()
+                {
+                    return;
+                }
+                expr.span
+            },
+            _ => return,
+        };
+        if let Some(def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+            self.check_def(cx, def_id, expr.hir_id, span);
+        }
+    }
+}
+
+/// Returns the path `def_id` is visible at, starting with `crate` for the items of the crate being
+/// checked. Unlike in diagnostics, the path is never trimmed to the name of the item.
+fn visible_path(cx: &LateContext<'_>, def_id: DefId) -> String {
+    let path = with_no_trimmed_paths!(cx.tcx.def_path_str(def_id));
+    if def_id.is_local() {
+        format!("crate::{path}")
+    } else {
+        path
+    }
+}
+
+/// Returns the path of the definition of `def_id`, starting with `crate` for the items of the
+/// crate being checked.
+fn def_path(cx: &LateContext<'_>, def_id: DefId) -> Vec<String> {
+    let mut path: Vec<_> = cx.get_def_path(def_id).iter().map(ToString::to_string).collect();
+    if def_id.is_local()
+        && let Some(first) = path.first_mut()
+    {
+        *first = String::from("crate");
+    }
+    path
+}
+
+/// Checks if `path` starts with the segments of `prefix`, where the name of the crate being
+/// checked can be used instead of `crate`.
+fn starts_with(cx: &LateContext<'_>, path: &[String], prefix: &str) -> bool {
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    let prefix: Vec<_> = prefix
+        .split("::")
+        .enumerate()
+        .map(|(i, segment)| {
+            if i == 0 && segment == crate_name.as_str() {
+                "crate"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    path.len() >= prefix.len() && path.iter().zip(prefix).all(|(segment, prefix)| segment == prefix)
+}
@@ -50,12 +50,16 @@ extern crate clippy_utils;
 extern crate declare_clippy_lint;
 
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clippy_utils::msrvs::Msrv;
 use rustc_data_structures::fx::FxHashSet;
-use rustc_lint::{Lint, LintId};
+use rustc_errors::{Applicability, Diagnostic};
+use rustc_lint::{CheckLintNameResult, Lint, LintId};
+use rustc_middle::ty::RegisteredTools;
 use rustc_session::Session;
+use rustc_span::symbol::Ident;
+use rustc_span::{sym, BytePos, Span};
 
 #[cfg(feature = "internal")]
 pub mod deprecated_lints;
@@ -91,6 +95,7 @@ mod copies;
 mod copy_iterator;
 mod crate_in_macro_def;
 mod create_dir;
+mod custom_lints;
 mod dbg_macro;
 mod default;
 mod default_instead_of_iter_empty;
@@ -99,6 +104,7 @@ mod default_union_representation;
 mod dereference;
 mod derivable_impls;
 mod derive;
+mod disallowed_impls;
 mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
@@ -162,6 +168,7 @@ mod large_const_arrays;
 mod large_enum_variant;
 mod large_include_file;
 mod large_stack_arrays;
+mod layering_violations;
 mod len_zero;
 mod let_if_seq;
 mod let_underscore;
@@ -314,8 +321,9 @@ mod zero_div_zero;
 mod zero_sized_map_values;
 // end lints modules, do not remove this comment, it’s used in `update_lints`
 
-use crate::utils::conf::{format_error, TryConf};
-pub use crate::utils::conf::{lookup_conf_file, Conf};
+pub use crate::utils::conf::{self, lookup_conf_file, lookup_inherited_conf_files, Conf, TryConf};
+use crate::utils::conf::{ConfError, UnknownLint};
+pub use clippy_utils::path_lint_levels::{allow_early_lints_by_path, provide as override_lint_levels};
 
 /// Register all pre expansion lints
 ///
@@ -333,38 +341,135 @@ pub fn register_pre_expansion_lints(store: &mut rustc_lint::LintStore, sess: &Se
     store.register_pre_expansion_pass(move || Box::new(attrs::EarlyAttributes { msrv: msrv() }));
 }
 
+/// Reads Clippy's configuration file at `path`, along with the files it inherits from. The file is
+/// read once, before the compiler session is created, its errors are reported by
+/// `report_conf_errors`.
+///
+/// Used in `./src/driver.rs`.
 #[doc(hidden)]
-pub fn read_conf(sess: &Session, path: &io::Result<Option<PathBuf>>) -> Conf {
+pub fn read_conf(path: &io::Result<Option<PathBuf>>) -> TryConf {
+    match path {
+        Ok(Some(path)) => utils::conf::read(path),
+        _ => TryConf::default(),
+    }
+}
+
+/// Reports the errors and warnings of the configuration `conf`, read from `path` by `read_conf`.
+///
+/// Used in `./src/driver.rs`.
+#[doc(hidden)]
+pub fn report_conf_errors(sess: &Session, path: &io::Result<Option<PathBuf>>, conf: &TryConf) {
     let file_name = match path {
         Ok(Some(path)) => path,
-        Ok(None) => return Conf::default(),
+        Ok(None) => return,
         Err(error) => {
             sess.struct_err(format!("error finding Clippy's configuration file: {error}"))
                 .emit();
-            return Conf::default();
+            return;
         },
     };
 
-    let TryConf { conf, errors, warnings } = utils::conf::read(file_name);
-    // all conf errors are non-fatal, we just use the default conf in case of error
-    for error in errors {
-        sess.err(format!(
-            "error reading Clippy's configuration file `{}`: {}",
-            file_name.display(),
-            format_error(error)
-        ));
+    // all conf errors are non-fatal, we just use the default conf in case of error, the unknown
+    // lints are reported by `check_conf_lint_names`
+    for error in &conf.errors {
+        // the error may be in one of the files this file inherits from
+        let file_name = error.file.as_deref().unwrap_or(file_name);
+        let span = conf_error_span(sess, file_name, error);
+        let mut diag = sess.struct_err(conf_error_message(file_name, error, span));
+        add_conf_error_details(&mut diag, error, span);
+        diag.emit();
     }
 
-    for warning in warnings {
-        sess.struct_warn(format!(
-            "error reading Clippy's configuration file `{}`: {}",
-            file_name.display(),
-            format_error(warning)
-        ))
-        .emit();
+    for warning in &conf.warnings {
+        let file_name = warning.file.as_deref().unwrap_or(file_name);
+        let span = conf_error_span(sess, file_name, warning);
+        let mut diag = sess.struct_warn(conf_error_message(file_name, warning, span));
+        add_conf_error_details(&mut diag, warning, span);
+        diag.emit();
+    }
+}
+
+/// Warns about the names of the `lints` tables of the configuration `conf`, read from `path`, that
+/// aren't lints, once all the lints are registered in `store`, including the ones of the lint
+/// plugins and the custom lints.
+///
+/// The levels of the `[lints]` table are passed to rustc before the lints are registered, so the
+/// unknown ones are registered as ignored for rustc not to warn about them again.
+///
+/// Used in `./src/driver.rs`.
+#[doc(hidden)]
+pub fn check_conf_lint_names(
+    sess: &Session,
+    store: &mut rustc_lint::LintStore,
+    path: &io::Result<Option<PathBuf>>,
+    conf: &TryConf,
+) {
+    let Ok(Some(file_name)) = path else {
+        return;
+    };
+    let tools: RegisteredTools = [Ident::with_dummy_span(sym::clippy)].into_iter().collect();
+    let mut ignored = FxHashSet::default();
+    for UnknownLint { name, warning } in &conf.unknown_lints {
+        let suggestion = match store.check_lint_name(name.trim_start_matches("clippy::"), Some(sym::clippy), &tools) {
+            CheckLintNameResult::NoLint(suggestion) => suggestion,
+            _ => continue,
+        };
+        let mut warning = warning.clone();
+        if warning.suggestion.is_none()
+            && let Some(suggestion) = suggestion
+        {
+            let lint = suggestion.as_str().trim_start_matches("clippy::").to_string();
+            warning.suggestion = Some((String::from("perhaps you meant"), lint, Applicability::MaybeIncorrect));
+        }
+        let file_name = warning.file.as_deref().unwrap_or(file_name);
+        let span = conf_error_span(sess, file_name, &warning);
+        let mut diag = sess.struct_warn(conf_error_message(file_name, &warning, span));
+        add_conf_error_details(&mut diag, &warning, span);
+        diag.emit();
+        ignored.insert(name);
+    }
+    for name in ignored {
+        store.register_ignored(&name);
+    }
+}
+
+/// Returns the span in the configuration file `file_name` that `error` points to, if any.
+fn conf_error_span(sess: &Session, file_name: &Path, error: &ConfError) -> Option<Span> {
+    let range = error.span.clone()?;
+    let file = sess.source_map().load_file(file_name).ok()?;
+    let lo = file.start_pos + BytePos(u32::try_from(range.start).ok()?);
+    let hi = file.start_pos + BytePos(u32::try_from(range.end).ok()?);
+    Some(Span::with_root_ctxt(lo, hi))
+}
+
+fn conf_error_message(file_name: &Path, error: &ConfError, span: Option<Span>) -> String {
+    // the file is already shown with the span
+    if span.is_some() {
+        format!("error reading Clippy's configuration file: {error}")
+    } else {
+        format!(
+            "error reading Clippy's configuration file `{}`: {error}",
+            file_name.display()
+        )
     }
+}
 
-    conf
+fn add_conf_error_details(diag: &mut Diagnostic, error: &ConfError, span: Option<Span>) {
+    if let Some(span) = span {
+        diag.set_span(span);
+    }
+    if let Some(help) = &error.help {
+        diag.help(help.clone());
+    }
+    match (&error.suggestion, span) {
+        (Some((msg, replacement, applicability)), Some(span)) => {
+            diag.span_suggestion(span, msg.clone(), replacement, *applicability);
+        },
+        (Some((msg, replacement, _)), None) => {
+            diag.help(format!("{msg}: `{replacement}`"));
+        },
+        (None, _) => {},
+    }
 }
 
 #[derive(Default)]
@@ -423,6 +528,23 @@ impl LintCategory {
         matches!(self, Correctness | Suspicious | Style | Complexity | Perf)
     }
 
+    /// The name of the lint group, without the `clippy::` prefix.
+    fn name(self) -> &'static str {
+        match self {
+            Cargo => "cargo",
+            Complexity => "complexity",
+            Correctness => "correctness",
+            Nursery => "nursery",
+            Pedantic => "pedantic",
+            Perf => "perf",
+            Restriction => "restriction",
+            Style => "style",
+            Suspicious => "suspicious",
+            #[cfg(feature = "internal")]
+            Internal => "internal",
+        }
+    }
+
     fn group(self, groups: &mut RegistrationGroups) -> &mut Vec<LintId> {
         match self {
             Cargo => &mut groups.cargo,
@@ -440,21 +562,36 @@ impl LintCategory {
     }
 }
 
-pub(crate) struct LintInfo {
+pub struct LintInfo {
     /// Double reference to maintain pointer equality
     lint: &'static &'static Lint,
     category: LintCategory,
     explanation: &'static str,
 }
 
-pub fn explain(name: &str) {
-    let target = format!("clippy::{}", name.to_ascii_uppercase());
-    match declared_lints::LINTS.iter().find(|info| info.lint.name == target) {
-        Some(info) => print!("{}", info.explanation),
-        None => println!("unknown lint: {name}"),
+impl LintInfo {
+    pub fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
+    /// The name of the lint group, without the `clippy::` prefix.
+    pub fn group(&self) -> &'static str {
+        self.category.name()
+    }
+
+    /// The documentation of the lint, in Markdown.
+    pub fn explanation(&self) -> &'static str {
+        self.explanation
     }
 }
 
+/// Returns the lints declared by Clippy.
+///
+/// Used in `./src/main.rs` and `./src/driver.rs`.
+pub fn lints() -> &'static [&'static LintInfo] {
+    declared_lints::LINTS
+}
+
 fn register_categories(store: &mut rustc_lint::LintStore) {
     let mut groups = RegistrationGroups::default();
 
@@ -482,6 +619,10 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
 
     include!("lib.deprecated.rs");
 
+    let path_overrides = utils::conf::PathOverrides::new(&conf.overrides);
+    clippy_utils::path_lint_levels::set_path_lint_levels(path_overrides.lint_levels());
+    clippy_utils::diagnostics::set_documented_lints(declared_lints::LINTS.iter().map(|info| *info.lint));
+
     #[cfg(feature = "internal")]
     {
         if std::env::var("ENABLE_METADATA_COLLECTION").eq(&Ok("1".to_string())) {
@@ -630,9 +771,11 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(temporary_assignment::TemporaryAssignment));
     store.register_late_pass(move |_| Box::new(transmute::Transmute::new(msrv())));
     let cognitive_complexity_threshold = conf.cognitive_complexity_threshold;
+    let cognitive_complexity_overrides = path_overrides.clone();
     store.register_late_pass(move |_| {
         Box::new(cognitive_complexity::CognitiveComplexity::new(
             cognitive_complexity_threshold,
+            cognitive_complexity_overrides.clone(),
         ))
     });
     let too_large_for_stack = conf.too_large_for_stack;
@@ -657,11 +800,13 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     let too_many_arguments_threshold = conf.too_many_arguments_threshold;
     let too_many_lines_threshold = conf.too_many_lines_threshold;
     let large_error_threshold = conf.large_error_threshold;
+    let functions_overrides = path_overrides.clone();
     store.register_late_pass(move |_| {
         Box::new(functions::Functions::new(
             too_many_arguments_threshold,
             too_many_lines_threshold,
             large_error_threshold,
+            functions_overrides.clone(),
         ))
     });
     let doc_valid_idents = conf.doc_valid_idents.iter().cloned().collect::<FxHashSet<_>>();
@@ -830,6 +975,12 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(unused_async::UnusedAsync));
     let disallowed_types = conf.disallowed_types.clone();
     store.register_late_pass(move |_| Box::new(disallowed_types::DisallowedTypes::new(disallowed_types.clone())));
+    let disallowed_impls = conf.disallowed_impls.clone();
+    store.register_late_pass(move |_| Box::new(disallowed_impls::DisallowedImpls::new(disallowed_impls.clone())));
+    let layering = conf.layering.clone();
+    store.register_late_pass(move |_| Box::new(layering_violations::LayeringViolations::new(layering.clone())));
+    let custom_lints = custom_lints::declare_lints(store, sess, &conf.custom_lints);
+    store.register_late_pass(move |_| Box::new(custom_lints::CustomLints::new(custom_lints.clone())));
     let import_renames = conf.enforced_import_renames.clone();
     store.register_late_pass(move |_| {
         Box::new(missing_enforced_import_rename::ImportRename::new(
@@ -135,7 +135,7 @@ declare_clippy_lint! {
     ///     // ..
     /// }
     /// ```
-    /// can be rewritten to
+    /// Use instead:
     /// ```rust
     /// # let y = vec![1];
     /// for x in y {
@@ -217,7 +217,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # let v = vec![1];
-    /// # fn bar(bar: usize, baz: usize) {}
+    /// # fn bar(index: usize, item: usize) {}
     /// let mut i = 0;
     /// for item in &v {
     ///     bar(i, *item);
@@ -228,7 +228,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust
     /// # let v = vec![1];
-    /// # fn bar(bar: usize, baz: usize) {}
+    /// # fn bar(index: usize, item: usize) {}
     /// for (i, item) in v.iter().enumerate() { bar(i, *item); }
     /// ```
     #[clippy::version = "pre 1.29.0"]
@@ -339,7 +339,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// loop {
-    ///     ..;
+    ///     println!("so much work");
     ///     break;
     /// }
     /// ```
@@ -373,9 +373,9 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// let mut foo = 42;
-    /// for i in 0..foo {
-    ///     foo -= 1;
+    /// let mut count = 42;
+    /// for i in 0..count {
+    ///     count -= 1;
     ///     println!("{}", i); // prints numbers from 0 to 42, not 0 to 21
     /// }
     /// ```
@@ -20,11 +20,12 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// std::mem::size_of::<usize>() * 8;
+    /// # use std::mem::size_of;
+    /// let _ = size_of::<usize>() * 8;
     /// ```
     /// Use instead:
     /// ```rust
-    /// usize::BITS as usize;
+    /// let _ = usize::BITS as usize;
     /// ```
     #[clippy::version = "1.60.0"]
     pub MANUAL_BITS,
@@ -1,3 +1,4 @@
+use crate::utils::conf::ConfSchema;
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::higher::IfLetOrMatch;
 use clippy_utils::msrvs::{self, Msrv};
@@ -14,7 +15,7 @@ use rustc_middle::lint::in_external_macro;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::symbol::sym;
 use rustc_span::Span;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::ops::ControlFlow;
 
 declare_clippy_lint! {
@@ -34,7 +35,7 @@ declare_clippy_lint! {
     /// let v = if let Some(v) = w { v } else { return };
     /// ```
     ///
-    /// Could be written:
+    /// Use instead:
     ///
     /// ```rust
     /// # #![feature(let_else)]
@@ -292,9 +293,15 @@ fn expr_is_simple_identity(pat: &'_ Pat<'_>, expr: &'_ Expr<'_>) -> bool {
     true
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum MatchLintBehaviour {
     AllTypes,
     WellKnownTypes,
     Never,
 }
+
+impl ConfSchema for MatchLintBehaviour {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({ "enum": ["AllTypes", "WellKnownTypes", "Never"] })
+    }
+}
@@ -23,37 +23,37 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// struct S {
+    /// pub struct S {
     ///     pub a: i32,
     ///     pub b: i32,
     ///     _c: (),
     /// }
     ///
-    /// enum E {
+    /// pub enum E {
     ///     A,
     ///     B,
     ///     #[doc(hidden)]
     ///     _C,
     /// }
     ///
-    /// struct T(pub i32, pub i32, ());
+    /// pub struct T(pub i32, pub i32, ());
     /// ```
     /// Use instead:
     /// ```rust
     /// #[non_exhaustive]
-    /// struct S {
+    /// pub struct S {
     ///     pub a: i32,
     ///     pub b: i32,
     /// }
     ///
     /// #[non_exhaustive]
-    /// enum E {
+    /// pub enum E {
     ///     A,
     ///     B,
     /// }
     ///
     /// #[non_exhaustive]
-    /// struct T(pub i32, pub i32);
+    /// pub struct T(pub i32, pub i32);
     /// ```
     #[clippy::version = "1.45.0"]
     pub MANUAL_NON_EXHAUSTIVE,
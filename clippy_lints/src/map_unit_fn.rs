@@ -23,20 +23,20 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # fn do_stuff() -> Option<String> { Some(String::new()) }
-    /// # fn log_err_msg(foo: String) -> Option<String> { Some(foo) }
-    /// # fn format_msg(foo: String) -> String { String::new() }
+    /// # fn log_err_msg(msg: String) {}
+    /// # fn format_msg(msg: String) -> String { String::new() }
     /// let x: Option<String> = do_stuff();
     /// x.map(log_err_msg);
     /// # let x: Option<String> = do_stuff();
     /// x.map(|msg| log_err_msg(format_msg(msg)));
     /// ```
     ///
-    /// The correct use would be:
+    /// Use instead:
     ///
     /// ```rust
     /// # fn do_stuff() -> Option<String> { Some(String::new()) }
-    /// # fn log_err_msg(foo: String) -> Option<String> { Some(foo) }
-    /// # fn format_msg(foo: String) -> String { String::new() }
+    /// # fn log_err_msg(msg: String) {}
+    /// # fn format_msg(msg: String) -> String { String::new() }
     /// let x: Option<String> = do_stuff();
     /// if let Some(msg) = x {
     ///     log_err_msg(msg);
@@ -65,20 +65,20 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # fn do_stuff() -> Result<String, String> { Ok(String::new()) }
-    /// # fn log_err_msg(foo: String) -> Result<String, String> { Ok(foo) }
-    /// # fn format_msg(foo: String) -> String { String::new() }
+    /// # fn log_err_msg(msg: String) {}
+    /// # fn format_msg(msg: String) -> String { String::new() }
     /// let x: Result<String, String> = do_stuff();
     /// x.map(log_err_msg);
     /// # let x: Result<String, String> = do_stuff();
     /// x.map(|msg| log_err_msg(format_msg(msg)));
     /// ```
     ///
-    /// The correct use would be:
+    /// Use instead:
     ///
     /// ```rust
     /// # fn do_stuff() -> Result<String, String> { Ok(String::new()) }
-    /// # fn log_err_msg(foo: String) -> Result<String, String> { Ok(foo) }
-    /// # fn format_msg(foo: String) -> String { String::new() }
+    /// # fn log_err_msg(msg: String) {}
+    /// # fn format_msg(msg: String) -> String { String::new() }
     /// let x: Result<String, String> = do_stuff();
     /// if let Ok(msg) = x {
     ///     log_err_msg(msg);
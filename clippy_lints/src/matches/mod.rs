@@ -46,7 +46,7 @@ declare_clippy_lint! {
     /// # fn bar(stool: &str) {}
     /// # let x = Some("abc");
     /// match x {
-    ///     Some(ref foo) => bar(foo),
+    ///     Some(s) => bar(s),
     ///     _ => (),
     /// }
     /// ```
@@ -55,8 +55,8 @@ declare_clippy_lint! {
     /// ```rust
     /// # fn bar(stool: &str) {}
     /// # let x = Some("abc");
-    /// if let Some(ref foo) = x {
-    ///     bar(foo);
+    /// if let Some(s) = x {
+    ///     bar(s);
     /// }
     /// ```
     #[clippy::version = "pre 1.29.0"]
@@ -80,24 +80,28 @@ declare_clippy_lint! {
     /// Using `match`:
     ///
     /// ```rust
-    /// # fn bar(foo: &usize) {}
+    /// # fn bar(n: &usize) {}
     /// # let other_ref: usize = 1;
     /// # let x: Option<&usize> = Some(&1);
     /// match x {
-    ///     Some(ref foo) => bar(foo),
-    ///     _ => bar(&other_ref),
+    ///     Some(n) => bar(n),
+    ///     _ => {
+    ///         println!("using the other value");
+    ///         bar(&other_ref);
+    ///     },
     /// }
     /// ```
     ///
-    /// Using `if let` with `else`:
+    /// Use instead:
     ///
     /// ```rust
-    /// # fn bar(foo: &usize) {}
+    /// # fn bar(n: &usize) {}
     /// # let other_ref: usize = 1;
     /// # let x: Option<&usize> = Some(&1);
-    /// if let Some(ref foo) = x {
-    ///     bar(foo);
+    /// if let Some(n) = x {
+    ///     bar(n);
     /// } else {
+    ///     println!("using the other value");
     ///     bar(&other_ref);
     /// }
     /// ```
@@ -158,7 +162,7 @@ declare_clippy_lint! {
     ///     false => bar(),
     /// }
     /// ```
-    /// Use if/else instead:
+    /// Use instead:
     /// ```rust
     /// # fn foo() {}
     /// # fn bar() {}
@@ -264,21 +268,21 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # enum Foo { A(usize), B(usize) }
+    /// # enum Foo { A(usize), B(usize), C(usize) }
     /// # let x = Foo::B(1);
     /// match x {
-    ///     Foo::A(_) => {},
-    ///     _ => {},
+    ///     Foo::A(_) => println!("A"),
+    ///     _ => println!("B or C"),
     /// }
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # enum Foo { A(usize), B(usize) }
+    /// # enum Foo { A(usize), B(usize), C(usize) }
     /// # let x = Foo::B(1);
     /// match x {
-    ///     Foo::A(_) => {},
-    ///     Foo::B(_) => {},
+    ///     Foo::A(_) => println!("A"),
+    ///     Foo::B(_) | Foo::C(_) => println!("B or C"),
     /// }
     /// ```
     #[clippy::version = "1.34.0"]
@@ -337,8 +341,8 @@ declare_clippy_lint! {
     /// ```rust
     /// # let s = "foo";
     /// match s {
-    ///     "a" => {},
-    ///     "bar" | _ => {},
+    ///     "a" => println!("a"),
+    ///     "bar" | _ => println!("something else"),
     /// }
     /// ```
     ///
@@ -346,8 +350,8 @@ declare_clippy_lint! {
     /// ```rust
     /// # let s = "foo";
     /// match s {
-    ///     "a" => {},
-    ///     _ => {},
+    ///     "a" => println!("a"),
+    ///     _ => println!("something else"),
     /// }
     /// ```
     #[clippy::version = "1.42.0"]
@@ -377,7 +381,7 @@ declare_clippy_lint! {
     /// };
     /// ```
     ///
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
     /// enum Wrapper {
     ///     Data(i32),
@@ -440,8 +444,8 @@ declare_clippy_lint! {
     /// let a = A { a: 5 };
     ///
     /// match a {
-    ///     A { a: 5, .. } => {},
-    ///     _ => {},
+    ///     A { a: 5, .. } => println!("five"),
+    ///     _ => println!("not five"),
     /// }
     /// ```
     ///
@@ -450,8 +454,8 @@ declare_clippy_lint! {
     /// # struct A { a: i32 }
     /// # let a = A { a: 5 };
     /// match a {
-    ///     A { a: 5 } => {},
-    ///     _ => {},
+    ///     A { a: 5 } => println!("five"),
+    ///     _ => println!("not five"),
     /// }
     /// ```
     #[clippy::version = "1.43.0"]
@@ -495,7 +499,7 @@ declare_clippy_lint! {
     /// };
     /// ```
     ///
-    /// The more idiomatic use would be:
+    /// Use instead:
     ///
     /// ```rust
     /// # use std::task::Poll;
@@ -690,8 +694,8 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// let foo: Option<i32> = None;
-    /// match foo {
+    /// let opt: Option<i32> = None;
+    /// match opt {
     ///     Some(v) => v,
     ///     None => 1,
     /// };
@@ -699,8 +703,8 @@ declare_clippy_lint! {
     ///
     /// Use instead:
     /// ```rust
-    /// let foo: Option<i32> = None;
-    /// foo.unwrap_or(1);
+    /// let opt: Option<i32> = None;
+    /// opt.unwrap_or(1);
     /// ```
     #[clippy::version = "1.49.0"]
     pub MANUAL_UNWRAP_OR,
@@ -826,12 +830,9 @@ declare_clippy_lint! {
     /// let mutex = Mutex::new(State {});
     ///
     /// let is_foo = mutex.lock().unwrap().foo();
-    /// match is_foo {
-    ///     true => {
-    ///         mutex.lock().unwrap().bar();
-    ///     }
-    ///     false => {}
-    /// };
+    /// if is_foo {
+    ///     mutex.lock().unwrap().bar();
+    /// }
     ///
     /// println!("All done!");
     /// ```
@@ -860,7 +861,7 @@ declare_clippy_lint! {
     ///     Ok(0)
     /// }
     /// ```
-    /// Could be written:
+    /// Use instead:
     ///
     /// ```rust
     /// fn foo(fail: bool) -> Result<i32, String> {
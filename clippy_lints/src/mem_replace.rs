@@ -30,7 +30,7 @@ declare_clippy_lint! {
     /// let mut an_option = Some(0);
     /// let replaced = mem::replace(&mut an_option, None);
     /// ```
-    /// Is better expressed with:
+    /// Use instead:
     /// ```rust
     /// let mut an_option = Some(0);
     /// let taken = an_option.take();
@@ -87,7 +87,7 @@ declare_clippy_lint! {
     /// let mut text = String::from("foo");
     /// let replaced = std::mem::replace(&mut text, String::default());
     /// ```
-    /// Is better expressed with:
+    /// Use instead:
     /// ```rust
     /// let mut text = String::from("foo");
     /// let taken = std::mem::take(&mut text);
@@ -153,7 +153,7 @@ declare_clippy_lint! {
     /// ```rust
     /// let hello = "hesuo worpd"
     ///     .replace('s', "l")
-    ///     .replace("u", "l")
+    ///     .replace('u', "l")
     ///     .replace('p', "l");
     /// ```
     /// Use instead:
@@ -323,9 +323,9 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// struct X;
+    /// pub struct X;
     /// impl X {
-    ///     fn add(&self, other: &X) -> X {
+    ///     pub fn add(self, other: X) -> X {
     ///         // ..
     /// # X
     ///     }
@@ -479,7 +479,7 @@ declare_clippy_lint! {
     /// ```rust
     /// # let option = Some(1);
     /// # let result: Result<usize, ()> = Ok(1);
-    /// # fn some_function(foo: ()) -> usize { 1 }
+    /// # fn some_function(_: ()) -> usize { 1 }
     /// option.map(|a| a + 1).unwrap_or(0);
     /// result.map(|a| a + 1).unwrap_or_else(some_function);
     /// ```
@@ -488,7 +488,7 @@ declare_clippy_lint! {
     /// ```rust
     /// # let option = Some(1);
     /// # let result: Result<usize, ()> = Ok(1);
-    /// # fn some_function(foo: ()) -> usize { 1 }
+    /// # fn some_function(_: ()) -> usize { 1 }
     /// option.map_or(0, |a| a + 1);
     /// result.map_or_else(some_function, |a| a + 1);
     /// ```
@@ -511,14 +511,14 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let opt = Some(1);
-    /// opt.map_or(None, |a| Some(a + 1));
+    /// # let opt = Some(1_u32);
+    /// opt.map_or(None, |a| a.checked_add(1));
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # let opt = Some(1);
-    /// opt.and_then(|a| Some(a + 1));
+    /// # let opt = Some(1_u32);
+    /// opt.and_then(|a| a.checked_add(1));
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub OPTION_MAP_OR_NONE,
@@ -569,7 +569,7 @@ declare_clippy_lint! {
     /// let _ = res().or_else(|s| if s.len() == 42 { Err(10) } else { Err(20) });
     /// ```
     ///
-    /// The correct use would be:
+    /// Use instead:
     ///
     /// ```rust
     /// # fn opt() -> Option<&'static str> { Some("42") }
@@ -645,18 +645,18 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// let vec = vec![vec![1]];
-    /// let opt = Some(5);
+    /// let opt = Some(5_u32);
     ///
     /// vec.iter().map(|x| x.iter()).flatten();
-    /// opt.map(|x| Some(x * 2)).flatten();
+    /// opt.map(|x| x.checked_mul(2)).flatten();
     /// ```
     ///
     /// Use instead:
     /// ```rust
     /// # let vec = vec![vec![1]];
-    /// # let opt = Some(5);
+    /// # let opt = Some(5_u32);
     /// vec.iter().flat_map(|x| x.iter());
-    /// opt.and_then(|x| Some(x * 2));
+    /// opt.and_then(|x| x.checked_mul(2));
     /// ```
     #[clippy::version = "1.31.0"]
     pub MAP_FLATTEN,
@@ -728,12 +728,12 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    ///  (0..3).filter_map(|x| if x == 2 { Some(x) } else { None }).next();
+    /// (0..3).filter_map(|x| if x == 2 { Some(x * 2) } else { None }).next();
     /// ```
-    /// Can be written as
+    /// Use instead:
     ///
     /// ```rust
-    ///  (0..3).find_map(|x| if x == 2 { Some(x) } else { None });
+    /// (0..3).find_map(|x| if x == 2 { Some(x * 2) } else { None });
     /// ```
     #[clippy::version = "1.36.0"]
     pub FILTER_MAP_NEXT,
@@ -753,7 +753,7 @@ declare_clippy_lint! {
     /// # let iter = vec![vec![0]].into_iter();
     /// iter.flat_map(|x| x);
     /// ```
-    /// Can be written as
+    /// Use instead:
     /// ```rust
     /// # let iter = vec![vec![0]].into_iter();
     /// iter.flatten();
@@ -843,14 +843,14 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let foo = Some(String::new());
-    /// foo.unwrap_or(String::from("empty"));
+    /// # let opt = Some(String::new());
+    /// opt.unwrap_or(String::from("empty"));
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # let foo = Some(String::new());
-    /// foo.unwrap_or_else(|| String::from("empty"));
+    /// # let opt = Some(String::new());
+    /// opt.unwrap_or_else(|| String::from("empty"));
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub OR_FUN_CALL,
@@ -908,23 +908,23 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let foo = Some(String::new());
+    /// # let opt = Some(String::new());
     /// # let err_code = "418";
     /// # let err_msg = "I'm a teapot";
-    /// foo.expect(&format!("Err {}: {}", err_code, err_msg));
+    /// opt.expect(&format!("Err {}: {}", err_code, err_msg));
     ///
     /// // or
     ///
-    /// # let foo = Some(String::new());
-    /// foo.expect(format!("Err {}: {}", err_code, err_msg).as_str());
+    /// # let opt = Some(String::new());
+    /// opt.expect(format!("Err {}: {}", err_code, err_msg).as_str());
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # let foo = Some(String::new());
+    /// # let opt = Some(String::new());
     /// # let err_code = "418";
     /// # let err_msg = "I'm a teapot";
-    /// foo.unwrap_or_else(|| panic!("Err {}: {}", err_code, err_msg));
+    /// opt.unwrap_or_else(|| panic!("Err {}: {}", err_code, err_msg));
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub EXPECT_FUN_CALL,
@@ -1059,6 +1059,15 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
+    /// Or in a trait definition:
+    /// ```rust
+    /// pub trait Trait {
+    ///     // Bad. The type name must contain `Self`
+    ///     fn new();
+    /// }
+    /// ```
+    ///
+    /// Use instead:
     /// ```rust
     /// # struct Foo;
     /// # struct FooError;
@@ -1070,14 +1079,6 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Or in a trait definition:
-    /// ```rust
-    /// pub trait Trait {
-    ///     // Bad. The type name must contain `Self`
-    ///     fn new();
-    /// }
-    /// ```
-    ///
     /// ```rust
     /// pub trait Trait {
     ///     // Good. Return type contains `Self`
@@ -1204,7 +1205,7 @@ declare_clippy_lint! {
     /// let bad_vec = some_vec.iter().nth(3);
     /// let bad_slice = &some_vec[..].iter().nth(3);
     /// ```
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
     /// let some_vec = vec![0, 1, 2, 3];
     /// let bad_vec = some_vec.get(3);
@@ -1225,15 +1226,13 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// let some_vec = vec![0, 1, 2, 3];
-    /// let bad_vec = some_vec.iter().skip(3).next();
-    /// let bad_slice = &some_vec[..].iter().skip(3).next();
+    /// let text = "one two three";
+    /// let word = text.split(' ').skip(2).next();
     /// ```
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
-    /// let some_vec = vec![0, 1, 2, 3];
-    /// let bad_vec = some_vec.iter().nth(3);
-    /// let bad_slice = &some_vec[..].iter().nth(3);
+    /// let text = "one two three";
+    /// let word = text.split(' ').nth(2);
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub ITER_SKIP_NEXT,
@@ -1251,14 +1250,14 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # use std::collections::HashSet;
-    /// let mut foo = vec![0, 1, 2, 3];
-    /// let bar: HashSet<usize> = foo.drain(..).collect();
+    /// let mut vec = vec![0, 1, 2, 3];
+    /// let bar: HashSet<usize> = vec.drain(..).collect();
     /// ```
     /// Use instead:
     /// ```rust
     /// # use std::collections::HashSet;
-    /// let foo = vec![0, 1, 2, 3];
-    /// let bar: HashSet<usize> = foo.into_iter().collect();
+    /// let vec = vec![0, 1, 2, 3];
+    /// let bar: HashSet<usize> = vec.into_iter().collect();
     /// ```
     #[clippy::version = "1.61.0"]
     pub ITER_WITH_DRAIN,
@@ -1324,7 +1323,7 @@ declare_clippy_lint! {
     /// let last = some_vec.get(3).unwrap();
     /// *some_vec.get_mut(0).unwrap() = 1;
     /// ```
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
     /// let mut some_vec = vec![0, 1, 2, 3];
     /// let last = some_vec[3];
@@ -1380,7 +1379,7 @@ declare_clippy_lint! {
     /// s.extend(abc.chars());
     /// s.extend(def.chars());
     /// ```
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
     /// let abc = "abc";
     /// let def = String::from("def");
@@ -1407,7 +1406,7 @@ declare_clippy_lint! {
     /// let s = [1, 2, 3, 4, 5];
     /// let s2: Vec<isize> = s[..].iter().cloned().collect();
     /// ```
-    /// The better use would be:
+    /// Use instead:
     /// ```rust
     /// let s = [1, 2, 3, 4, 5];
     /// let s2: Vec<isize> = s.to_vec();
@@ -1430,13 +1429,13 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # let name = "_";
-    /// name.chars().last() == Some('_') || name.chars().next_back() == Some('-');
+    /// let _ = name.chars().last() == Some('_') || name.chars().next_back() == Some('-');
     /// ```
     ///
     /// Use instead:
     /// ```rust
     /// # let name = "_";
-    /// name.ends_with('_') || name.ends_with('-');
+    /// let _ = name.ends_with('_') || name.ends_with('-');
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub CHARS_LAST_CMP,
@@ -1458,7 +1457,7 @@ declare_clippy_lint! {
     /// let x: &[i32] = &[1, 2, 3, 4, 5];
     /// do_stuff(x.as_ref());
     /// ```
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
     /// # fn do_stuff(x: &[i32]) {}
     /// let x: &[i32] = &[1, 2, 3, 4, 5];
@@ -1574,7 +1573,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust
     /// # let vec = vec![3, 4, 5];
-    /// (&vec).iter();
+    /// vec.iter();
     /// ```
     #[clippy::version = "1.32.0"]
     pub INTO_ITER_ON_REF,
@@ -1622,7 +1621,9 @@ declare_clippy_lint! {
     /// let _: usize = unsafe { MaybeUninit::uninit().assume_init() };
     /// ```
     ///
-    /// Note that the following is OK:
+    /// Uninitialized `MaybeUninit`s are OK.
+    ///
+    /// Use instead:
     ///
     /// ```rust
     /// use std::mem::MaybeUninit;
@@ -1652,7 +1653,9 @@ declare_clippy_lint! {
     /// let sub = x.checked_sub(y).unwrap_or(u32::MIN);
     /// ```
     ///
-    /// can be written using dedicated methods for saturating addition/subtraction as:
+    /// The dedicated methods for saturating addition/subtraction do the same.
+    ///
+    /// Use instead:
     ///
     /// ```rust
     /// # let y: u32 = 0;
@@ -1696,7 +1699,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # || {
+    /// # let _ = || {
     /// let metadata = std::fs::metadata("foo.txt")?;
     /// let filetype = metadata.file_type();
     ///
@@ -1707,10 +1710,10 @@ declare_clippy_lint! {
     /// # };
     /// ```
     ///
-    /// should be written as:
+    /// Use instead:
     ///
     /// ```rust
-    /// # || {
+    /// # let _ = || {
     /// let metadata = std::fs::metadata("foo.txt")?;
     /// let filetype = metadata.file_type();
     ///
@@ -1740,7 +1743,7 @@ declare_clippy_lint! {
     /// opt.as_ref().map(String::as_str)
     /// # ;
     /// ```
-    /// Can be written as
+    /// Use instead:
     /// ```rust
     /// # let opt = Some("".to_string());
     /// opt.as_deref()
@@ -1766,12 +1769,12 @@ declare_clippy_lint! {
     /// a[2..].iter().next();
     /// b.iter().next();
     /// ```
-    /// should be written as:
+    /// Use instead:
     /// ```rust
     /// # let a = [1, 2, 3];
     /// # let b = vec![1, 2, 3];
     /// a.get(2);
-    /// b.get(0);
+    /// b.first();
     /// ```
     #[clippy::version = "1.46.0"]
     pub ITER_NEXT_SLICE,
@@ -1855,11 +1858,11 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// (0..3).map(|t| Err(t)).collect::<Result<(), _>>();
+    /// (0..3).map(Err).collect::<Result<(), _>>();
     /// ```
     /// Use instead:
     /// ```rust
-    /// (0..3).try_for_each(|t| Err(t));
+    /// (0..3).try_for_each(Err);
     /// ```
     #[clippy::version = "1.49.0"]
     pub MAP_COLLECT_RESULT_UNIT,
@@ -1914,7 +1917,7 @@ declare_clippy_lint! {
     ///     assert!(x >= 0);
     /// });
     /// ```
-    /// Can be written as
+    /// Use instead:
     /// ```rust
     /// [1,2,3,4,5].iter()
     /// .for_each(|&x| {
@@ -2039,7 +2042,7 @@ declare_clippy_lint! {
     /// let some_vec = vec![0, 1, 2, 3];
     ///
     /// some_vec.iter().count();
-    /// &some_vec[..].iter().count();
+    /// some_vec[..].iter().count();
     /// ```
     ///
     /// Use instead:
@@ -2047,7 +2050,7 @@ declare_clippy_lint! {
     /// let some_vec = vec![0, 1, 2, 3];
     ///
     /// some_vec.len();
-    /// &some_vec[..].len();
+    /// some_vec[..].len();
     /// ```
     #[clippy::version = "1.52.0"]
     pub ITER_COUNT,
@@ -2259,7 +2262,7 @@ declare_clippy_lint! {
     /// let output = vector.iter().map(|item| item.to_uppercase()).collect::<Vec<String>>().join("");
     /// println!("{}", output);
     /// ```
-    /// The correct use would be:
+    /// Use instead:
     /// ```rust
     /// let vector = vec!["hello",  "world"];
     /// let output = vector.iter().map(|item| item.to_uppercase()).collect::<String>();
@@ -2585,14 +2588,14 @@ declare_clippy_lint! {
     ///
     /// ### Examples
     /// ```rust
-    /// let foo: Option<i32> = None;
-    /// foo.map_or(Err("error"), |v| Ok(v));
+    /// let opt: Option<i32> = None;
+    /// opt.map_or(Err("error"), Ok);
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// let foo: Option<i32> = None;
-    /// foo.ok_or("error");
+    /// let opt: Option<i32> = None;
+    /// opt.ok_or("error");
     /// ```
     #[clippy::version = "1.49.0"]
     pub MANUAL_OK_OR,
@@ -2616,7 +2619,7 @@ declare_clippy_lint! {
     /// let z = y.map(|i| *i);
     /// ```
     ///
-    /// The correct use would be:
+    /// Use instead:
     ///
     /// ```rust
     /// let x = vec![42, 43];
@@ -2677,7 +2680,7 @@ declare_clippy_lint! {
     /// }
     ///  ```
     ///
-    ///  After:
+    /// Use instead:
     ///  ```rust
     /// use std::{fmt, num::ParseIntError};
     ///
@@ -2778,7 +2781,7 @@ declare_clippy_lint! {
     /// ```rust
     /// use std::fs::OpenOptions;
     ///
-    /// OpenOptions::new().read(true).truncate(true);
+    /// let file = OpenOptions::new().read(true).truncate(true).open("foo.txt");
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub NONSENSICAL_OPEN_OPTIONS,
@@ -2803,7 +2806,7 @@ declare_clippy_lint! {
     /// x.push("/bar");
     /// assert_eq!(x, PathBuf::from("/bar"));
     /// ```
-    /// Could be written:
+    /// Use instead:
     ///
     /// ```rust
     /// use std::path::PathBuf;
@@ -2969,14 +2972,14 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # struct A;
-    /// # impl A { fn foo(&self) {} }
+    /// # impl A { fn foo(&self) -> usize { 0 } }
     /// # let mut vec: Vec<A> = Vec::new();
     /// vec.sort_by(|a, b| a.foo().cmp(&b.foo()));
     /// ```
     /// Use instead:
     /// ```rust
     /// # struct A;
-    /// # impl A { fn foo(&self) {} }
+    /// # impl A { fn foo(&self) -> usize { 0 } }
     /// # let mut vec: Vec<A> = Vec::new();
     /// vec.sort_by_key(|a| a.foo());
     /// ```
@@ -3024,7 +3027,7 @@ declare_clippy_lint! {
     /// let mut bytes = Vec::new();
     /// f.read_to_end(&mut bytes).unwrap();
     /// ```
-    /// Can be written more concisely as
+    /// Use instead:
     /// ```rust,no_run
     /// # use std::fs;
     /// let mut bytes = fs::read("foo.txt").unwrap();
@@ -36,8 +36,8 @@ declare_clippy_lint! {
     /// let f = Foo { a: 0, b: 0, c: 0 };
     ///
     /// match f {
-    ///     Foo { a: _, b: 0, .. } => {},
-    ///     Foo { a: _, b: _, c: _ } => {},
+    ///     Foo { a: _, b: 0, .. } => println!("b is zero"),
+    ///     Foo { a: _, b: _, c: _ } => println!("b is not zero"),
     /// }
     /// ```
     ///
@@ -51,8 +51,8 @@ declare_clippy_lint! {
     /// let f = Foo { a: 0, b: 0, c: 0 };
     ///
     /// match f {
-    ///     Foo { b: 0, .. } => {},
-    ///     Foo { .. } => {},
+    ///     Foo { b: 0, .. } => println!("b is zero"),
+    ///     Foo { .. } => println!("b is not zero"),
     /// }
     /// ```
     #[clippy::version = "pre 1.29.0"]
@@ -298,8 +298,8 @@ declare_clippy_lint! {
     /// # struct TupleStruct(u32, u32, u32);
     /// # let t = TupleStruct(1, 2, 3);
     /// match t {
-    ///     TupleStruct(0, .., _) => (),
-    ///     _ => (),
+    ///     TupleStruct(0, .., _) => println!("starts with zero"),
+    ///     _ => println!("doesn't start with zero"),
     /// }
     /// ```
     ///
@@ -308,8 +308,8 @@ declare_clippy_lint! {
     /// # struct TupleStruct(u32, u32, u32);
     /// # let t = TupleStruct(1, 2, 3);
     /// match t {
-    ///     TupleStruct(0, ..) => (),
-    ///     _ => (),
+    ///     TupleStruct(0, ..) => println!("starts with zero"),
+    ///     _ => println!("doesn't start with zero"),
     /// }
     /// ```
     #[clippy::version = "1.40.0"]
@@ -52,7 +52,7 @@ declare_clippy_lint! {
     /// # }
     /// ```
     ///
-    /// Could be a const fn:
+    /// Use instead:
     ///
     /// ```rust
     /// # struct Foo {
@@ -50,13 +50,13 @@ declare_clippy_lint! {
     /// use std::collections::HashSet;
     /// use std::hash::{Hash, Hasher};
     /// use std::sync::atomic::AtomicUsize;
-    ///# #[allow(unused)]
     ///
+    ///# #[allow(unused)]
     /// struct Bad(AtomicUsize);
     /// impl PartialEq for Bad {
     ///     fn eq(&self, rhs: &Self) -> bool {
-    ///          ..
-    /// ; unimplemented!();
+    ///         // ..
+    /// #         unimplemented!();
     ///     }
     /// }
     ///
@@ -64,13 +64,13 @@ declare_clippy_lint! {
     ///
     /// impl Hash for Bad {
     ///     fn hash<H: Hasher>(&self, h: &mut H) {
-    ///         ..
-    /// ; unimplemented!();
+    ///         // ..
+    /// #         unimplemented!();
     ///     }
     /// }
     ///
     /// fn main() {
-    ///     let _: HashSet<Bad> = HashSet::new();
+    ///     let set: HashSet<Bad> = HashSet::new();
     /// }
     /// ```
     #[clippy::version = "1.42.0"]
@@ -16,7 +16,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let mut vec = Vec::new();
+    /// # let mut vec: Vec<&i32> = Vec::new();
     /// # let mut value = 5;
     /// vec.push(&mut value);
     /// ```
@@ -32,7 +32,7 @@ declare_clippy_lint! {
     /// ```rust
     /// # let y = true;
     /// # use std::sync::Mutex;
-    /// let x = Mutex::new(&y);
+    /// let x = Mutex::new(y);
     /// ```
     ///
     /// Use instead:
@@ -64,13 +64,13 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # use std::sync::Mutex;
-    /// let x = Mutex::new(0usize);
+    /// let x = Mutex::new(0u32);
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// # use std::sync::atomic::AtomicUsize;
-    /// let x = AtomicUsize::new(0usize);
+    /// # use std::sync::atomic::AtomicU32;
+    /// let x = AtomicU32::new(0u32);
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub MUTEX_INTEGER,
@@ -33,7 +33,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Could be rewritten as
+    /// Use instead:
     ///
     /// ```rust
     /// enum ValType {
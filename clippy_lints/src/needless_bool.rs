@@ -32,6 +32,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # let x = true;
+    /// # let _ =
     /// if x {
     ///     false
     /// } else {
@@ -43,6 +44,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust
     /// # let x = true;
+    /// # let _ =
     /// !x
     /// # ;
     /// ```
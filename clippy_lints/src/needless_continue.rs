@@ -70,7 +70,21 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Could be rewritten as
+    /// As another example, the following code
+    ///
+    /// ```rust
+    /// # fn waiting() -> bool { false }
+    /// loop {
+    ///     if waiting() {
+    ///         continue;
+    ///     } else {
+    ///         // Do something useful
+    ///     }
+    ///     # break;
+    /// }
+    /// ```
+    ///
+    /// Use instead:
     ///
     /// ```rust
     /// # fn condition() -> bool { false }
@@ -85,20 +99,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// As another example, the following code
-    ///
-    /// ```rust
-    /// # fn waiting() -> bool { false }
-    /// loop {
-    ///     if waiting() {
-    ///         continue;
-    ///     } else {
-    ///         // Do something useful
-    ///     }
-    ///     # break;
-    /// }
-    /// ```
-    /// Could be rewritten as
+    /// and
     ///
     /// ```rust
     /// # fn waiting() -> bool { false }
@@ -49,7 +49,7 @@ declare_clippy_lint! {
     ///     assert_eq!(v.len(), 42);
     /// }
     /// ```
-    /// should be
+    /// Use instead:
     /// ```rust
     /// fn foo(v: &[i32]) {
     ///     assert_eq!(v.len(), 42);
@@ -24,7 +24,7 @@ declare_clippy_lint! {
     /// #     z: i32,
     /// # }
     /// # let zero_point = Point { x: 0, y: 0, z: 0 };
-    /// Point {
+    /// let _ = Point {
     ///     x: 1,
     ///     y: 1,
     ///     z: 1,
@@ -35,7 +35,7 @@ declare_clippy_lint! {
     /// Use instead:
     /// ```rust,ignore
     /// // Missing field `z`
-    /// Point {
+    /// let _ = Point {
     ///     x: 1,
     ///     y: 1,
     ///     ..zero_point
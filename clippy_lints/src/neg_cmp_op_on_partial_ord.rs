@@ -31,10 +31,7 @@ declare_clippy_lint! {
     /// # let a = 1.0;
     /// # let b = f64::NAN;
     ///
-    /// let _not_less_or_equal = match a.partial_cmp(&b) {
-    ///     None | Some(Ordering::Greater) => true,
-    ///     _ => false,
-    /// };
+    /// let _not_less_or_equal = matches!(a.partial_cmp(&b), None | Some(Ordering::Greater));
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub NEG_CMP_OP_ON_PARTIAL_ORD,
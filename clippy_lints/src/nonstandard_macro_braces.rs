@@ -3,6 +3,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+use crate::utils::conf::ConfSchema;
 use clippy_utils::diagnostics::span_lint_and_sugg;
 use clippy_utils::source::snippet_opt;
 use if_chain::if_chain;
@@ -14,7 +15,8 @@ use rustc_lint::{EarlyContext, EarlyLintPass};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::hygiene::{ExpnKind, MacroKind};
 use rustc_span::Span;
-use serde::{de, Deserialize};
+use serde::ser::SerializeStruct;
+use serde::{de, Deserialize, Serialize};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -275,3 +277,26 @@ impl<'de> Deserialize<'de> for MacroMatcher {
         deser.deserialize_struct("MacroMatcher", FIELDS, MacVisitor)
     }
 }
+
+impl Serialize for MacroMatcher {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("MacroMatcher", 2)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("brace", &self.braces.0)?;
+        state.end()
+    }
+}
+
+impl ConfSchema for MacroMatcher {
+    fn schema() -> serde_json::Value {
+        let braces = BRACES.iter().map(|(open, _)| *open).collect::<Vec<_>>();
+        serde_json::json!({
+            "type": "object",
+            "properties": { "name": String::schema(), "brace": { "enum": braces } },
+            "required": ["name", "brace"],
+        })
+    }
+}
@@ -133,7 +133,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # let a = 0.0;
-    /// a + 1.0;
+    /// let _ = a + 1.0;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub FLOAT_ARITHMETIC,
@@ -423,9 +423,9 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// let x = 1;
-    /// 0 / x;
-    /// 0 * x;
-    /// x & 0;
+    /// let _ = 0 / x;
+    /// let _ = 0 * x;
+    /// let _ = x & 0;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub ERASING_OP,
@@ -477,7 +477,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # let x = 1;
-    /// x / 1 + 0 * 1 - 0 | 0;
+    /// let _ = (x / 1 + 0 - 0) | 0;
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub IDENTITY_OP,
@@ -764,7 +764,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Should be:
+    /// Use instead:
     /// ```rust
     /// struct Event {
     ///     x: i32,
@@ -21,7 +21,7 @@ declare_clippy_lint! {
     /// let _ = option_env!("HOME").unwrap();
     /// ```
     ///
-    /// Is better expressed as:
+    /// Use instead:
     ///
     /// ```rust,no_run
     /// let _ = env!("HOME");
@@ -35,8 +35,8 @@ declare_clippy_lint! {
     /// ```rust
     /// # let optional: Option<u32> = Some(0);
     /// # fn do_complicated_function() -> u32 { 5 };
-    /// let _ = if let Some(foo) = optional {
-    ///     foo
+    /// let _ = if let Some(x) = optional {
+    ///     x * 2
     /// } else {
     ///     5
     /// };
@@ -44,25 +44,25 @@ declare_clippy_lint! {
     ///     Some(val) => val + 1,
     ///     None => 5
     /// };
-    /// let _ = if let Some(foo) = optional {
-    ///     foo
+    /// let _ = if let Some(x) = optional {
+    ///     x * 2
     /// } else {
     ///     let y = do_complicated_function();
     ///     y*y
     /// };
     /// ```
     ///
-    /// should be
+    /// Use instead:
     ///
     /// ```rust
     /// # let optional: Option<u32> = Some(0);
     /// # fn do_complicated_function() -> u32 { 5 };
-    /// let _ = optional.map_or(5, |foo| foo);
+    /// let _ = optional.map_or(5, |x| x * 2);
     /// let _ = optional.map_or(5, |val| val + 1);
     /// let _ = optional.map_or_else(||{
     ///     let y = do_complicated_function();
     ///     y*y
-    /// }, |foo| foo);
+    /// }, |x| x * 2);
     /// ```
     // FIXME: Before moving this lint out of nursery, the lint name needs to be updated. It now also
     // covers matches and `Result`.
@@ -27,7 +27,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Could be written:
+    /// Use instead:
     ///
     /// ```rust
     /// let vec = vec![b'a', b'b', b'c'];
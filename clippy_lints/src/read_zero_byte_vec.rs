@@ -30,16 +30,15 @@ declare_clippy_lint! {
     /// use std::io;
     /// fn foo<F: io::Read>(mut f: F) {
     ///     let mut data = Vec::with_capacity(100);
-    ///     f.read(&mut data).unwrap();
+    ///     f.read_exact(&mut data).unwrap();
     /// }
     /// ```
     /// Use instead:
     /// ```rust
     /// use std::io;
     /// fn foo<F: io::Read>(mut f: F) {
-    ///     let mut data = Vec::with_capacity(100);
-    ///     data.resize(100, 0);
-    ///     f.read(&mut data).unwrap();
+    ///     let mut data = vec![0; 100];
+    ///     f.read_exact(&mut data).unwrap();
     /// }
     /// ```
     #[clippy::version = "1.63.0"]
@@ -17,6 +17,7 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
+    /// # #![allow(clippy::needless_return)]
     /// fn my_func(count: u32) {
     ///     if count == 0 {
     ///         print!("Nothing to do");
@@ -28,6 +29,7 @@ declare_clippy_lint! {
     /// ```
     /// Use instead:
     /// ```rust
+    /// # #![allow(clippy::needless_return)]
     /// fn my_func(count: u32) {
     ///     if count == 0 {
     ///         print!("Nothing to do");
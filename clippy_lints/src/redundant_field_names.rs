@@ -23,11 +23,11 @@ declare_clippy_lint! {
     ///     bar: u8,
     /// }
     ///
-    /// let foo = Foo { bar: bar };
+    /// let value = Foo { bar: bar };
     /// ```
     /// the last line can be simplified to
     /// ```ignore
-    /// let foo = Foo { bar };
+    /// let value = Foo { bar };
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub REDUNDANT_FIELD_NAMES,
@@ -24,7 +24,9 @@ declare_clippy_lint! {
     /// }
     /// ```
     /// This function is not visible outside the module and it can be declared with `pub` or
-    /// private visibility
+    /// private visibility.
+    ///
+    /// Use instead:
     /// ```rust
     /// mod internal {
     ///     pub fn internal_fn() { }
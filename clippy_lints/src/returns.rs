@@ -30,7 +30,7 @@ declare_clippy_lint! {
     ///     x
     /// }
     /// ```
-    /// instead, use
+    /// Use instead:
     /// ```
     /// fn foo() -> String {
     ///     String::new()
@@ -56,7 +56,7 @@ declare_clippy_lint! {
     ///     return x;
     /// }
     /// ```
-    /// simplify to
+    /// Use instead:
     /// ```rust
     /// fn foo(x: usize) -> usize {
     ///     x
@@ -53,7 +53,9 @@ declare_clippy_lint! {
     /// let x = 2;
     /// let x = x + 1;
     /// ```
-    /// use different variable name:
+    /// Use a different variable name.
+    ///
+    /// Use instead:
     /// ```rust
     /// let x = 2;
     /// let y = x + 1;
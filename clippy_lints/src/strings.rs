@@ -25,11 +25,16 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
+    /// # #![allow(clippy::assign_op_pattern)]
     /// let mut x = "Hello".to_owned();
     /// x = x + ", World";
+    /// ```
     ///
-    /// // More readable
+    /// Use instead:
+    /// ```rust
+    /// let mut x = "Hello".to_owned();
     /// x += ", World";
+    /// // or
     /// x.push_str(", World");
     /// ```
     #[clippy::version = "pre 1.29.0"]
@@ -230,12 +235,12 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// std::str::from_utf8(&"Hello World!".as_bytes()[6..11]).unwrap();
+    /// let _ = std::str::from_utf8(&"Hello World!".as_bytes()[6..11]).unwrap();
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// &"Hello World!"[6..11];
+    /// let _ = &"Hello World!"[6..11];
     /// ```
     #[clippy::version = "1.50.0"]
     pub STRING_FROM_UTF8_AS_BYTES,
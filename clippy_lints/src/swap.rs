@@ -31,7 +31,7 @@ declare_clippy_lint! {
     /// b = a;
     /// a = t;
     /// ```
-    /// Use std::mem::swap():
+    /// Use instead:
     /// ```rust
     /// let mut a = 1;
     /// let mut b = 2;
@@ -57,7 +57,9 @@ declare_clippy_lint! {
     /// a = b;
     /// b = a;
     /// ```
-    /// If swapping is intended, use `swap()` instead:
+    /// If swapping is intended, use `swap()`.
+    ///
+    /// Use instead:
     /// ```rust
     /// # let mut a = 1;
     /// # let mut b = 2;
@@ -33,7 +33,7 @@ declare_clippy_lint! {
     ///}
     /// ```
     ///
-    /// Will be converted to:
+    /// Use instead:
     /// ```rust
     /// ///
     /// /// Struct to hold two strings:
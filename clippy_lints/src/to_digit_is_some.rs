@@ -22,7 +22,7 @@ declare_clippy_lint! {
     /// # let radix = 10;
     /// let is_digit = c.to_digit(radix).is_some();
     /// ```
-    /// can be written as:
+    /// Use instead:
     /// ```
     /// # let c = 'c';
     /// # let radix = 10;
@@ -54,6 +54,14 @@ declare_clippy_lint! {
     /// fn func<T: Clone + Default>(arg: T) where T: Clone + Default {}
     /// ```
     ///
+    /// ```rust
+    /// fn foo<T: Default + Default>(bar: T) {}
+    /// ```
+    ///
+    /// ```rust
+    /// fn foo<T>(bar: T) where T: Default + Default {}
+    /// ```
+    ///
     /// Use instead:
     /// ```rust
     /// # mod hidden {
@@ -66,18 +74,10 @@ declare_clippy_lint! {
     /// ```
     ///
     /// ```rust
-    /// fn foo<T: Default + Default>(bar: T) {}
-    /// ```
-    /// Use instead:
-    /// ```rust
     /// fn foo<T: Default>(bar: T) {}
     /// ```
     ///
     /// ```rust
-    /// fn foo<T>(bar: T) where T: Default + Default {}
-    /// ```
-    /// Use instead:
-    /// ```rust
     /// fn foo<T>(bar: T) where T: Default {}
     /// ```
     #[clippy::version = "1.47.0"]
@@ -78,13 +78,13 @@ declare_clippy_lint! {
     /// ### Example
     ///
     /// ```rust
-    /// # let p: *const [i32] = &[];
-    /// unsafe { std::mem::transmute::<*const [i32], *const [u16]>(p) };
+    /// # let p: *const i32 = &0;
+    /// let addr = unsafe { std::mem::transmute::<*const i32, usize>(p) };
     /// ```
     /// Use instead:
     /// ```rust
-    /// # let p: *const [i32] = &[];
-    /// p as *const [u16];
+    /// # let p: *const i32 = &0;
+    /// let addr = p as usize;
     /// ```
     #[clippy::version = "1.47.0"]
     pub TRANSMUTES_EXPRESSIBLE_AS_PTR_CASTS,
@@ -348,7 +348,9 @@ declare_clippy_lint! {
     /// };
     /// ```
     ///
-    /// You must always iterate, map and collect the values:
+    /// Iterate over the values, map them and collect them.
+    ///
+    /// Use instead:
     ///
     /// ```rust
     /// vec![2_u16].into_iter().map(u32::from).collect::<Vec<_>>();
@@ -402,7 +404,8 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// let null_ref: &u64 = unsafe { std::mem::transmute(0 as *const u64) };
+    /// # #![allow(clippy::transmute_ptr_to_ref)]
+    /// let null_ref: &u64 = unsafe { std::mem::transmute(std::ptr::null::<u64>()) };
     /// ```
     #[clippy::version = "1.35.0"]
     pub TRANSMUTING_NULL,
@@ -69,7 +69,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Better:
+    /// Use instead:
     ///
     /// ```rust
     /// struct X {
@@ -102,7 +102,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// Better:
+    /// Use instead:
     ///
     /// ```rust
     /// pub enum Contents {
@@ -153,7 +153,9 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// # use std::collections::LinkedList;
-    /// let x: LinkedList<usize> = LinkedList::new();
+    /// struct Queue {
+    ///     items: LinkedList<usize>,
+    /// }
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub LINKEDLIST,
@@ -201,7 +203,7 @@ declare_clippy_lint! {
     /// fn foo(bar: Rc<&usize>) {}
     /// ```
     ///
-    /// Better:
+    /// Use instead:
     ///
     /// ```rust
     /// fn foo(bar: &usize) {}
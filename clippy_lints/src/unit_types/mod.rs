@@ -18,7 +18,7 @@ declare_clippy_lint! {
     /// ### Example
     /// ```rust
     /// let x = {
-    ///     1;
+    ///     println!("unit");
     /// };
     /// ```
     #[clippy::version = "pre 1.29.0"]
@@ -50,7 +50,16 @@ declare_clippy_lint! {
     ///     baz();
     /// }
     /// ```
-    /// is equal to
+    ///
+    /// For asserts:
+    /// ```rust
+    /// # fn foo() {};
+    /// # fn bar() {};
+    /// assert_eq!({ foo(); }, { bar(); });
+    /// ```
+    /// will always succeed, as the unit values are always equal.
+    ///
+    /// Use instead:
     /// ```rust
     /// # fn foo() {};
     /// # fn bar() {};
@@ -61,14 +70,6 @@ declare_clippy_lint! {
     ///     baz();
     /// }
     /// ```
-    ///
-    /// For asserts:
-    /// ```rust
-    /// # fn foo() {};
-    /// # fn bar() {};
-    /// assert_eq!({ foo(); }, { bar(); });
-    /// ```
-    /// will always succeed
     #[clippy::version = "pre 1.29.0"]
     pub UNIT_CMP,
     correctness,
@@ -23,7 +23,7 @@ declare_clippy_lint! {
     ///     ()
     /// }
     /// ```
-    /// is equivalent to
+    /// Use instead:
     /// ```rust
     /// fn return_unit() {}
     /// ```
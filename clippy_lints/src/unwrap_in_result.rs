@@ -36,7 +36,7 @@ declare_clippy_lint! {
     /// }
     /// ```
     ///
-    /// After:
+    /// Use instead:
     /// ```rust
     /// fn divisible_by_3(i_str: String) -> Result<(), String> {
     ///     let i = i_str
@@ -27,11 +27,11 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// struct HTTPResponse;
+    /// struct HTTP;
     /// ```
     /// Use instead:
     /// ```rust
-    /// struct HttpResponse;
+    /// struct Http;
     /// ```
     #[clippy::version = "1.51.0"]
     pub UPPER_CASE_ACRONYMS,
@@ -40,7 +40,7 @@ declare_clippy_lint! {
     ///     }
     /// }
     /// ```
-    /// could be
+    /// Use instead:
     /// ```rust
     /// struct Foo;
     /// impl Foo {
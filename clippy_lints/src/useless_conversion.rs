@@ -21,13 +21,15 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
+    /// # let name = "world";
     /// // format!() returns a `String`
-    /// let s: String = format!("hello").into();
+    /// let s: String = format!("hello {name}").into();
     /// ```
     ///
     /// Use instead:
     /// ```rust
-    /// let s: String = format!("hello");
+    /// # let name = "world";
+    /// let s: String = format!("hello {name}");
     /// ```
     #[clippy::version = "1.45.0"]
     pub USELESS_CONVERSION,
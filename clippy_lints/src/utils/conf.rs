@@ -2,12 +2,24 @@
 
 #![allow(clippy::module_name_repetitions)]
 
-use serde::de::{Deserializer, IgnoredAny, IntoDeserializer, MapAccess, Visitor};
-use serde::Deserialize;
-use std::error::Error;
+use clippy_utils::path_patterns::{span_file_path, PathPattern};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_errors::Applicability;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_hir::HirId;
+use rustc_lint::{LateContext, Level};
+use rustc_session::Session;
+use rustc_span::{Span, Symbol};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{self, Deserializer, IgnoredAny, IntoDeserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::marker::PhantomData;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::{cmp, env, fmt, fs, io, iter};
+use std::{cmp, env, fmt, fs, io};
 
 #[rustfmt::skip]
 const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
@@ -32,21 +44,335 @@ const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
 ];
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
 
+/// Types of configuration values, described in the JSON schema of the configuration file.
+pub trait ConfSchema {
+    fn schema() -> serde_json::Value;
+}
+
+impl ConfSchema for bool {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "boolean" })
+    }
+}
+
+impl ConfSchema for u64 {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl ConfSchema for u128 {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl ConfSchema for String {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "string" })
+    }
+}
+
+/// Optional values are written by leaving the key out.
+impl<T: ConfSchema> ConfSchema for Option<T> {
+    fn schema() -> serde_json::Value {
+        T::schema()
+    }
+}
+
+impl<T: ConfSchema> ConfSchema for Vec<T> {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ConfSchema> ConfSchema for FxHashSet<T> {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ConfSchema, const N: usize> ConfSchema for [T; N] {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "array", "items": T::schema(), "minItems": N, "maxItems": N })
+    }
+}
+
 /// Holds information used by `MISSING_ENFORCED_IMPORT_RENAMES` lint.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Rename {
     pub path: String,
     pub rename: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// A path disallowed by one of the `disallowed-*` configurations, `REPLACEABLE` telling whether
+/// its lint can suggest a `replacement`.
+#[derive(Clone, Debug, Serialize)]
 #[serde(untagged)]
-pub enum DisallowedPath {
+pub enum DisallowedPath<const REPLACEABLE: bool = true> {
     Simple(String),
-    WithReason { path: String, reason: Option<String> },
+    #[serde(rename_all = "kebab-case")]
+    WithReason {
+        path: String,
+        reason: Option<String>,
+        /// The modules, like `crate::config`, and the files, like `src/config/*.rs`, where the
+        /// path is allowed.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        allowed_in: Vec<String>,
+        /// The path to suggest instead, always `None` if the lint can't suggest one.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+    },
+}
+
+/// A trait implementation disallowed by `disallowed-impls`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DisallowedImpl {
+    /// The path of the trait.
+    pub path: String,
+    pub reason: Option<String>,
+    /// The modules and files where the implementations are allowed, like the `allowed-in` of a
+    /// [`DisallowedPath`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_in: Vec<String>,
+    /// The path of the trait to suggest instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    /// A path pattern like `crate::secrets` or `crate::*::Secret*`, only the implementations for
+    /// the types matching it are disallowed. A pattern matching a module matches every type in it.
+    pub self_type: Option<String>,
+    /// The path of a type, only the implementations for types with a field containing it are
+    /// disallowed.
+    pub field_type: Option<String>,
+}
+
+/// A rule of the `layering` configuration.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayeringRule {
+    /// The path of the module the rule applies to, along with its submodules, like
+    /// `crate::domain`.
+    pub module: String,
+    /// The paths of the crates and modules the module may not use, like `sqlx` or `crate::infra`.
+    pub disallowed: Vec<String>,
+    pub reason: Option<String>,
+}
+
+/// An out-of-tree lint plugin, see `clippy_utils::plugin`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintPlugin {
+    /// The path of the dynamic library, relative to the configuration file.
+    pub path: String,
+    /// The configuration of the plugin, passed to it as TOML.
+    #[serde(default)]
+    pub conf: toml::value::Table,
+}
+
+/// A lint declared in a `[[custom-lints]]` section of the configuration.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomLint {
+    /// The name of the lint, used as `clippy::name` in attributes and lint flags, keeping its
+    /// position for diagnostics.
+    pub name: toml::Spanned<String>,
+    #[serde(default = "default_custom_lint_level")]
+    pub level: LintLevel,
+    /// The message of the warnings.
+    pub message: String,
+    pub pattern: CustomLintPattern,
+}
+
+fn default_custom_lint_level() -> LintLevel {
+    LintLevel::Warn
+}
+
+/// The code linted by a [`CustomLint`]. Either `call` or `method` is set, the other fields
+/// restrict the matching calls.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CustomLintPattern {
+    /// The path of the function or method whose calls are linted.
+    pub call: Option<String>,
+    /// The name of the method whose calls are linted, whatever the type it's called on.
+    pub method: Option<String>,
+    /// The path of a trait the receiver of the method, or the first argument of the function, has
+    /// to implement.
+    pub receiver_implements: Option<String>,
+    /// Only lint calls with a literal argument.
+    #[serde(default)]
+    pub literal_argument: bool,
+    /// Only lint calls in an `async` function, block or closure.
+    #[serde(default)]
+    pub in_async: bool,
+}
+
+impl ConfSchema for Rename {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "path": String::schema(), "rename": String::schema() },
+            "required": ["path", "rename"],
+        })
+    }
+}
+
+impl<const REPLACEABLE: bool> ConfSchema for DisallowedPath<REPLACEABLE> {
+    fn schema() -> serde_json::Value {
+        let mut properties = json!({
+            "path": String::schema(),
+            "reason": String::schema(),
+            "allowed-in": Vec::<String>::schema(),
+        });
+        if REPLACEABLE {
+            properties["replacement"] = String::schema();
+        }
+        json!({
+            "anyOf": [
+                String::schema(),
+                {
+                    "type": "object",
+                    "properties": properties,
+                    "required": ["path"],
+                    "additionalProperties": false,
+                },
+            ],
+        })
+    }
+}
+
+impl ConfSchema for DisallowedImpl {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": String::schema(),
+                "reason": String::schema(),
+                "allowed-in": Vec::<String>::schema(),
+                "replacement": String::schema(),
+                "self-type": String::schema(),
+                "field-type": String::schema(),
+            },
+            "required": ["path"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ConfSchema for LayeringRule {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "module": String::schema(),
+                "disallowed": Vec::<String>::schema(),
+                "reason": String::schema(),
+            },
+            "required": ["module", "disallowed"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ConfSchema for LintPlugin {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "path": String::schema(), "conf": { "type": "object" } },
+            "required": ["path"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ConfSchema for CustomLint {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": String::schema(),
+                "level": LintLevel::schema(),
+                "message": String::schema(),
+                "pattern": CustomLintPattern::schema(),
+            },
+            "required": ["name", "message", "pattern"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ConfSchema for CustomLintPattern {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "call": String::schema(),
+                "method": String::schema(),
+                "receiver-implements": String::schema(),
+                "literal-argument": bool::schema(),
+                "in-async": bool::schema(),
+            },
+            "additionalProperties": false,
+        })
+    }
+}
+
+/// The table form of a [`DisallowedPath`]. It's deserialized on its own rather than as a variant
+/// of an untagged enum, whose errors don't say which field is wrong.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct DisallowedPathTable {
+    path: String,
+    reason: Option<String>,
+    #[serde(default)]
+    allowed_in: Vec<String>,
+    replacement: Option<String>,
 }
 
-impl DisallowedPath {
+impl<'de, const REPLACEABLE: bool> Deserialize<'de> for DisallowedPath<REPLACEABLE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct DisallowedPathVisitor<const REPLACEABLE: bool>;
+
+        impl<'de, const REPLACEABLE: bool> Visitor<'de> for DisallowedPathVisitor<REPLACEABLE> {
+            type Value = DisallowedPath<REPLACEABLE>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a path or a table with a `path`")
+            }
+
+            fn visit_str<E>(self, path: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(DisallowedPath::Simple(path.to_string()))
+            }
+
+            fn visit_map<V>(self, map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let table = DisallowedPathTable::deserialize(MapAccessDeserializer::new(map))?;
+                if !REPLACEABLE && table.replacement.is_some() {
+                    return Err(de::Error::custom("this lint can't suggest a `replacement`"));
+                }
+                Ok(DisallowedPath::WithReason {
+                    path: table.path,
+                    reason: table.reason,
+                    allowed_in: table.allowed_in,
+                    replacement: table.replacement,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(DisallowedPathVisitor)
+    }
+}
+
+impl<const REPLACEABLE: bool> DisallowedPath<REPLACEABLE> {
     pub fn path(&self) -> &str {
         let (Self::Simple(path) | Self::WithReason { path, .. }) = self;
 
@@ -61,39 +387,569 @@ impl DisallowedPath {
             _ => None,
         }
     }
+
+    pub fn allowed_in(&self) -> &[String] {
+        match self {
+            Self::WithReason { allowed_in, .. } => allowed_in,
+            Self::Simple(_) => &[],
+        }
+    }
+
+    pub fn replacement(&self) -> Option<&str> {
+        match self {
+            Self::WithReason { replacement, .. } => replacement.as_deref(),
+            Self::Simple(_) => None,
+        }
+    }
+
+    /// Checks if the path is allowed at `span`, in the module containing the node `hir_id`.
+    ///
+    /// The entries of `allowed-in` containing a `/` or a `*`, or ending in `.rs`, are patterns
+    /// matched against the file containing `span`. The other entries are module paths, starting
+    /// with `crate` or the name of the crate, that also allow the path in their submodules.
+    pub fn is_allowed_at(&self, cx: &LateContext<'_>, hir_id: HirId, span: Span) -> bool {
+        is_allowed_in(cx, self.allowed_in(), hir_id, span)
+    }
+}
+
+impl DisallowedImpl {
+    pub fn reason(&self) -> Option<String> {
+        self.reason
+            .as_ref()
+            .map(|reason| format!("{reason} (from clippy.toml)"))
+    }
+
+    /// Checks if the implementation at `span`, with the node `hir_id`, is allowed by `allowed-in`,
+    /// see [`DisallowedPath::is_allowed_at`].
+    pub fn is_allowed_at(&self, cx: &LateContext<'_>, hir_id: HirId, span: Span) -> bool {
+        is_allowed_in(cx, &self.allowed_in, hir_id, span)
+    }
+}
+
+/// Checks if the `allowed-in` entries `allowed_in` allow a path at `span`, in the module
+/// containing the node `hir_id`.
+fn is_allowed_in(cx: &LateContext<'_>, allowed_in: &[String], hir_id: HirId, span: Span) -> bool {
+    if allowed_in.is_empty() {
+        return false;
+    }
+
+    let file = span_file_path(cx.sess(), span);
+    let module = cx.get_def_path(cx.tcx.parent_module(hir_id).to_def_id());
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    allowed_in.iter().any(|entry| {
+        if entry.contains(['/', '*']) || entry.ends_with(".rs") {
+            return file
+                .as_deref()
+                .map_or(false, |file| PathPattern::new(entry).matches(file));
+        }
+        let segments: Vec<_> = entry
+            .split("::")
+            .enumerate()
+            .map(|(i, segment)| {
+                if i == 0 && segment == "crate" {
+                    crate_name
+                } else {
+                    Symbol::intern(segment)
+                }
+            })
+            .collect();
+        module.starts_with(&segments)
+    })
+}
+
+/// The level of a lint or lint group in the `[lints]` table.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+            Self::Forbid => "forbid",
+        }
+    }
+
+    pub fn as_level(self) -> Level {
+        match self {
+            Self::Allow => Level::Allow,
+            Self::Warn => Level::Warn,
+            Self::Deny => Level::Deny,
+            Self::Forbid => Level::Forbid,
+        }
+    }
+}
+
+impl ConfSchema for LintLevel {
+    fn schema() -> serde_json::Value {
+        json!({ "enum": ["allow", "warn", "deny", "forbid"] })
+    }
+}
+
+/// An entry of the `[lints]` table, written either as `lint = "level"` or as
+/// `lint = { level = "level", priority = 1 }`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum LintLevelConf {
+    Level(LintLevel),
+    WithPriority {
+        level: LintLevel,
+        #[serde(default)]
+        priority: i64,
+    },
+}
+
+impl LintLevelConf {
+    pub fn level(&self) -> LintLevel {
+        let (Self::Level(level) | Self::WithPriority { level, .. }) = self;
+
+        *level
+    }
+
+    pub fn priority(&self) -> i64 {
+        match self {
+            Self::Level(_) => 0,
+            Self::WithPriority { priority, .. } => *priority,
+        }
+    }
+}
+
+impl ConfSchema for LintLevelConf {
+    fn schema() -> serde_json::Value {
+        json!({
+            "anyOf": [
+                LintLevel::schema(),
+                {
+                    "type": "object",
+                    "properties": { "level": LintLevel::schema(), "priority": { "type": "integer" } },
+                    "required": ["level"],
+                },
+            ],
+        })
+    }
+}
+
+/// The entries of a `lints` table, keeping the position of the lint names for diagnostics.
+#[derive(Clone, Debug, Default)]
+pub struct LintLevels {
+    entries: Vec<(toml::Spanned<String>, LintLevelConf)>,
+}
+
+impl LintLevels {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &LintLevelConf)> {
+        self.entries.iter().map(|(name, conf)| (name.get_ref().as_str(), conf))
+    }
+
+    /// Returns the normalized lint names and their levels in the order they are applied: by
+    /// increasing priority, then lint groups before individual lints.
+    fn sorted_levels(&self) -> Vec<(String, Level)> {
+        let mut lints = self
+            .iter()
+            .map(|(name, conf)| (normalize_lint_name(name), conf))
+            .collect::<Vec<_>>();
+        lints.sort_by_key(|(name, conf)| (conf.priority(), !is_lint_group(name)));
+        lints
+            .into_iter()
+            .map(|(name, conf)| (name, conf.level().as_level()))
+            .collect()
+    }
+
+    /// Collects the names that are neither a Clippy lint nor a lint group, `table` describing where
+    /// this table is in the configuration file.
+    fn check_names(&self, table: &str, unknown_lints: &mut Vec<UnknownLint>) {
+        for (name, _) in &self.entries {
+            let normalized = normalize_lint_name(name.get_ref());
+            if is_lint_group(&normalized) || is_known_lint(&normalized) {
+                continue;
+            }
+            let warning = ConfError::spanned(
+                format!("unknown lint `{}` in {table}", name.get_ref()),
+                name.start()..name.end(),
+            );
+            unknown_lints.push(UnknownLint {
+                name: format!("clippy::{normalized}"),
+                warning: match closest_lint_name(&normalized) {
+                    Some(lint) => warning.with_suggestion("perhaps you meant", lint, Applicability::MaybeIncorrect),
+                    None => warning,
+                },
+            });
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LintLevels {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LintLevelsVisitor;
+
+        impl<'de> Visitor<'de> for LintLevelsVisitor {
+            type Value = LintLevels;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a table of lint levels")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(name) = map.next_key()? {
+                    entries.push((name, map.next_value()?));
+                }
+                Ok(LintLevels { entries })
+            }
+        }
+
+        deserializer.deserialize_map(LintLevelsVisitor)
+    }
+}
+
+impl Serialize for LintLevels {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (name, conf) in &self.entries {
+            map.serialize_entry(name.get_ref(), conf)?;
+        }
+        map.end()
+    }
+}
+
+impl ConfSchema for LintLevels {
+    fn schema() -> serde_json::Value {
+        json!({ "type": "object", "additionalProperties": LintLevelConf::schema() })
+    }
+}
+
+/// An `[[overrides]]` section, adjusting the configuration for the source files matching one of
+/// the `paths` patterns.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ConfOverride {
+    /// Glob patterns matched against the paths of the source files relative to the crate root.
+    pub paths: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cognitive_complexity_threshold: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_many_arguments_threshold: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_many_lines_threshold: Option<u64>,
+    /// The levels of the lints in the matching files.
+    #[serde(default)]
+    pub lints: LintLevels,
+}
+
+impl ConfSchema for ConfOverride {
+    fn schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": Vec::<String>::schema(),
+                "cognitive-complexity-threshold": u64::schema(),
+                "too-many-arguments-threshold": u64::schema(),
+                "too-many-lines-threshold": u64::schema(),
+                "lints": LintLevels::schema(),
+            },
+            "required": ["paths"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+/// The `[[overrides]]` sections of the configuration, ready to be matched against spans.
+#[derive(Clone, Debug, Default)]
+pub struct PathOverrides {
+    overrides: Vec<(Vec<PathPattern>, ConfOverride)>,
+}
+
+impl PathOverrides {
+    pub fn new(overrides: &[ConfOverride]) -> Self {
+        Self {
+            overrides: overrides
+                .iter()
+                .map(|conf| {
+                    (
+                        conf.paths.iter().map(|path| PathPattern::new(path)).collect(),
+                        conf.clone(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the value set by the last override matching the file containing `span`, if any.
+    fn get<T>(&self, sess: &Session, span: Span, value: impl Fn(&ConfOverride) -> Option<T>) -> Option<T> {
+        if self.overrides.is_empty() {
+            return None;
+        }
+        let path = span_file_path(sess, span)?;
+        self.overrides
+            .iter()
+            .rev()
+            .filter(|(patterns, _)| patterns.iter().any(|pattern| pattern.matches(&path)))
+            .find_map(|(_, conf)| value(conf))
+    }
+
+    pub fn cognitive_complexity_threshold(&self, sess: &Session, span: Span) -> Option<u64> {
+        self.get(sess, span, |conf| conf.cognitive_complexity_threshold)
+    }
+
+    pub fn too_many_arguments_threshold(&self, sess: &Session, span: Span) -> Option<u64> {
+        self.get(sess, span, |conf| conf.too_many_arguments_threshold)
+    }
+
+    pub fn too_many_lines_threshold(&self, sess: &Session, span: Span) -> Option<u64> {
+        self.get(sess, span, |conf| conf.too_many_lines_threshold)
+    }
+
+    /// Returns the lint levels set by each override, with lint groups expanded to their lints, in
+    /// the form expected by `clippy_utils::path_lint_levels::set_path_lint_levels`. Like in the
+    /// `[lints]` table, the entries are ordered by priority, then lint groups first.
+    pub fn lint_levels(&self) -> Vec<(Vec<PathPattern>, Vec<(String, Level)>)> {
+        self.overrides
+            .iter()
+            .filter(|(_, conf)| !conf.lints.is_empty())
+            .map(|(patterns, conf)| {
+                let levels = conf
+                    .lints
+                    .sorted_levels()
+                    .into_iter()
+                    .flat_map(|(name, level)| expand_lint_group(&name).into_iter().map(move |lint| (lint, level)))
+                    .collect();
+                (patterns.clone(), levels)
+            })
+            .collect()
+    }
+}
+
+/// The lint groups that can be used as keys in the `[lints]` table.
+const LINT_GROUPS: &[&str] = &[
+    "all",
+    "cargo",
+    "complexity",
+    "correctness",
+    "nursery",
+    "pedantic",
+    "perf",
+    "restriction",
+    "style",
+    "suspicious",
+    #[cfg(feature = "internal")]
+    "internal",
+];
+
+/// Normalizes a key of the `[lints]` table, accepting an optional `clippy::` prefix and dashes
+/// in place of underscores.
+fn normalize_lint_name(name: &str) -> String {
+    name.strip_prefix("clippy::").unwrap_or(name).replace('-', "_")
+}
+
+pub fn is_lint_group(name: &str) -> bool {
+    LINT_GROUPS.contains(&name)
+}
+
+/// Returns the names of the lints in the group `name` in the `clippy::lint_name` form, or just the
+/// lint itself if `name` is not a group.
+pub fn expand_lint_group(name: &str) -> Vec<String> {
+    if is_lint_group(name) {
+        crate::declared_lints::LINTS
+            .iter()
+            .filter(|info| info.category.name() == name || (name == "all" && info.category.is_all()))
+            .map(|info| info.lint.name_lower())
+            .collect()
+    } else {
+        vec![format!("clippy::{name}")]
+    }
+}
+
+fn is_known_lint(name: &str) -> bool {
+    let target = format!("clippy::{}", name.to_ascii_uppercase());
+    let renamed = format!("clippy::{name}");
+    crate::declared_lints::LINTS.iter().any(|info| info.lint.name == target)
+        || crate::renamed_lints::RENAMED_LINTS
+            .iter()
+            .any(|(old_name, _)| *old_name == renamed)
+}
+
+/// Returns the lint or lint group with the name closest to `name`, if it is likely a typo.
+fn closest_lint_name(name: &str) -> Option<String> {
+    let lints = crate::declared_lints::LINTS
+        .iter()
+        .map(|info| info.lint.name_lower())
+        .collect::<Vec<_>>();
+    let candidates = lints
+        .iter()
+        .map(|lint| lint.trim_start_matches("clippy::"))
+        .chain(LINT_GROUPS.iter().copied());
+    closest_name(name, candidates).map(ToString::to_string)
+}
+
+/// Returns the candidate closest to `name`, if it is close enough to be a likely typo.
+fn closest_name<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = cmp::max(name.len(), 3) / 3;
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The number of single character insertions, deletions and substitutions to go from `a` to `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != b);
+            diagonal = row[j + 1];
+            row[j + 1] = cmp::min(substitution, cmp::min(row[j], row[j + 1]) + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Removes the module paths from a type as written in `define_Conf!`, e.g.
+/// `Vec<crate::utils::conf::Rename>` becomes `Vec<Rename>`.
+fn display_type(ty: &str) -> String {
+    let mut result = String::new();
+    let mut rest = ty;
+    while let Some((before, after)) = rest.split_once("::") {
+        let segment_start = before
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |i| i + 1);
+        result.push_str(&before[..segment_start]);
+        rest = after;
+    }
+    result.push_str(rest);
+    result
 }
 
 /// Conf with parse errors
 #[derive(Default)]
 pub struct TryConf {
     pub conf: Conf,
-    pub errors: Vec<Box<dyn Error>>,
-    pub warnings: Vec<Box<dyn Error>>,
+    pub errors: Vec<ConfError>,
+    pub warnings: Vec<ConfError>,
+    /// The names of the `lints` tables that aren't Clippy lints. They can still be the names of
+    /// the lints of a lint plugin or of a custom lint, so they are only reported once all the lints
+    /// are registered.
+    pub unknown_lints: Vec<UnknownLint>,
 }
 
 impl TryConf {
-    fn from_error(error: impl Error + 'static) -> Self {
+    fn from_error(error: ConfError) -> Self {
         Self {
             conf: Conf::default(),
-            errors: vec![Box::new(error)],
+            errors: vec![error],
             warnings: vec![],
+            unknown_lints: vec![],
         }
     }
 }
 
-#[derive(Debug)]
-struct ConfError(String);
+/// A name of a `lints` table that isn't the name of a Clippy lint or lint group.
+#[derive(Clone, Debug)]
+pub struct UnknownLint {
+    /// The name, in the `clippy::lint_name` form.
+    pub name: String,
+    pub warning: ConfError,
+}
 
-impl fmt::Display for ConfError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <String as fmt::Display>::fmt(&self.0, f)
-    }
+/// An error or a warning found when reading a configuration file.
+#[derive(Clone, Debug)]
+pub struct ConfError {
+    pub message: String,
+    /// The configuration file the diagnostic is in, if it's not the file that was read but one
+    /// that it inherits from.
+    pub file: Option<PathBuf>,
+    /// The range of bytes of the file the diagnostic points to, if known.
+    pub span: Option<Range<usize>>,
+    pub help: Option<String>,
+    /// The message and replacement of a suggestion for the text at `span`.
+    pub suggestion: Option<(String, String, Applicability)>,
 }
 
-impl Error for ConfError {}
+impl ConfError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            file: None,
+            span: None,
+            help: None,
+            suggestion: None,
+        }
+    }
+
+    fn spanned(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            span: Some(span),
+            ..Self::new(message)
+        }
+    }
+
+    fn in_file(self, file: &Path) -> Self {
+        Self {
+            file: Some(file.to_path_buf()),
+            ..self
+        }
+    }
+
+    fn with_help(self, help: impl Into<String>) -> Self {
+        Self {
+            help: Some(help.into()),
+            ..self
+        }
+    }
 
-fn conf_error(s: impl Into<String>) -> Box<dyn Error> {
-    Box::new(ConfError(s.into()))
+    fn with_suggestion(self, message: &str, replacement: impl Into<String>, applicability: Applicability) -> Self {
+        Self {
+            suggestion: Some((message.to_string(), replacement.into(), applicability)),
+            ..self
+        }
+    }
+
+    /// Converts an error of the `toml` parser for the file `content`, moving the position from
+    /// the message to the span.
+    fn from_toml(content: &str, error: &toml::de::Error) -> Self {
+        let message = error.to_string();
+        let Some((line, column)) = error.line_col() else {
+            return Self::new(message);
+        };
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message);
+        let start = content.split_inclusive('\n').take(line).map(str::len).sum::<usize>() + column;
+        let end = content
+            .get(start..)
+            .and_then(|rest| rest.chars().next())
+            .map_or(start, |c| start + c.len_utf8());
+        Self::spanned(message, start..end)
+    }
+}
+
+impl fmt::Display for ConfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
 }
 
 macro_rules! define_Conf {
@@ -105,6 +961,14 @@ macro_rules! define_Conf {
         /// Clippy lint configuration
         pub struct Conf {
             $($(#[doc = $doc])+ pub $name: $ty,)*
+            /// The lint levels set in the `[lints]` table, keyed by lint or lint group name.
+            pub lints: LintLevels,
+            /// The `[[overrides]]` sections, adjusting the configuration for some source files.
+            pub overrides: Vec<ConfOverride>,
+            /// The `[[lint-plugins]]` sections, the out-of-tree lint plugins to load.
+            pub lint_plugins: Vec<LintPlugin>,
+            /// The lints declared in `[[custom-lints]]` sections.
+            pub custom_lints: Vec<CustomLint>,
         }
 
         mod defaults {
@@ -113,10 +977,121 @@ macro_rules! define_Conf {
 
         impl Default for Conf {
             fn default() -> Self {
-                Self { $($name: defaults::$name(),)* }
+                Self {
+                    $($name: defaults::$name(),)*
+                    lints: LintLevels::default(),
+                    overrides: Vec::new(),
+                    lint_plugins: Vec::new(),
+                    custom_lints: Vec::new(),
+                }
+            }
+        }
+
+        impl Conf {
+            /// Returns the value of every configuration key, in the `kebab-case` form used in the
+            /// configuration file. Deprecated keys are left out.
+            pub fn values(&self) -> Vec<(String, serde_json::Value)> {
+                let values = vec![
+                    $((stringify!($name).replace('_', "-"), serde_json::to_value(&self.$name)),)*
+                    ("lints".to_string(), serde_json::to_value(&self.lints)),
+                    ("overrides".to_string(), serde_json::to_value(&self.overrides)),
+                    ("lint-plugins".to_string(), serde_json::to_value(&self.lint_plugins)),
+                    ("custom-lints".to_string(), serde_json::to_value(&self.custom_lints)),
+                ];
+                values
+                    .into_iter()
+                    .filter(|(name, _)| canonical_key(name) == *name)
+                    .map(|(name, value)| (name, value.unwrap_or(serde_json::Value::Null)))
+                    .collect()
             }
         }
 
+        /// Returns the default value of the configuration key `key`, if it can be written in TOML.
+        fn default_value(key: &str) -> Option<toml::Value> {
+            let key = key.replace('_', "-");
+            $(
+                if key == stringify!($name).replace('_', "-") {
+                    return toml::Value::try_from(defaults::$name()).ok();
+                }
+            )*
+            None
+        }
+
+        /// Returns the key replacing the deprecated key `key`, or `key` itself, in `kebab-case`.
+        fn canonical_key(key: &str) -> String {
+            let key = key.replace('_', "-");
+            $($(
+                if key == stringify!($name).replace('_', "-") {
+                    return stringify!($new_conf).replace('_', "-");
+                }
+            )?)*
+            key
+        }
+
+        /// Returns the configuration keys affecting the lint `name`, given in `UPPER_CASE`, as listed
+        /// in the `Lint: ...` paragraph of their documentation. Deprecated keys are left out.
+        pub fn lint_conf_keys(name: &str) -> Vec<String> {
+            let mut keys = Vec::new();
+            $(
+                if doc_lint_names(concat!($($doc, '\n',)*)).any(|lint| lint == name) {
+                    keys.push(stringify!($name).replace('_', "-"));
+                }
+            )*
+            keys.retain(|key| canonical_key(key) == *key);
+            keys
+        }
+
+        /// Returns the JSON schema of the configuration file, used by editors to offer completion
+        /// and validation in `clippy.toml`.
+        pub fn json_schema() -> serde_json::Value {
+            let mut properties = serde_json::Map::new();
+            $(
+                let mut schema = <$ty as ConfSchema>::schema();
+                let description = schema_description(concat!($($doc, '\n',)*));
+                if !description.is_empty() {
+                    schema["description"] = description.into();
+                }
+                if let Ok(default) = serde_json::to_value(defaults::$name()) && !default.is_null() {
+                    schema["default"] = default;
+                }
+                $(
+                    schema["description"] = $dep.into();
+                    schema["deprecated"] = true.into();
+                )?
+                properties.insert(stringify!($name).replace('_', "-"), schema);
+            )*
+            properties.insert("inherit".to_string(), json!({
+                "type": "boolean",
+                "default": false,
+                "description": "Layer this file on top of the closest configuration file found in the parent directories.",
+            }));
+            let mut lints = LintLevels::schema();
+            lints["description"] = "Lint levels, keyed by lint or lint group name.".into();
+            properties.insert("lints".to_string(), lints);
+            let mut overrides = Vec::<ConfOverride>::schema();
+            overrides["description"] = "Configuration adjusted for the source files matching some paths.".into();
+            properties.insert("overrides".to_string(), overrides);
+            let mut lint_plugins = Vec::<LintPlugin>::schema();
+            lint_plugins["description"] = "Out-of-tree lint plugins to load, see `clippy_utils::plugin`.".into();
+            properties.insert("lint-plugins".to_string(), lint_plugins);
+            let mut custom_lints = Vec::<CustomLint>::schema();
+            custom_lints["description"] = "Lints matching the calls described by a pattern.".into();
+            properties.insert("custom-lints".to_string(), custom_lints);
+            properties.insert("third-party".to_string(), json!({
+                "type": "object",
+                "description": "Configuration of other tools, ignored by Clippy.",
+            }));
+
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "clippy.toml",
+                "description": "Clippy configuration file, see https://doc.rust-lang.org/clippy/configuration.html",
+                "type": "object",
+                "properties": properties,
+                "additionalProperties": false,
+            })
+        }
+
         impl<'de> Deserialize<'de> for TryConf {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
                 deserializer.deserialize_map(ConfVisitor)
@@ -126,7 +1101,11 @@ macro_rules! define_Conf {
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "kebab-case")]
         #[allow(non_camel_case_types)]
-        enum Field { $($name,)* third_party, }
+        enum Field { $($name,)* third_party, inherit, lints, overrides, lint_plugins, custom_lints, }
+
+        const FIELDS: &[&str] = &[
+            $(stringify!($name),)* "third_party", "inherit", "lints", "overrides", "lint_plugins", "custom_lints",
+        ];
 
         struct ConfVisitor;
 
@@ -141,36 +1120,109 @@ macro_rules! define_Conf {
                 let mut errors = Vec::new();
                 let mut warnings = Vec::new();
                 $(let mut $name = None;)*
-                // could get `Field` here directly, but get `str` first for diagnostics
-                while let Some(name) = map.next_key::<&str>()? {
-                    match Field::deserialize(name.into_deserializer())? {
+                let mut lints = None;
+                let mut overrides = None;
+                let mut lint_plugins = None;
+                let mut custom_lints = None;
+                // could get `Field` here directly, but get the spanned `str` first for diagnostics
+                while let Some(name) = map.next_key::<toml::Spanned<String>>()? {
+                    let key_span = name.start()..name.end();
+                    let name = name.into_inner();
+                    let field: Result<Field, serde::de::value::Error> =
+                        Field::deserialize(name.as_str().into_deserializer());
+                    let Ok(field) = field else {
+                        errors.push(unknown_field_error(&name, key_span, FIELDS));
+                        drop(map.next_value::<IgnoredAny>());
+                        continue;
+                    };
+                    match field {
                         $(Field::$name => {
-                            $(warnings.push(conf_error(format!("deprecated field `{}`. {}", name, $dep)));)?
-                            match map.next_value() {
-                                Err(e) => errors.push(conf_error(e.to_string())),
+                            $(warnings.push(
+                                ConfError::spanned(format!("deprecated field `{}`. {}", name, $dep), key_span.clone())
+                                    .with_suggestion(
+                                        "replace it with",
+                                        stringify!($new_conf).replace('_', "-"),
+                                        Applicability::MachineApplicable,
+                                    )
+                            );)?
+                            let value = map.next_value::<toml::Spanned<toml::Value>>()?;
+                            let value_span = value.start()..value.end();
+                            match <$ty as Deserialize>::deserialize(value.into_inner()) {
+                                Err(e) => errors.push(
+                                    ConfError::spanned(e.to_string(), value_span).with_help(format!(
+                                        "`{}` expects a value of type `{}`",
+                                        name,
+                                        display_type(stringify!($ty))
+                                    )),
+                                ),
                                 Ok(value) => match $name {
-                                    Some(_) => errors.push(conf_error(format!("duplicate field `{}`", name))),
+                                    Some(_) => errors.push(ConfError::spanned(format!("duplicate field `{}`", name), key_span)),
                                     None => {
                                         $name = Some(value);
                                         // $new_conf is the same as one of the defined `$name`s, so
                                         // this variable is defined in line 2 of this function.
                                         $(match $new_conf {
-                                            Some(_) => errors.push(conf_error(concat!(
+                                            Some(_) => errors.push(ConfError::spanned(concat!(
                                                 "duplicate field `", stringify!($new_conf),
                                                 "` (provided as `", stringify!($name), "`)"
-                                            ))),
+                                            ), key_span)),
                                             None => $new_conf = $name.clone(),
                                         })?
                                     },
-                                }
+                                },
                             }
                         })*
                         // white-listed; ignore
-                        Field::third_party => drop(map.next_value::<IgnoredAny>())
+                        Field::third_party => drop(map.next_value::<IgnoredAny>()),
+                        // already handled when merging inherited configuration files
+                        Field::inherit => drop(map.next_value::<IgnoredAny>()),
+                        Field::lints => match map.next_value() {
+                            // located in the value by `locate_section_errors`
+                            Err(e) => errors.push(ConfError::spanned(e.to_string(), key_span)),
+                            Ok(value) => match lints {
+                                Some(_) => errors.push(ConfError::spanned("duplicate field `lints`", key_span)),
+                                None => lints = Some(value),
+                            },
+                        },
+                        Field::overrides => match map.next_value() {
+                            // located in the value by `locate_section_errors`
+                            Err(e) => errors.push(ConfError::spanned(e.to_string(), key_span)),
+                            Ok(value) => match overrides {
+                                Some(_) => errors.push(ConfError::spanned("duplicate field `overrides`", key_span)),
+                                None => overrides = Some(value),
+                            },
+                        },
+                        Field::lint_plugins => match map.next_value() {
+                            // located in the value by `locate_section_errors`
+                            Err(e) => errors.push(ConfError::spanned(e.to_string(), key_span)),
+                            Ok(value) => match lint_plugins {
+                                Some(_) => errors.push(ConfError::spanned("duplicate field `lint-plugins`", key_span)),
+                                None => lint_plugins = Some(value),
+                            },
+                        },
+                        Field::custom_lints => match map.next_value() {
+                            // located in the value by `locate_section_errors`
+                            Err(e) => errors.push(ConfError::spanned(e.to_string(), key_span)),
+                            Ok(value) => match custom_lints {
+                                Some(_) => errors.push(ConfError::spanned("duplicate field `custom-lints`", key_span)),
+                                None => custom_lints = Some(value),
+                            },
+                        },
                     }
                 }
-                let conf = Conf { $($name: $name.unwrap_or_else(defaults::$name),)* };
-                Ok(TryConf { conf, errors, warnings })
+                let conf = Conf {
+                    $($name: $name.unwrap_or_else(defaults::$name),)*
+                    lints: lints.unwrap_or_default(),
+                    overrides: overrides.unwrap_or_default(),
+                    lint_plugins: lint_plugins.unwrap_or_default(),
+                    custom_lints: custom_lints.unwrap_or_default(),
+                };
+                Ok(TryConf {
+                    conf,
+                    errors,
+                    warnings,
+                    unknown_lints: Vec::new(),
+                })
             }
         }
 
@@ -358,7 +1410,7 @@ define_Conf! {
     /// Lint: DISALLOWED_MACROS.
     ///
     /// The list of disallowed macros, written as fully qualified paths.
-    (disallowed_macros: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    (disallowed_macros: Vec<crate::utils::conf::DisallowedPath<false>> = Vec::new()),
     /// Lint: DISALLOWED_METHODS.
     ///
     /// The list of disallowed methods, written as fully qualified paths.
@@ -367,6 +1419,16 @@ define_Conf! {
     ///
     /// The list of disallowed types, written as fully qualified paths.
     (disallowed_types: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: DISALLOWED_IMPLS.
+    ///
+    /// The list of disallowed trait implementations, written as the fully qualified path of the
+    /// trait, optionally restricted to some types with `self-type` or `field-type`.
+    (disallowed_impls: Vec<crate::utils::conf::DisallowedImpl> = Vec::new()),
+    /// Lint: LAYERING_VIOLATIONS.
+    ///
+    /// The layers of the crate, as a list of modules along with the crates and modules they may
+    /// not use.
+    (layering: Vec<crate::utils::conf::LayeringRule> = Vec::new()),
     /// Lint: UNREADABLE_LITERAL.
     ///
     /// Should the fraction of a decimal be linted to include separators.
@@ -412,7 +1474,7 @@ define_Conf! {
     /// For example, `[_, _, _, e, ..]` is a slice pattern with 4 elements.
     (max_suggested_slice_pattern_length: u64 = 3),
     /// Lint: AWAIT_HOLDING_INVALID_TYPE.
-    (await_holding_invalid_types: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    (await_holding_invalid_types: Vec<crate::utils::conf::DisallowedPath<false>> = Vec::new()),
     /// Lint: LARGE_INCLUDE_FILE.
     ///
     /// The maximum size of a file included via `include_bytes!()` or `include_str!()`, in bytes
@@ -456,21 +1518,81 @@ define_Conf! {
     (suppress_restriction_lint_in_const: bool = false),
 }
 
+/// The path of the JSON schema of the configuration file, relative to the root of the repository.
+pub const JSON_SCHEMA_FILE: &str = "util/gh-pages/clippy_toml.schema.json";
+
+/// Returns the content of [`JSON_SCHEMA_FILE`].
+pub fn json_schema_file() -> String {
+    format!("{}\n", serde_json::to_string_pretty(&json_schema()).unwrap())
+}
+
+/// Turns the doc comment of a configuration key into the description used in the JSON schema,
+/// removing the `Lint: ...` paragraph.
+fn schema_description(doc: &str) -> String {
+    let doc = doc
+        .lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let is_lint_list = |paragraph: &str| paragraph.starts_with("Lint: ") || paragraph.starts_with("DEPRECATED LINT: ");
+    let description = match doc.split_once("\n\n") {
+        Some((lints, description)) if is_lint_list(lints) => description,
+        None if is_lint_list(&doc) => "",
+        _ => &doc,
+    };
+    description.trim().to_string()
+}
+
+/// Returns the lint names listed in the `Lint: ...` paragraph of the doc comment of a
+/// configuration key.
+fn doc_lint_names(doc: &str) -> impl Iterator<Item = &str> {
+    doc.trim_start()
+        .strip_prefix("Lint:")
+        .and_then(|lints| lints.split_once('.'))
+        .map(|(lints, _)| lints)
+        .into_iter()
+        .flat_map(|lints| lints.split(','))
+        .map(str::trim)
+}
+
+/// Possible filename to search for.
+const CONFIG_FILE_NAMES: [&str; 2] = [".clippy.toml", "clippy.toml"];
+
+impl Conf {
+    /// Returns the lint levels of the `[lints]` table in the order they have to be applied.
+    ///
+    /// Entries with a lower `priority` are applied first so that the ones with a higher priority
+    /// take precedence. With the same priority, lint groups are applied before individual lints.
+    ///
+    /// The unknown lints are kept, the lints of the lint plugins and the custom lints aren't known
+    /// before they are registered. The ones that are still unknown then are ignored, see
+    /// `clippy_lints::check_conf_lint_names`.
+    pub fn lint_level_opts(&self) -> Vec<(String, Level)> {
+        self.lints
+            .sorted_levels()
+            .into_iter()
+            .map(|(name, level)| (format!("clippy::{name}"), level))
+            .collect()
+    }
+}
+
 /// Search for the configuration file.
 ///
 /// # Errors
 ///
 /// Returns any unexpected filesystem error encountered when searching for the config file
 pub fn lookup_conf_file() -> io::Result<Option<PathBuf>> {
-    /// Possible filename to search for.
-    const CONFIG_FILE_NAMES: [&str; 2] = [".clippy.toml", "clippy.toml"];
-
     // Start looking for a config file in CLIPPY_CONF_DIR, or failing that, CARGO_MANIFEST_DIR.
     // If neither of those exist, use ".".
-    let mut current = env::var_os("CLIPPY_CONF_DIR")
+    let current = env::var_os("CLIPPY_CONF_DIR")
         .or_else(|| env::var_os("CARGO_MANIFEST_DIR"))
         .map_or_else(|| PathBuf::from("."), PathBuf::from);
 
+    lookup_conf_file_from(current)
+}
+
+/// Search for the configuration file in `current` and its ancestors.
+pub fn lookup_conf_file_from(mut current: PathBuf) -> io::Result<Option<PathBuf>> {
     let mut found_config: Option<PathBuf> = None;
 
     loop {
@@ -507,22 +1629,336 @@ pub fn lookup_conf_file() -> io::Result<Option<PathBuf>> {
     }
 }
 
+/// A configuration file parsed as a plain `toml` table, before it is merged with the files it
+/// inherits from.
+struct ConfFile {
+    path: PathBuf,
+    content: String,
+    table: toml::value::Table,
+}
+
+impl ConfFile {
+    fn read(path: &Path) -> Result<Self, ConfError> {
+        let content = fs::read_to_string(path).map_err(|e| ConfError::new(e.to_string()))?;
+        let table = toml::from_str(&content).map_err(|e| ConfError::from_toml(&content, &e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            content,
+            table,
+        })
+    }
+
+    /// Whether this file sets `inherit = true`.
+    fn inherits(&self) -> Result<bool, ConfError> {
+        match self.table.get("inherit") {
+            None => Ok(false),
+            Some(toml::Value::Boolean(inherit)) => Ok(*inherit),
+            Some(value) => Err(ConfError::new(format!(
+                "invalid type: {} `{value}`, expected a boolean for key `inherit`",
+                value.type_str()
+            ))),
+        }
+    }
+
+    /// Finds the closest configuration file in an ancestor directory of this file.
+    fn lookup_parent(&self) -> io::Result<Option<PathBuf>> {
+        match self.path.parent().and_then(Path::parent) {
+            Some(dir) => lookup_conf_file_from(dir.to_path_buf()),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Collects the configuration file at `path` and every file it inherits from, starting with
+/// `path` itself and ending with the outermost ancestor.
+///
+/// Errors in the inherited files are reported in the file they are in.
+fn read_conf_file_chain(path: &Path) -> Result<Vec<ConfFile>, ConfError> {
+    let mut chain = vec![ConfFile::read(path)?];
+    loop {
+        let last = chain.last().unwrap();
+        let in_last = |error: ConfError| match chain.len() {
+            1 => error,
+            _ => error.in_file(&last.path),
+        };
+        if !last.inherits().map_err(in_last)? {
+            return Ok(chain);
+        }
+        let parent = last
+            .lookup_parent()
+            .map_err(|e| in_last(ConfError::new(e.to_string())))?;
+        match parent {
+            Some(parent) => {
+                let file = ConfFile::read(&parent).map_err(|error| error.in_file(&parent))?;
+                chain.push(file);
+            },
+            None => return Ok(chain),
+        }
+    }
+}
+
+/// Returns the paths of all configuration files that `path` inherits from (including `path`
+/// itself). Files that cannot be read are skipped, errors are reported by [`read`].
+pub fn lookup_inherited_conf_files(path: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![path.to_path_buf()];
+    let mut current = path.to_path_buf();
+    while let Ok(file) = ConfFile::read(&current)
+        && let Ok(true) = file.inherits()
+        && let Ok(Some(parent)) = file.lookup_parent()
+    {
+        paths.push(parent.clone());
+        current = parent;
+    }
+    paths
+}
+
+/// Returns the configuration file setting each key, for `path` and the files it inherits from.
+/// The keys are in `kebab-case`, with deprecated keys reported under their replacement.
+pub fn value_sources(path: &Path) -> FxHashMap<String, PathBuf> {
+    let mut sources = FxHashMap::default();
+    for file in lookup_inherited_conf_files(path) {
+        if let Ok(file) = ConfFile::read(&file) {
+            for key in file.table.keys() {
+                sources.entry(canonical_key(key)).or_insert_with(|| file.path.clone());
+            }
+        }
+    }
+    sources
+}
+
+/// Layers `child` on top of `parent`. Keys set in `child` override the ones in `parent`, except
+/// for lists containing `".."`, where the `".."` is replaced by the list from `parent`, and for
+/// tables like `[lints]`, which are merged key by key.
+fn merge_tables(parent: toml::value::Table, child: toml::value::Table) -> toml::value::Table {
+    let mut merged = parent;
+    for (key, value) in child {
+        let value = match (value, merged.remove(&key)) {
+            (toml::Value::Array(values), inherited) if values.iter().any(|value| value.as_str() == Some("..")) => {
+                toml::Value::Array(inherit_list(&key, values, inherited))
+            },
+            (toml::Value::Table(values), Some(toml::Value::Table(inherited))) => {
+                toml::Value::Table(merge_tables(inherited, values))
+            },
+            (value, _) => value,
+        };
+        merged.insert(key, value);
+    }
+    merged
+}
+
+/// Replaces the `".."` in the list `values` of the key `key` by the `inherited` list. Without a
+/// list to inherit, the default value of the key is inherited.
+fn inherit_list(key: &str, values: Vec<toml::Value>, inherited: Option<toml::Value>) -> Vec<toml::Value> {
+    let inherited = match inherited {
+        Some(toml::Value::Array(inherited)) => inherited,
+        // the `".."` of these lists already stands for their default value
+        _ if matches!(canonical_key(key).as_str(), "disallowed-names" | "doc-valid-idents") => return values,
+        _ => match default_value(key) {
+            Some(toml::Value::Array(default)) => default,
+            _ => Vec::new(),
+        },
+    };
+    let mut inherited = Some(inherited);
+    let mut result = Vec::new();
+    for value in values {
+        if value.as_str() == Some("..") {
+            result.extend(inherited.take().into_iter().flatten());
+        } else {
+            result.push(value);
+        }
+    }
+    result
+}
+
 /// Read the `toml` configuration file.
 ///
+/// If the file sets `inherit = true`, it is layered on top of the closest configuration file
+/// found in its ancestor directories, recursively.
+///
 /// In case of error, the function tries to continue as much as possible.
 pub fn read(path: &Path) -> TryConf {
-    let content = match fs::read_to_string(path) {
+    let mut chain = match read_conf_file_chain(path) {
         Err(e) => return TryConf::from_error(e),
-        Ok(content) => content,
+        Ok(chain) => chain,
+    };
+    // a file inheriting from no file only inherits the default values
+    let inherits_defaults = chain.last().map_or(false, |file| matches!(file.inherits(), Ok(true)));
+    if chain.len() == 1 && !inherits_defaults {
+        return parse(&chain.remove(0).content);
+    }
+
+    // Each file is validated on its own, so that the diagnostics keep pointing into the file they
+    // are about.
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut unknown_lints = Vec::new();
+    for (i, file) in chain.iter().enumerate() {
+        let in_file = |error: ConfError| if i == 0 { error } else { error.in_file(&file.path) };
+        let parsed = parse(&file.content);
+        errors.extend(parsed.errors.into_iter().map(in_file));
+        warnings.extend(parsed.warnings.into_iter().map(in_file));
+        for unknown in parsed.unknown_lints {
+            unknown_lints.push(UnknownLint {
+                warning: in_file(unknown.warning),
+                ..unknown
+            });
+        }
+    }
+
+    let defaults = inherits_defaults.then(toml::value::Table::new);
+    let table = defaults
+        .into_iter()
+        .chain(chain.into_iter().rev().map(|file| file.table))
+        .reduce(merge_tables)
+        .unwrap_or_default();
+    // The merged table is parsed from its text, as positions are needed to deserialize the
+    // spanned values. These positions don't point into any of the files, so only the errors that
+    // come from merging the files, like lists extended with the same custom lint twice, are
+    // reported, and without a span.
+    let merged = match toml::to_string(&table) {
+        Err(e) => TryConf::from_error(ConfError::new(e.to_string())),
+        Ok(content) => parse(&content),
     };
-    match toml::from_str::<TryConf>(&content) {
+    for error in merged.errors {
+        if !errors.iter().any(|e: &ConfError| e.message == error.message) {
+            errors.push(ConfError { span: None, ..error });
+        }
+    }
+
+    TryConf {
+        conf: merged.conf,
+        errors,
+        warnings,
+        unknown_lints,
+    }
+}
+
+/// Parses and validates the content of a single configuration file.
+fn parse(content: &str) -> TryConf {
+    match toml::from_str::<TryConf>(content) {
         Ok(mut conf) => {
+            locate_section_errors(content, &mut conf.errors);
             extend_vec_if_indicator_present(&mut conf.conf.doc_valid_idents, DEFAULT_DOC_VALID_IDENTS);
             extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
+            conf.conf
+                .lints
+                .check_names("the `[lints]` table", &mut conf.unknown_lints);
+            for section in &conf.conf.overrides {
+                section
+                    .lints
+                    .check_names("an `[[overrides]]` section", &mut conf.unknown_lints);
+            }
+            let mut names = FxHashSet::default();
+            conf.conf
+                .custom_lints
+                .retain(|lint| match custom_lint_error(lint, &mut names) {
+                    Some(error) => {
+                        conf.errors
+                            .push(ConfError::spanned(error, lint.name.start()..lint.name.end()));
+                        false
+                    },
+                    None => true,
+                });
 
             conf
         },
-        Err(e) => TryConf::from_error(e),
+        Err(e) => TryConf::from_error(ConfError::from_toml(content, &e)),
+    }
+}
+
+/// Moves the errors of the `lints`, `overrides`, `lint-plugins` and `custom-lints` sections from
+/// the key of the section to the value they are about.
+///
+/// These sections keep the spans of their keys, so unlike the other values they can't be
+/// deserialized from a `toml::Value`, and their errors don't say where they are until they reach
+/// the `toml` deserializer. Each section with an error is deserialized again on its own to find it.
+fn locate_section_errors(content: &str, errors: &mut [ConfError]) {
+    for error in errors {
+        let Some(key) = error.span.clone().and_then(|span| content.get(span)) else {
+            continue;
+        };
+        let located = match key {
+            "lints" => section_error::<LintLevels>(content, key),
+            "overrides" => section_error::<Vec<ConfOverride>>(content, key),
+            "lint-plugins" => section_error::<Vec<LintPlugin>>(content, key),
+            "custom-lints" => section_error::<Vec<CustomLint>>(content, key),
+            _ => None,
+        };
+        if let Some(located) = located {
+            *error = located;
+        }
+    }
+}
+
+/// Deserializes the value of the top-level key `key` of `content` as a `T`, ignoring the other
+/// keys, and returns the error if it fails.
+fn section_error<T: for<'de> Deserialize<'de>>(content: &str, key: &str) -> Option<ConfError> {
+    struct SectionVisitor<'a, T> {
+        key: &'a str,
+        value: PhantomData<T>,
+    }
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for SectionVisitor<'_, T> {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a table")
+        }
+
+        fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+        where
+            V: MapAccess<'de>,
+        {
+            while let Some(key) = map.next_key::<String>()? {
+                if key == self.key {
+                    map.next_value::<T>()?;
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let visitor = SectionVisitor::<T> {
+        key,
+        value: PhantomData,
+    };
+    let mut deserializer = toml::Deserializer::new(content);
+    deserializer
+        .deserialize_map(visitor)
+        .err()
+        .map(|error| ConfError::from_toml(content, &error))
+}
+
+/// Returns why the lint declared in a `[[custom-lints]]` section can't be registered, if it can't,
+/// `names` being the names of the custom lints seen so far.
+fn custom_lint_error(lint: &CustomLint, names: &mut FxHashSet<String>) -> Option<String> {
+    let lint_name = lint.name.get_ref();
+    let name = normalize_lint_name(lint_name);
+    let pattern = &lint.pattern;
+    if name.is_empty()
+        || name.starts_with(|c: char| c.is_ascii_digit())
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        Some(format!("`{lint_name}` is not a valid lint name"))
+    } else if is_lint_group(&name) || is_known_lint(&name) {
+        Some(format!("the custom lint `{lint_name}` has the name of a Clippy lint"))
+    } else if !names.insert(name) {
+        Some(format!("duplicate custom lint `{lint_name}`"))
+    } else if pattern.call.is_some() == pattern.method.is_some() {
+        Some(format!(
+            "the pattern of the custom lint `{lint_name}` must have either a `call` or a `method`"
+        ))
+    } else if let Some(path) = &pattern.receiver_implements
+        && path.contains(['<', '>'])
+    {
+        Some(format!(
+            "the trait `{path}` of the custom lint `{lint_name}` has generic arguments, which \
+             `receiver-implements` doesn't support"
+        ))
+    } else {
+        None
     }
 }
 
@@ -534,66 +1970,36 @@ fn extend_vec_if_indicator_present(vec: &mut Vec<String>, default: &[&str]) {
 
 const SEPARATOR_WIDTH: usize = 4;
 
-// Check whether the error is "unknown field" and, if so, list the available fields sorted and at
-// least one per line, more if `CLIPPY_TERMINAL_WIDTH` is set and allows it.
-pub fn format_error(error: Box<dyn Error>) -> String {
-    let s = error.to_string();
-
-    if_chain! {
-        if error.downcast::<toml::de::Error>().is_ok();
-        if let Some((prefix, mut fields, suffix)) = parse_unknown_field_message(&s);
-        then {
-            use fmt::Write;
-
-            fields.sort_unstable();
-
-            let (rows, column_widths) = calculate_dimensions(&fields);
-
-            let mut msg = String::from(prefix);
-            for row in 0..rows {
-                writeln!(msg).unwrap();
-                for (column, column_width) in column_widths.iter().copied().enumerate() {
-                    let index = column * rows + row;
-                    let field = fields.get(index).copied().unwrap_or_default();
-                    write!(
-                        msg,
-                        "{:SEPARATOR_WIDTH$}{field:column_width$}",
-                        " "
-                    )
-                    .unwrap();
-                }
-            }
-            write!(msg, "\n{suffix}").unwrap();
-            msg
-        } else {
-            s
-        }
-    }
-}
-
-// `parse_unknown_field_message` will become unnecessary if
-// https://github.com/alexcrichton/toml-rs/pull/364 is merged.
-fn parse_unknown_field_message(s: &str) -> Option<(&str, Vec<&str>, &str)> {
-    // An "unknown field" message has the following form:
-    //   unknown field `UNKNOWN`, expected one of `FIELD0`, `FIELD1`, ..., `FIELDN` at line X column Y
-    //                                           ^^      ^^^^                     ^^
-    if_chain! {
-        if s.starts_with("unknown field");
-        let slices = s.split("`, `").collect::<Vec<_>>();
-        let n = slices.len();
-        if n >= 2;
-        if let Some((prefix, first_field)) = slices[0].rsplit_once(" `");
-        if let Some((last_field, suffix)) = slices[n - 1].split_once("` ");
-        then {
-            let fields = iter::once(first_field)
-                .chain(slices[1..n - 1].iter().copied())
-                .chain(iter::once(last_field))
-                .collect::<Vec<_>>();
-            Some((prefix, fields, suffix))
-        } else {
-            None
+/// Reports the unknown field `name`. The available fields are listed sorted and at least one per
+/// line, more if `CLIPPY_TERMINAL_WIDTH` is set and allows it, unless one of them is close enough
+/// to `name` to be suggested instead.
+fn unknown_field_error(name: &str, span: Range<usize>, fields: &[&str]) -> ConfError {
+    use fmt::Write;
+
+    let mut fields = fields.iter().map(|field| field.replace('_', "-")).collect::<Vec<_>>();
+    fields.sort_unstable();
+    let fields = fields.iter().map(String::as_str).collect::<Vec<_>>();
+
+    if let Some(field) = closest_name(&name.replace('_', "-"), fields.iter().copied()) {
+        return ConfError::spanned(format!("unknown field `{name}`"), span).with_suggestion(
+            "perhaps you meant",
+            field,
+            Applicability::MaybeIncorrect,
+        );
+    }
+
+    let (rows, column_widths) = calculate_dimensions(&fields);
+
+    let mut msg = format!("unknown field `{name}`, expected one of");
+    for row in 0..rows {
+        writeln!(msg).unwrap();
+        for (column, column_width) in column_widths.iter().copied().enumerate() {
+            let index = column * rows + row;
+            let field = fields.get(index).copied().unwrap_or_default();
+            write!(msg, "{:SEPARATOR_WIDTH$}{field:column_width$}", " ").unwrap();
         }
     }
+    ConfError::spanned(msg, span)
 }
 
 fn calculate_dimensions(fields: &[&str]) -> (usize, Vec<usize>) {
@@ -627,3 +2033,35 @@ fn calculate_dimensions(fields: &[&str]) -> (usize, Vec<usize>) {
 
     (rows, column_widths)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_schema_is_up_to_date() {
+        let checked_in = include_str!("../../../util/gh-pages/clippy_toml.schema.json");
+        assert!(
+            json_schema_file() == checked_in,
+            "`{JSON_SCHEMA_FILE}` is out of date, run `cargo collect-metadata` to update it"
+        );
+    }
+
+    #[test]
+    fn inherit_without_parent_list() {
+        let child: toml::value::Table = toml::from_str(
+            r#"
+            disallowed-methods = ["std::process::exit", ".."]
+            allowed-scripts = ["Cyrillic", ".."]
+            disallowed-names = ["toto", ".."]
+            "#,
+        )
+        .unwrap();
+        let merged = merge_tables(toml::value::Table::new(), child);
+        let list = |values: &[&str]| toml::Value::Array(values.iter().map(|&value| value.into()).collect());
+        assert_eq!(merged["disallowed-methods"], list(&["std::process::exit"]));
+        assert_eq!(merged["allowed-scripts"], list(&["Cyrillic", "Latin"]));
+        // extended with the default list when the configuration is parsed
+        assert_eq!(merged["disallowed-names"], list(&["toto", ".."]));
+    }
+}
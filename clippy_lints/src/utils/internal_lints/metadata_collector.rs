@@ -8,6 +8,7 @@
 //! a simple mistake)
 
 use crate::renamed_lints::RENAMED_LINTS;
+use crate::utils::conf::JSON_SCHEMA_FILE;
 use crate::utils::internal_lints::lint_without_lint_pass::{extract_clippy_version_value, is_lint_ref_type};
 
 use clippy_utils::diagnostics::span_lint;
@@ -39,6 +40,8 @@ use std::process::Command;
 const JSON_OUTPUT_FILE: &str = "../util/gh-pages/lints.json";
 /// This is the markdown output file of the lint collector.
 const MARKDOWN_OUTPUT_FILE: &str = "../book/src/lint_configuration.md";
+/// The applicability of the suggestions of each lint, read by `cargo clippy --explain`.
+const LINT_APPLICABILITY_FILE: &str = "../util/gh-pages/lint_applicability.json";
 /// These lints are excluded from the export.
 const BLACK_LISTED_LINTS: &[&str] = &["lint_author", "dump_hir", "internal_metadata_collector"];
 /// These groups will be ignored by the lint group matcher. This is useful for collections like
@@ -250,6 +253,30 @@ Please use that command to update the file and do not edit it by hand.
             self.get_markdown_docs(),
         )
         .unwrap();
+
+        // Outputting the applicability of the lints with suggestions for `--explain`
+        let lint_applicability = lints
+            .iter()
+            .filter_map(|lint| {
+                let info = lint.applicability.as_ref().filter(|info| info.has_suggestion)?;
+                let applicability = info.applicability.map_or(APPLICABILITY_UNRESOLVED_STR, |index| {
+                    paths::APPLICABILITY_VALUES[index][APPLICABILITY_NAME_INDEX]
+                });
+                Some((lint.id.clone(), serde_json::Value::from(applicability)))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        fs::write(
+            LINT_APPLICABILITY_FILE,
+            format!("{}\n", serde_json::to_string_pretty(&lint_applicability).unwrap()),
+        )
+        .unwrap();
+
+        // Outputting the JSON schema of the configuration file
+        fs::write(
+            Path::new("..").join(JSON_SCHEMA_FILE),
+            crate::utils::conf::json_schema_file(),
+        )
+        .unwrap();
     }
 }
 
@@ -495,6 +522,9 @@ struct ApplicabilityInfo {
     /// currently not be applied automatically.
     is_multi_part_suggestion: bool,
     applicability: Option<usize>,
+    /// Indicates if any of the lint emissions has a suggestion, even if its applicability can't be
+    /// resolved.
+    has_suggestion: bool,
 }
 
 impl Serialize for ApplicabilityInfo {
@@ -723,10 +753,15 @@ impl<'hir> LateLintPass<'hir> for MetadataCollector {
                 return;
             }
 
-            for (lint_name, applicability, is_multi_part) in emission_info {
+            for (lint_name, applicability, is_multi_part, has_suggestion) in emission_info {
                 let app_info = self.applicability_info.entry(lint_name).or_default();
-                app_info.applicability = applicability;
-                app_info.is_multi_part_suggestion = is_multi_part;
+                app_info.has_suggestion |= has_suggestion;
+                // an emission without a suggestion doesn't change the applicability of the
+                // suggestions of the lint's other emissions
+                if applicability.is_some() {
+                    app_info.applicability = applicability;
+                }
+                app_info.is_multi_part_suggestion |= is_multi_part;
             }
         }
     }
@@ -954,13 +989,16 @@ fn take_higher_applicability(a: Option<usize>, b: Option<usize>) -> Option<usize
     a.map_or(b, |a| a.max(b.unwrap_or_default()).into())
 }
 
+/// Returns the lints of a lint emission, with the applicability of its suggestion, whether it's a
+/// multi part suggestion and whether the emission has a suggestion at all.
 fn extract_emission_info<'hir>(
     cx: &LateContext<'hir>,
     args: &'hir [hir::Expr<'hir>],
-) -> Vec<(String, Option<usize>, bool)> {
+) -> Vec<(String, Option<usize>, bool, bool)> {
     let mut lints = Vec::new();
     let mut applicability = None;
     let mut multi_part = false;
+    let mut has_suggestion = false;
 
     for arg in args {
         let (arg_ty, _) = walk_ptrs_ty_depth(cx.typeck_results().expr_ty(arg));
@@ -970,6 +1008,7 @@ fn extract_emission_info<'hir>(
             let mut resolved_lints = resolve_lints(cx, arg);
             lints.append(&mut resolved_lints);
         } else if match_type(cx, arg_ty, &paths::APPLICABILITY) {
+            has_suggestion = true;
             applicability = resolve_applicability(cx, arg);
         } else if arg_ty.is_closure() {
             multi_part |= check_is_multi_part(cx, arg);
@@ -979,7 +1018,14 @@ fn extract_emission_info<'hir>(
 
     lints
         .into_iter()
-        .map(|lint_name| (lint_name, applicability, multi_part))
+        .map(|lint_name| {
+            (
+                lint_name,
+                applicability,
+                multi_part,
+                has_suggestion || applicability.is_some(),
+            )
+        })
         .collect()
 }
 
@@ -46,7 +46,7 @@ declare_clippy_lint! {
     /// # let name = "World";
     /// print!("Hello {}!\n", name);
     /// ```
-    /// use println!() instead
+    /// Use instead:
     /// ```rust
     /// # let name = "World";
     /// println!("Hello {}!", name);
@@ -112,8 +112,8 @@ declare_clippy_lint! {
     ///
     /// ### Example
     /// ```rust
-    /// # let foo = "bar";
-    /// println!("{:?}", foo);
+    /// # let value = "bar";
+    /// println!("{:?}", value);
     /// ```
     #[clippy::version = "pre 1.29.0"]
     pub USE_DEBUG,
@@ -134,7 +134,9 @@ declare_clippy_lint! {
     /// ```rust
     /// println!("{}", "foo");
     /// ```
-    /// use the literal without formatting:
+    /// Put the literal in the format string.
+    ///
+    /// Use instead:
     /// ```rust
     /// println!("foo");
     /// ```
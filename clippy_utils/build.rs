@@ -0,0 +1,22 @@
+use std::env;
+use std::process::Command;
+
+fn main() {
+    // The commit of Clippy and the version of rustc embedded in the lint plugins, see `plugin.rs`
+    rustc_tools_util::setup_version_info!();
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    // `rustc --version` prints the version of `rustc_interface::util::rustc_version_str`
+    let version = version.trim();
+    println!(
+        "cargo:rustc-env=RUSTC_VERSION={}",
+        version.strip_prefix("rustc ").unwrap_or(version)
+    );
+    // Don't rebuild even if nothing changed
+    println!("cargo:rerun-if-changed=build.rs");
+}
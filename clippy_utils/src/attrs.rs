@@ -43,6 +43,10 @@ impl LimitStack {
     pub fn limit(&self) -> u64 {
         *self.stack.last().expect("there should always be a value in the stack")
     }
+    /// Whether the limit was changed by an attribute on an enclosing item.
+    pub fn is_set_by_attr(&self) -> bool {
+        self.stack.len() > 1
+    }
     pub fn push_attrs(&mut self, sess: &Session, attrs: &[ast::Attribute], name: &'static str) {
         let stack = &mut self.stack;
         parse_attrs(sess, attrs, name, |val| stack.push(val));
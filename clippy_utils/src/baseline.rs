@@ -0,0 +1,205 @@
+//! Baselines of known warnings, recorded by `--write-baseline` and suppressed by `--baseline`.
+//!
+//! Warnings are identified by the lint, the file and a fingerprint of the code they point to
+//! rather than by their line numbers, so that a baseline keeps matching when unrelated code is
+//! added or removed above a warning.
+//!
+//! The warnings are suppressed where the compiler emits them, so that it also applies to the lints
+//! of plugins and to the lints that don't use the functions of [`crate::diagnostics`].
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::{Diagnostic, DiagnosticId, Level as DiagnosticLevel};
+use rustc_session::Session;
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+use std::cell::RefCell;
+use std::sync::{Mutex, Once, OnceLock};
+
+/// A warning, identified independently of its position in the file.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BaselineKey {
+    /// The path of the file containing the warning, as displayed in diagnostics.
+    pub file: String,
+    /// The lint name in the `clippy::lint_name` form.
+    pub lint: String,
+    /// A hash of the lint name and of the code on the lines of the warning.
+    pub fingerprint: String,
+}
+
+impl BaselineKey {
+    /// Returns the key of a warning of `lint`, in the `clippy::lint_name` form, at `span`, or
+    /// `None` if `span` doesn't point into a source file.
+    fn new(source_map: &SourceMap, lint: &str, span: Span) -> Option<Self> {
+        let span = span.source_callsite();
+        if span.is_dummy() {
+            return None;
+        }
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        let last_line = if lo.file.start_pos == hi.file.start_pos {
+            hi.line
+        } else {
+            lo.line
+        };
+
+        let mut hash = Fnv1a::default();
+        hash.write(lint.as_bytes());
+        for line in lo.line..=last_line {
+            let line = lo.file.get_line(line - 1)?;
+            hash.write(b"\n");
+            // Only the tokens matter, not the indentation or the alignment
+            for (i, word) in line.split_whitespace().enumerate() {
+                if i > 0 {
+                    hash.write(b" ");
+                }
+                hash.write(word.as_bytes());
+            }
+        }
+
+        Some(Self {
+            file: lo.file.name.prefer_local().to_string().replace('\\', "/"),
+            lint: lint.to_string(),
+            fingerprint: format!("{:016x}", hash.0),
+        })
+    }
+}
+
+/// The 64 bit FNV-1a hash, which unlike the hashers of `std` is stable across Rust versions and
+/// platforms.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// The warnings of the baseline passed with `--baseline`, with the number of times each of them
+/// may still be suppressed.
+static KNOWN_WARNINGS: OnceLock<Mutex<FxHashMap<BaselineKey, usize>>> = OnceLock::new();
+
+/// The warnings emitted so far when writing a baseline with `--write-baseline`.
+static RECORDED_WARNINGS: OnceLock<Mutex<FxHashMap<BaselineKey, usize>>> = OnceLock::new();
+
+/// Sets the warnings to suppress, with the number of times each of them was recorded.
+///
+/// Only the first call has an effect.
+pub fn set_known_warnings(sess: &Session, warnings: FxHashMap<BaselineKey, usize>) {
+    let _ = KNOWN_WARNINGS.set(Mutex::new(warnings));
+    track_warnings(sess);
+}
+
+/// Starts recording the emitted warnings, see [`recorded_warnings`].
+pub fn record_warnings(sess: &Session) {
+    let _ = RECORDED_WARNINGS.set(Mutex::default());
+    track_warnings(sess);
+}
+
+/// Returns the warnings emitted since [`record_warnings`] was called, sorted by file and lint,
+/// with the number of times each of them was emitted.
+pub fn recorded_warnings() -> Vec<(BaselineKey, usize)> {
+    let mut warnings = RECORDED_WARNINGS
+        .get()
+        .map(|warnings| warnings.lock().unwrap().clone().into_iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    warnings.sort();
+    warnings
+}
+
+thread_local! {
+    /// The source map of the session of the compiler thread, to find the code of the warnings.
+    static SOURCE_MAP: RefCell<Option<Lrc<SourceMap>>> = RefCell::new(None);
+}
+
+type TrackDiagnostic = fn(&mut Diagnostic, &mut dyn FnMut(&mut Diagnostic));
+
+/// The function that `rustc_errors::TRACK_DIAGNOSTICS` was set to before [`track_warnings`].
+static PREVIOUS_TRACK_DIAGNOSTIC: OnceLock<TrackDiagnostic> = OnceLock::new();
+
+static TRACK_DIAGNOSTIC: TrackDiagnostic = track_diagnostic;
+
+/// Makes the compiler pass the diagnostics it emits to [`is_known_warning`].
+fn track_warnings(sess: &Session) {
+    static TRACKING: Once = Once::new();
+    TRACKING.call_once(|| {
+        SOURCE_MAP.with(|source_map| *source_map.borrow_mut() = Some(sess.parse_sess.clone_source_map()));
+        let previous = rustc_errors::TRACK_DIAGNOSTICS.swap(&TRACK_DIAGNOSTIC);
+        let _ = PREVIOUS_TRACK_DIAGNOSTIC.set(*previous);
+    });
+}
+
+/// Called by the compiler for every diagnostic, `emit` emits it.
+fn track_diagnostic(diagnostic: &mut Diagnostic, emit: &mut dyn FnMut(&mut Diagnostic)) {
+    if !is_known_warning(diagnostic) {
+        match PREVIOUS_TRACK_DIAGNOSTIC.get() {
+            Some(previous) => previous(diagnostic, emit),
+            None => emit(diagnostic),
+        }
+    }
+}
+
+/// Records `diagnostic` if it's a warning of a Clippy lint and a baseline is being written, and
+/// returns whether the warning is in the baseline and should be suppressed.
+///
+/// A warning recorded `n` times in the baseline is only suppressed for its first `n` occurrences,
+/// further ones are new warnings. The lints at the `allow` and `expect` levels aren't warnings.
+fn is_known_warning(diagnostic: &Diagnostic) -> bool {
+    if !matches!(
+        diagnostic.level(),
+        DiagnosticLevel::Warning(_) | DiagnosticLevel::Error { lint: true }
+    ) {
+        return false;
+    }
+    let Some(DiagnosticId::Lint { name, .. }) = &diagnostic.code else {
+        return false;
+    };
+    let Some(span) = diagnostic.span.primary_span().filter(|_| name.starts_with("clippy::")) else {
+        return false;
+    };
+    let key = SOURCE_MAP.with(|source_map| {
+        source_map
+            .borrow()
+            .as_deref()
+            .and_then(|source_map| BaselineKey::new(source_map, name, span))
+    });
+    let Some(key) = key else {
+        return false;
+    };
+    if let Some(recorded) = RECORDED_WARNINGS.get() {
+        *recorded.lock().unwrap().entry(key.clone()).or_default() += 1;
+    }
+    if let Some(known) = KNOWN_WARNINGS.get()
+        && let Some(remaining) = known.lock().unwrap().get_mut(&key)
+        && *remaining > 0
+    {
+        *remaining -= 1;
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fnv1a() {
+        let hash = |bytes: &[u8]| {
+            let mut hash = Fnv1a::default();
+            hash.write(bytes);
+            hash.0
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -8,22 +8,43 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{Applicability, Diagnostic, MultiSpan};
 use rustc_hir::HirId;
 use rustc_lint::{LateContext, Lint, LintContext};
 use rustc_span::source_map::Span;
 use std::env;
+use std::sync::OnceLock;
+
+/// The names of the lints with a page in Clippy's documentation, set by [`set_documented_lints`].
+static DOCUMENTED_LINTS: OnceLock<FxHashSet<&'static str>> = OnceLock::new();
+
+/// Sets the lints whose diagnostics link to their documentation. The other lints emitted with these
+/// functions, the custom lints and the lints of the plugins, have no page to link to.
+///
+/// Only the first call has an effect.
+pub fn set_documented_lints(lints: impl IntoIterator<Item = &'static Lint>) {
+    let _ = DOCUMENTED_LINTS.set(lints.into_iter().map(|lint| lint.name).collect());
+}
+
+/// Returns the URL of the documentation of the Clippy lint `name`, given without the `clippy::`
+/// prefix.
+pub fn lint_docs_url(name: &str) -> String {
+    format!(
+        "https://rust-lang.github.io/rust-clippy/{}/index.html#{name}",
+        &option_env!("RUST_RELEASE_NUM").map_or("master".to_string(), |n| {
+            // extract just major + minor version and ignore patch versions
+            format!("rust-{}", n.rsplit_once('.').unwrap().1)
+        })
+    )
+}
 
 fn docs_link(diag: &mut Diagnostic, lint: &'static Lint) {
-    if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
+    // a lint plugin has its own copy of this crate, where no lint is documented
+    let documented = DOCUMENTED_LINTS.get().map_or(false, |lints| lints.contains(lint.name));
+    if documented && env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
         if let Some(lint) = lint.name_lower().strip_prefix("clippy::") {
-            diag.help(format!(
-                "for further information visit https://rust-lang.github.io/rust-clippy/{}/index.html#{lint}",
-                &option_env!("RUST_RELEASE_NUM").map_or("master".to_string(), |n| {
-                    // extract just major + minor version and ignore patch versions
-                    format!("rust-{}", n.rsplit_once('.').unwrap().1)
-                })
-            ));
+            diag.help(format!("for further information visit {}", lint_docs_url(lint)));
         }
     }
 }
@@ -45,6 +45,7 @@ pub mod sym_helper;
 
 pub mod ast_utils;
 pub mod attrs;
+pub mod baseline;
 mod check_proc_macro;
 pub mod comparisons;
 pub mod consts;
@@ -56,7 +57,10 @@ pub mod macros;
 pub mod mir;
 pub mod msrvs;
 pub mod numeric_literal;
+pub mod path_lint_levels;
+pub mod path_patterns;
 pub mod paths;
+pub mod plugin;
 pub mod ptr;
 pub mod qualify_min_const_fn;
 pub mod source;
@@ -621,6 +625,8 @@ fn item_children_by_name(tcx: TyCtxt<'_>, def_id: DefId, name: Symbol) -> Vec<Re
 /// Also returns multiple results when there are mulitple paths under the same name e.g. `std::vec`
 /// would have both a [`DefKind::Mod`] and [`DefKind::Macro`].
 ///
+/// Paths of the crate being checked can start with `crate` or with the name of the crate.
+///
 /// This function is expensive and should be used sparingly.
 pub fn def_path_res(cx: &LateContext<'_>, path: &[&str]) -> Vec<Res> {
     fn find_crates(tcx: TyCtxt<'_>, name: Symbol) -> impl Iterator<Item = DefId> + '_ {
@@ -643,7 +649,7 @@ pub fn def_path_res(cx: &LateContext<'_>, path: &[&str]) -> Vec<Res> {
 
     let base_sym = Symbol::intern(base);
 
-    let local_crate = if tcx.crate_name(LOCAL_CRATE) == base_sym {
+    let local_crate = if base == "crate" || tcx.crate_name(LOCAL_CRATE) == base_sym {
         Some(LOCAL_CRATE.as_def_id())
     } else {
         None
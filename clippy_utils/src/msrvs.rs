@@ -59,6 +59,17 @@ fn parse_msrv(msrv: &str, sess: Option<&Session>, span: Option<Span>) -> Option<
     None
 }
 
+/// Where the initial MSRV is read from, see [`Msrv::read`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsrvSource {
+    /// The `msrv` key of `clippy.toml`.
+    ClippyToml,
+    /// The `rust-version` field of `Cargo.toml`, passed by Cargo in `CARGO_PKG_RUST_VERSION`.
+    CargoToml,
+    /// No MSRV is set, all lints assume the latest Rust version.
+    Unset,
+}
+
 /// Tracks the current MSRV from `clippy.toml`, `Cargo.toml` or set via `#[clippy::msrv]`
 #[derive(Debug, Clone, Default)]
 pub struct Msrv {
@@ -109,6 +120,19 @@ impl Msrv {
         PARSED.get_or_init(|| Self::read_inner(conf_msrv, sess))
     }
 
+    /// Returns the initial MSRV [`Msrv::read`] takes from the `msrv` of `clippy.toml` and the
+    /// `rust-version` of `Cargo.toml`, with where it comes from. The `clippy.toml` value takes
+    /// precedence when it is valid.
+    pub fn initial(conf_msrv: Option<&str>, cargo_msrv: Option<&str>) -> (Option<RustcVersion>, MsrvSource) {
+        if let Some(version) = conf_msrv.and_then(|s| parse_msrv(s, None, None)) {
+            (Some(version), MsrvSource::ClippyToml)
+        } else if let Some(version) = cargo_msrv.and_then(|s| parse_msrv(s, None, None)) {
+            (Some(version), MsrvSource::CargoToml)
+        } else {
+            (None, MsrvSource::Unset)
+        }
+    }
+
     pub fn current(&self) -> Option<RustcVersion> {
         self.stack.last().copied()
     }
@@ -0,0 +1,231 @@
+//! The lint levels set by the `[[overrides]]` sections of `clippy.toml` in the files matching some
+//! path patterns.
+//!
+//! The levels of late lints are set by overriding the `shallow_lint_levels_on` query, and work like
+//! lint attributes at the top of each file: the lint attributes in the file, `#[expect]` included,
+//! take precedence over them. Early lints are checked before the HIR exists, with the levels of the
+//! command line, so only allowing them is supported: their warnings in the files are dropped when
+//! they are emitted, the early lint passes being wrapped to know which warnings they emit. Lints at
+//! the `expect` level are still emitted, so that their expectations are fulfilled.
+
+use crate::path_patterns::{source_map_file_path, span_file_path, PathPattern};
+use rustc_ast as ast;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::{Diagnostic, DiagnosticId, Level as DiagnosticLevel};
+use rustc_hir::{Item, ItemKind, ItemLocalId, OwnerId, OwnerNode};
+use rustc_lint::{unerased_lint_store, EarlyContext, EarlyLintPass, Level, LintPass, LintStore};
+use rustc_middle::lint::{LintLevelSource, ShallowLintLevelMap};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::Session;
+use rustc_span::source_map::SourceMap;
+use rustc_span::symbol::Ident;
+use rustc_span::{Span, Symbol};
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The levels of the lints in the files matching some path patterns, set by the `[[overrides]]`
+/// sections of `clippy.toml`.
+static PATH_LINT_LEVELS: OnceLock<Vec<(Vec<PathPattern>, Vec<(String, Level)>)>> = OnceLock::new();
+
+/// Sets the levels of the lints in the files matching each list of path patterns. The lint names
+/// are expected in the `clippy::lint_name` form, and a later level of a lint takes precedence over
+/// the earlier ones.
+///
+/// Only the first call has an effect.
+pub fn set_path_lint_levels(levels: Vec<(Vec<PathPattern>, Vec<(String, Level)>)>) {
+    let _ = PATH_LINT_LEVELS.set(levels);
+}
+
+fn path_lint_levels() -> &'static [(Vec<PathPattern>, Vec<(String, Level)>)] {
+    PATH_LINT_LEVELS.get().map_or(&[], Vec::as_slice)
+}
+
+/// The levels of the lints in the file at `path`, in increasing order of precedence.
+fn levels_in(path: &Path) -> impl Iterator<Item = &'static (String, Level)> + '_ {
+    path_lint_levels()
+        .iter()
+        .filter(move |(patterns, _)| patterns.iter().any(|pattern| pattern.matches(path)))
+        .flat_map(|(_, levels)| levels)
+}
+
+type ShallowLintLevelsOn = for<'tcx> fn(TyCtxt<'tcx>, OwnerId) -> ShallowLintLevelMap;
+
+/// The provider of `shallow_lint_levels_on` replaced by [`provide`].
+static DEFAULT_SHALLOW_LINT_LEVELS_ON: OnceLock<ShallowLintLevelsOn> = OnceLock::new();
+
+/// Overrides the `shallow_lint_levels_on` query, to set the lint levels of the `[[overrides]]`
+/// sections in the items of the files they match.
+pub fn provide(providers: &mut Providers) {
+    let _ = DEFAULT_SHALLOW_LINT_LEVELS_ON.set(providers.shallow_lint_levels_on);
+    providers.shallow_lint_levels_on = shallow_lint_levels_on;
+}
+
+fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: OwnerId) -> ShallowLintLevelMap {
+    let mut levels = DEFAULT_SHALLOW_LINT_LEVELS_ON.get().unwrap()(tcx, owner);
+    if path_lint_levels().is_empty() {
+        return levels;
+    }
+    let Some(path) = owner_file_path(tcx, owner) else {
+        return levels;
+    };
+    // The items nested in an item of the same file inherit its levels
+    let parent = tcx.hir().opt_parent_id(owner.into());
+    if let Some(parent) = parent
+        && owner_file_path(tcx, parent.owner).as_ref() == Some(&path)
+    {
+        return levels;
+    }
+
+    // The last level set for each lint by the overrides matching the file
+    let store = unerased_lint_store(tcx);
+    let mut overrides = FxHashMap::default();
+    for &(ref name, level) in levels_in(&path) {
+        if let Ok(lints) = store.find_lints(name) {
+            overrides.extend(lints.into_iter().map(|lint| (lint, (name, level))));
+        }
+    }
+
+    let root = ItemLocalId::from_u32(0);
+    for (lint, (name, level)) in overrides {
+        // Like a lint attribute at the top of the file, the override takes precedence over the
+        // levels set outside of the file, but not over the lint attributes in the file, and a
+        // forbidden lint can't be allowed.
+        let current = levels
+            .specs
+            .get(&root)
+            .and_then(|specs| specs.get(&lint))
+            .copied()
+            .or_else(|| parent.map(|parent| tcx.lint_level_at_node(lint.lint, parent)));
+        if let Some((current_level, source)) = current
+            && (current_level == Level::Forbid
+                || matches!(
+                    source,
+                    LintLevelSource::Node { span, .. } if span_file_path(tcx.sess, span).as_ref() == Some(&path)
+                ))
+        {
+            continue;
+        }
+        let source = LintLevelSource::CommandLine(Symbol::intern(name), level);
+        levels
+            .specs
+            .get_mut_or_insert_default(root)
+            .insert(lint, (level, source));
+    }
+    levels
+}
+
+/// Returns the path of the file containing `owner`, which is the file of the module rather than
+/// the one of its declaration for an out-of-line module.
+fn owner_file_path(tcx: TyCtxt<'_>, owner: OwnerId) -> Option<PathBuf> {
+    let span = match tcx.hir().owner(owner) {
+        OwnerNode::Crate(module)
+        | OwnerNode::Item(Item {
+            kind: ItemKind::Mod(module),
+            ..
+        }) => module.spans.inner_span,
+        node => node.span(),
+    };
+    span_file_path(tcx.sess, span)
+}
+
+thread_local! {
+    /// The source map of the session of the compiler thread, to find the files of the warnings.
+    static SOURCE_MAP: RefCell<Option<Lrc<SourceMap>>> = RefCell::new(None);
+    /// Whether an early lint pass is running, the warnings emitted meanwhile are the ones of early
+    /// lints.
+    static IN_EARLY_PASS: Cell<bool> = Cell::new(false);
+}
+
+/// An early lint pass setting [`IN_EARLY_PASS`] while the inner pass runs. `LintPass` doesn't
+/// give the lints of a pass, so the lints emitted by the early passes can't be known in advance.
+struct EarlyPass(Box<dyn EarlyLintPass>);
+
+#[allow(rustc::lint_pass_impl_without_macro)]
+impl LintPass for EarlyPass {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+/// Implements the methods of `EarlyLintPass`, given by `rustc_lint::early_lint_methods!`, by
+/// calling the same method of the inner pass with [`IN_EARLY_PASS`] set.
+macro_rules! early_pass_methods {
+    ([$context:ty], [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => {
+        $(fn $name(&mut self, cx: &$context, $($param: $arg),*) {
+            IN_EARLY_PASS.with(|in_early_pass| in_early_pass.set(true));
+            self.0.$name(cx, $($param),*);
+            IN_EARLY_PASS.with(|in_early_pass| in_early_pass.set(false));
+        })*
+    };
+}
+
+impl EarlyLintPass for EarlyPass {
+    rustc_lint::early_lint_methods!(early_pass_methods, [EarlyContext<'_>]);
+}
+
+type TrackDiagnostic = fn(&mut Diagnostic, &mut dyn FnMut(&mut Diagnostic));
+
+/// The function that `rustc_errors::TRACK_DIAGNOSTICS` was set to before
+/// [`allow_early_lints_by_path`].
+static PREVIOUS_TRACK_DIAGNOSTIC: OnceLock<TrackDiagnostic> = OnceLock::new();
+
+static TRACK_DIAGNOSTIC: TrackDiagnostic = track_diagnostic;
+
+/// Drops the warnings of the early lints allowed in their file by the `[[overrides]]` sections,
+/// once all the lint passes are registered in `store`.
+pub fn allow_early_lints_by_path(sess: &Session, store: &mut LintStore) {
+    if path_lint_levels().is_empty() {
+        return;
+    }
+    for factory in mem::take(&mut store.pre_expansion_passes) {
+        store.register_pre_expansion_pass(move || Box::new(EarlyPass(factory())));
+    }
+    for factory in mem::take(&mut store.early_passes) {
+        store.register_early_pass(move || Box::new(EarlyPass(factory())));
+    }
+    SOURCE_MAP.with(|source_map| *source_map.borrow_mut() = Some(sess.parse_sess.clone_source_map()));
+    let previous = rustc_errors::TRACK_DIAGNOSTICS.swap(&TRACK_DIAGNOSTIC);
+    let _ = PREVIOUS_TRACK_DIAGNOSTIC.set(*previous);
+}
+
+/// Called by the compiler for every diagnostic, `emit` emits it.
+fn track_diagnostic(diagnostic: &mut Diagnostic, emit: &mut dyn FnMut(&mut Diagnostic)) {
+    if !is_allowed_by_path(diagnostic) {
+        match PREVIOUS_TRACK_DIAGNOSTIC.get() {
+            Some(previous) => previous(diagnostic, emit),
+            None => emit(diagnostic),
+        }
+    }
+}
+
+/// Whether `diagnostic` is the warning of an early lint allowed in its file by the last override
+/// setting its level.
+fn is_allowed_by_path(diagnostic: &Diagnostic) -> bool {
+    if !matches!(
+        diagnostic.level(),
+        DiagnosticLevel::Warning(_) | DiagnosticLevel::Error { lint: true }
+    ) {
+        return false;
+    }
+    let Some(DiagnosticId::Lint { name, .. }) = &diagnostic.code else {
+        return false;
+    };
+    if !IN_EARLY_PASS.with(Cell::get) {
+        return false;
+    }
+    let Some(span) = diagnostic.span.primary_span() else {
+        return false;
+    };
+    let path = SOURCE_MAP.with(|source_map| {
+        source_map
+            .borrow()
+            .as_deref()
+            .and_then(|source_map| source_map_file_path(source_map, span))
+    });
+    path.and_then(|path| levels_in(&path).filter(|(lint, _)| lint == name).last())
+        .map_or(false, |&(_, level)| level == Level::Allow)
+}
@@ -0,0 +1,140 @@
+//! Matching of source files against the path patterns used in `clippy.toml`.
+
+use rustc_session::Session;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, Span};
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+/// A glob pattern matched against the path of a source file relative to the crate root, e.g.
+/// `tests/**` or `src/generated/*.rs`.
+///
+/// `*` matches any sequence of characters within a path component, `?` matches a single
+/// character and `**` matches any number of path components. A pattern matching a directory also
+/// matches every file below it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PathPattern {
+    pattern: String,
+}
+
+impl PathPattern {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.replace('\\', "/"),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        let pattern = self
+            .pattern
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .collect::<Vec<_>>();
+        let path = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        match_components(&pattern, &path)
+    }
+}
+
+/// Checks if `name` matches the glob `pattern`, matched like a component of a [`PathPattern`]: `*`
+/// matches any sequence of characters and `?` a single character.
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    match_component(pattern.as_bytes(), name.as_bytes())
+}
+
+fn match_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        // the pattern matched a directory containing the path
+        None => true,
+        Some((&"**", rest)) => (0..=path.len()).any(|skipped| match_components(rest, &path[skipped..])),
+        Some((first, rest)) => path.split_first().map_or(false, |(component, path_rest)| {
+            match_component(first.as_bytes(), component.as_bytes()) && match_components(rest, path_rest)
+        }),
+    }
+}
+
+fn match_component(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => (0..=name.len()).any(|skipped| match_component(rest, &name[skipped..])),
+        (Some((b'?', rest)), Some((_, name_rest))) => match_component(rest, name_rest),
+        (Some((p, rest)), Some((n, name_rest))) if p == n => match_component(rest, name_rest),
+        _ => false,
+    }
+}
+
+/// Returns the path of the file containing `span`, relative to the root of the crate being
+/// checked (`CARGO_MANIFEST_DIR`) when the file is inside of it.
+///
+/// Returns `None` for spans that don't point into a real file, e.g. spans of macro expansions
+/// from other crates.
+pub fn span_file_path(sess: &Session, span: Span) -> Option<PathBuf> {
+    source_map_file_path(sess.source_map(), span)
+}
+
+/// Same as [`span_file_path`], for the spans of `source_map`.
+pub fn source_map_file_path(source_map: &SourceMap, span: Span) -> Option<PathBuf> {
+    let FileName::Real(name) = source_map.span_to_filename(span.source_callsite()) else {
+        return None;
+    };
+    let path = name.local_path()?;
+    let path = match env::current_dir() {
+        Ok(current_dir) => current_dir.join(path),
+        Err(_) => path.to_path_buf(),
+    };
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(root) => Some(path.strip_prefix(root).map_or_else(|_| path.clone(), Path::to_path_buf)),
+        None => Some(path),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        PathPattern::new(pattern).matches(Path::new(path))
+    }
+
+    #[test]
+    fn literal() {
+        assert!(matches("src/lib.rs", "src/lib.rs"));
+        assert!(matches("./src/lib.rs", "src/lib.rs"));
+        assert!(!matches("src/lib.rs", "src/main.rs"));
+    }
+
+    #[test]
+    fn directory() {
+        assert!(matches("tests", "tests/ui/foo.rs"));
+        assert!(matches("tests/", "tests/foo.rs"));
+        assert!(!matches("tests", "src/tests.rs"));
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "src/lib.txt"));
+        assert!(matches("src/gen_?.rs", "src/gen_a.rs"));
+        assert!(!matches("src/gen_?.rs", "src/gen_ab.rs"));
+        assert!(matches("tests/**", "tests/a/b/c.rs"));
+        assert!(matches("**/generated/*.rs", "src/a/generated/x.rs"));
+        assert!(matches("**/generated/*.rs", "generated/x.rs"));
+        assert!(!matches("**/generated/*.rs", "src/generated.rs"));
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob_matches("Secret*", "SecretString"));
+        assert!(glob_matches("*", "secrets"));
+        assert!(!glob_matches("Secret*", "ApiKey"));
+    }
+}
@@ -0,0 +1,124 @@
+//! The interface of out-of-tree lint plugins, dynamic libraries loaded by `clippy-driver` from the
+//! paths listed in the `lint-plugins` configuration.
+//!
+//! A plugin is a crate with `crate-type = ["dylib"]`, built with the same rustc and the
+//! `clippy_utils` of the same commit as Clippy, the loading of other plugins is refused. Its lints
+//! and lint passes are declared like the ones of Clippy, and registered by the function given to
+//! [`declare_lint_plugin!`], mirroring `clippy_lints::register_plugins`:
+//!
+//! ```rust,ignore
+//! #![feature(rustc_private)]
+//!
+//! extern crate rustc_hir;
+//! extern crate rustc_lint;
+//! extern crate rustc_session;
+//!
+//! use clippy_utils::msrvs::Msrv;
+//! use clippy_utils::plugin::Registry;
+//!
+//! rustc_session::declare_tool_lint! {
+//!     pub clippy::HANDLER_WITHOUT_INSTRUMENT,
+//!     Warn,
+//!     "request handler without `#[instrument]`",
+//!     report_in_external_macro: true
+//! }
+//!
+//! pub struct HandlerWithoutInstrument {
+//!     msrv: Msrv,
+//!     handler_prefix: String,
+//! }
+//!
+//! rustc_session::impl_lint_pass!(HandlerWithoutInstrument => [HANDLER_WITHOUT_INSTRUMENT]);
+//!
+//! impl<'tcx> rustc_lint::LateLintPass<'tcx> for HandlerWithoutInstrument {
+//!     // ...
+//!     clippy_utils::extract_msrv_attr!(LateContext);
+//! }
+//!
+//! clippy_utils::declare_lint_plugin!(register_lints);
+//!
+//! fn register_lints(registry: &mut Registry<'_>) {
+//!     // `conf` is the `conf` table of the plugin in `clippy.toml`
+//!     let conf: toml::value::Table = toml::from_str(registry.conf).unwrap_or_default();
+//!     let handler_prefix = conf.get("handler-prefix").and_then(|v| v.as_str()).unwrap_or("handle_");
+//!     let handler_prefix = handler_prefix.to_string();
+//!     let msrv = registry.msrv.clone();
+//!
+//!     registry.store.register_lints(&[HANDLER_WITHOUT_INSTRUMENT]);
+//!     registry.store.register_late_pass(move |_| {
+//!         Box::new(HandlerWithoutInstrument {
+//!             msrv: msrv.clone(),
+//!             handler_prefix: handler_prefix.clone(),
+//!         })
+//!     });
+//! }
+//! ```
+
+use crate::msrvs::Msrv;
+use rustc_lint::LintStore;
+use rustc_session::Session;
+
+/// The version of rustc a plugin is built with, like `rustc_interface::util::rustc_version_str`
+/// with its commit hash, followed by a NUL byte.
+pub const RUSTC_VERSION: &str = concat!(env!("RUSTC_VERSION"), "\0");
+
+/// The commit of Clippy the `clippy_utils` of a plugin is built from, like the `commit_hash` of
+/// `rustc_tools_util::get_version_info!`, followed by a NUL byte.
+pub const CLIPPY_COMMIT: &str = concat!(env!("GIT_HASH"), "\0");
+
+/// The symbol of the byte array static holding the [`RUSTC_VERSION`] of a plugin.
+pub const RUSTC_VERSION_SYMBOL: &[u8] = b"CLIPPY_LINT_PLUGIN_RUSTC_VERSION";
+
+/// The symbol of the byte array static holding the [`CLIPPY_COMMIT`] of a plugin.
+pub const CLIPPY_COMMIT_SYMBOL: &[u8] = b"CLIPPY_LINT_PLUGIN_CLIPPY_COMMIT";
+
+/// The symbol of the [`Registrar`] of a plugin.
+pub const REGISTRAR_SYMBOL: &[u8] = b"clippy_lint_plugin_registrar";
+
+/// The function registering the lints of a plugin.
+pub type Registrar = fn(&mut Registry<'_>);
+
+/// What a plugin registers its lints with.
+pub struct Registry<'a> {
+    /// The lint store to register the lints and lint passes of the plugin in.
+    pub store: &'a mut LintStore,
+    pub sess: &'a Session,
+    /// The MSRV of the crate, from the `msrv` configuration or the `rust-version` of `Cargo.toml`.
+    pub msrv: &'a Msrv,
+    /// The `conf` table of the plugin in `clippy.toml`, as TOML, for the plugin to deserialize.
+    pub conf: &'a str,
+}
+
+/// Converts a string to a byte array, for the version statics of a plugin to have a layout that
+/// doesn't depend on the compiler.
+#[doc(hidden)]
+pub const fn to_byte_array<const N: usize>(s: &str) -> [u8; N] {
+    let bytes = s.as_bytes();
+    let mut array = [0; N];
+    let mut i = 0;
+    while i < N {
+        array[i] = bytes[i];
+        i += 1;
+    }
+    array
+}
+
+/// Exports the symbols `clippy-driver` loads a plugin with, `$registrar` being the function
+/// registering the lints of the plugin. See the [`plugin`](crate::plugin) module.
+#[macro_export]
+macro_rules! declare_lint_plugin {
+    ($registrar:path) => {
+        #[no_mangle]
+        pub static CLIPPY_LINT_PLUGIN_RUSTC_VERSION: [u8; $crate::plugin::RUSTC_VERSION.len()] =
+            $crate::plugin::to_byte_array($crate::plugin::RUSTC_VERSION);
+
+        #[no_mangle]
+        pub static CLIPPY_LINT_PLUGIN_CLIPPY_COMMIT: [u8; $crate::plugin::CLIPPY_COMMIT.len()] =
+            $crate::plugin::to_byte_array($crate::plugin::CLIPPY_COMMIT);
+
+        #[no_mangle]
+        pub fn clippy_lint_plugin_registrar(registry: &mut $crate::plugin::Registry<'_>) {
+            $registrar(registry);
+        }
+    };
+}
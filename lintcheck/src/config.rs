@@ -1,6 +1,6 @@
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
 
 fn get_clap_config() -> ArgMatches {
     Command::new("lintcheck")
@@ -39,6 +39,35 @@ fn get_clap_config() -> ArgMatches {
                 .help("Run clippy on the dependencies of crates specified in crates-toml")
                 .conflicts_with("threads")
                 .conflicts_with("fix"),
+            Arg::new("offline")
+                .long("offline")
+                .help("Don't access the network, crates.io sources are read from --vendor-dir"),
+            Arg::new("vendor-dir")
+                .action(ArgAction::Set)
+                .value_name("DIR")
+                .long("vendor-dir")
+                .help("Check the crates vendored in DIR by `cargo vendor` offline, also taking dependencies from it"),
+            Arg::new("verify-fixes")
+                .long("verify-fixes")
+                .help("Apply the suggestions of each lint to a copy of each crate and report the ones breaking it")
+                .conflicts_with("fix")
+                .conflicts_with("recursive"),
+            Arg::new("diff")
+                .action(ArgAction::Set)
+                .num_args(2)
+                .value_names(["OLD", "NEW"])
+                .value_parser(clap::value_parser!(PathBuf))
+                .long("diff")
+                .help("Print the differences between the warnings of two runs, saved as JSON in lintcheck-logs"),
+            Arg::new("lint-timings")
+                .long("lint-timings")
+                .help("Measure the time spent in each lint pass and compare it with the previous run")
+                .conflicts_with("fix"),
+            Arg::new("minimize-ices")
+                .long("minimize-ices")
+                .help("Reduce the crates Clippy panics on to a reproduction for tests/ui/crashes")
+                .conflicts_with("fix")
+                .conflicts_with("recursive"),
         ])
         .get_matches()
 }
@@ -47,8 +76,8 @@ fn get_clap_config() -> ArgMatches {
 pub(crate) struct LintcheckConfig {
     /// max number of jobs to spawn (default 1)
     pub max_jobs: usize,
-    /// we read the sources to check from here
-    pub sources_toml_path: PathBuf,
+    /// we read the sources to check from here, or check all the crates of `vendor_dir` if `None`
+    pub sources_toml_path: Option<PathBuf>,
     /// we save the clippy lint results here
     pub lintcheck_results_path: PathBuf,
     /// Check only a specified package
@@ -61,33 +90,66 @@ pub(crate) struct LintcheckConfig {
     pub markdown: bool,
     /// Run clippy on the dependencies of crates
     pub recursive: bool,
+    /// We save the time spent in each lint pass here, with `--lint-timings`
+    pub lint_timings_path: Option<PathBuf>,
+    /// Don't access the network
+    pub offline: bool,
+    /// The absolute path of a directory created by `cargo vendor`, dependencies are taken from
+    /// here
+    pub vendor_dir: Option<PathBuf>,
+    /// The JSON warnings of two runs to compare, instead of checking crates
+    pub diff: Option<(PathBuf, PathBuf)>,
+    /// We save the suggestions breaking the crates here, with `--verify-fixes`
+    pub verify_fixes_path: Option<PathBuf>,
+    /// Minimize the crates with ICEs, checking each of them up to hundreds of times
+    pub minimize_ices: bool,
 }
 
 impl LintcheckConfig {
     pub fn new() -> Self {
         let clap_config = get_clap_config();
 
+        let vendor_dir = clap_config.get_one::<String>("vendor-dir").map(|dir| {
+            fs::canonicalize(dir).unwrap_or_else(|e| {
+                eprintln!("ERROR: could not find the vendor directory {dir}: {e}");
+                process::exit(1);
+            })
+        });
+
         // first, check if we got anything passed via the LINTCHECK_TOML env var,
         // if not, ask clap if we got any value for --crates-toml  <foo>
-        // if not, use the default "lintcheck/lintcheck_crates.toml"
-        let sources_toml = env::var("LINTCHECK_TOML").unwrap_or_else(|_| {
-            clap_config
-                .get_one::<String>("crates-toml")
-                .map_or("lintcheck/lintcheck_crates.toml", |s| &**s)
-                .into()
-        });
+        // if not, check the crates of --vendor-dir <dir>, or use the default
+        // "lintcheck/lintcheck_crates.toml"
+        let sources_toml = env::var("LINTCHECK_TOML")
+            .ok()
+            .or_else(|| clap_config.get_one::<String>("crates-toml").cloned());
+        let sources_toml_path = match (sources_toml, &vendor_dir) {
+            (Some(sources_toml), _) => Some(PathBuf::from(sources_toml)),
+            (None, Some(_)) => None,
+            (None, None) => Some(PathBuf::from("lintcheck/lintcheck_crates.toml")),
+        };
 
         let markdown = clap_config.contains_id("markdown");
-        let sources_toml_path = PathBuf::from(sources_toml);
 
         // for the path where we save the lint results, get the filename without extension (so for
-        // wasd.toml, use "wasd"...)
-        let filename: PathBuf = sources_toml_path.file_stem().unwrap().into();
+        // wasd.toml, use "wasd"...), or the name of the vendor directory
+        let filename: PathBuf = sources_toml_path
+            .as_deref()
+            .or(vendor_dir.as_deref())
+            .and_then(Path::file_stem)
+            .unwrap()
+            .into();
         let lintcheck_results_path = PathBuf::from(format!(
             "lintcheck-logs/{}_logs.{}",
             filename.display(),
             if markdown { "md" } else { "txt" }
         ));
+        let verify_fixes_path = clap_config
+            .contains_id("verify-fixes")
+            .then(|| PathBuf::from(format!("lintcheck-logs/{}_fixes.md", filename.display())));
+        let lint_timings_path = clap_config
+            .contains_id("lint-timings")
+            .then(|| PathBuf::from(format!("lintcheck-logs/{}_timings.json", filename.display())));
 
         // look at the --threads arg, if 0 is passed, ask rayon rayon how many threads it would spawn and
         // use half of that for the physical core count
@@ -103,6 +165,12 @@ impl LintcheckConfig {
             None => 1,
         };
 
+        let diff = clap_config.get_many::<PathBuf>("diff").map(|mut paths| {
+            let old = paths.next().unwrap().clone();
+            let new = paths.next().unwrap().clone();
+            (old, new)
+        });
+
         let lint_filter: Vec<String> = clap_config
             .get_many::<String>("filter")
             .map(|iter| {
@@ -126,6 +194,12 @@ impl LintcheckConfig {
             lint_filter,
             markdown,
             recursive: clap_config.contains_id("recursive"),
+            lint_timings_path,
+            offline: clap_config.contains_id("offline") || vendor_dir.is_some(),
+            vendor_dir,
+            diff,
+            verify_fixes_path,
+            minimize_ices: clap_config.contains_id("minimize-ices"),
         }
     }
 }
@@ -0,0 +1,162 @@
+//! `--diff OLD NEW` compares the warnings of two runs, saved as JSON next to the logs, to show
+//! the warnings a change of Clippy added, removed or changed rather than only how the lint counts
+//! changed
+
+use crate::ClippyWarning;
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Crate name, file, line, column and lint of a warning
+type Position<'a> = (&'a str, &'a str, usize, usize, &'a str);
+
+/// The warnings of both runs at each position
+type WarningsByPosition<'a> = BTreeMap<Position<'a>, (Vec<&'a ClippyWarning>, Vec<&'a ClippyWarning>)>;
+
+#[derive(Default)]
+struct Diff<'a> {
+    added: Vec<&'a ClippyWarning>,
+    removed: Vec<&'a ClippyWarning>,
+    /// Warnings at the same position with another message or snippet, as `(old, new)`
+    changed: Vec<(&'a ClippyWarning, &'a ClippyWarning)>,
+}
+
+fn read_warnings(path: &Path) -> Vec<ClippyWarning> {
+    let content = fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read {}", path.display()));
+    serde_json::from_str(&content).unwrap_or_else(|e| panic!("Failed to parse {}: \n{e}", path.display()))
+}
+
+/// Prints the warnings added, removed and changed between the runs saved at `old_path` and
+/// `new_path`
+pub(crate) fn print_diff(old_path: &Path, new_path: &Path, markdown: bool) {
+    let old_warnings = read_warnings(old_path);
+    let new_warnings = read_warnings(new_path);
+    print!("{}", diff(&old_warnings, &new_warnings).to_output(markdown));
+}
+
+fn diff<'a>(old_warnings: &'a [ClippyWarning], new_warnings: &'a [ClippyWarning]) -> Diff<'a> {
+    let mut by_position = WarningsByPosition::new();
+    for warning in old_warnings {
+        by_position.entry(position(warning)).or_default().0.push(warning);
+    }
+    for warning in new_warnings {
+        by_position.entry(position(warning)).or_default().1.push(warning);
+    }
+
+    let mut diff = Diff::default();
+    for (old, mut new) in by_position.into_values() {
+        // a lint can warn several times at the same position, the identical warnings are
+        // matched first
+        let mut old_unmatched = Vec::new();
+        for old_warning in old {
+            match new
+                .iter()
+                .position(|new_warning| is_same_warning(old_warning, new_warning))
+            {
+                Some(index) => {
+                    new.remove(index);
+                },
+                None => old_unmatched.push(old_warning),
+            }
+        }
+
+        let changed = old_unmatched.len().min(new.len());
+        diff.changed
+            .extend(old_unmatched.iter().copied().zip(new.iter().copied()));
+        diff.removed.extend(&old_unmatched[changed..]);
+        diff.added.extend(&new[changed..]);
+    }
+    diff
+}
+
+fn position(warning: &ClippyWarning) -> Position<'_> {
+    (
+        &warning.crate_name,
+        &warning.file,
+        warning.line,
+        warning.column,
+        &warning.lint_type,
+    )
+}
+
+fn is_same_warning(old: &ClippyWarning, new: &ClippyWarning) -> bool {
+    old.message == new.message && old.rendered == new.rendered
+}
+
+impl Diff<'_> {
+    fn to_output(&self, markdown: bool) -> String {
+        let mut output = String::new();
+        if markdown {
+            output.push_str("| added | removed | changed |\n| --- | --- | --- |\n");
+            let _ = writeln!(
+                output,
+                "| {} | {} | {} |",
+                self.added.len(),
+                self.removed.len(),
+                self.changed.len()
+            );
+        } else {
+            let _ = writeln!(
+                output,
+                "{} added, {} removed, {} changed",
+                self.added.len(),
+                self.removed.len(),
+                self.changed.len()
+            );
+        }
+
+        for (title, warnings) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if warnings.is_empty() {
+                continue;
+            }
+            let _ = write!(output, "\n### {title}\n");
+            for warning in warnings {
+                write_header(&mut output, warning, markdown);
+                write_snippet(&mut output, &warning.rendered, "", markdown);
+            }
+        }
+
+        if !self.changed.is_empty() {
+            output.push_str("\n### Changed\n");
+            for (old, new) in &self.changed {
+                write_header(&mut output, new, markdown);
+                let mut snippet = String::new();
+                for line in old.rendered.lines() {
+                    let _ = writeln!(snippet, "-{line}");
+                }
+                for line in new.rendered.lines() {
+                    let _ = writeln!(snippet, "+{line}");
+                }
+                write_snippet(&mut output, &snippet, "diff", markdown);
+            }
+        }
+        output
+    }
+}
+
+fn write_header(output: &mut String, warning: &ClippyWarning, markdown: bool) {
+    let file_with_pos = format!("{}:{}:{}", warning.file, warning.line, warning.column);
+    if markdown {
+        let _ = write!(
+            output,
+            "\n#### `{}` at `{file_with_pos}`\n\n\"{}\"\n\n",
+            warning.lint_type, warning.message
+        );
+    } else {
+        let _ = writeln!(
+            output,
+            "\n{file_with_pos} {} \"{}\"",
+            warning.lint_type, warning.message
+        );
+    }
+}
+
+fn write_snippet(output: &mut String, snippet: &str, language: &str, markdown: bool) {
+    if markdown {
+        let _ = writeln!(output, "```{language}\n{}\n```", snippet.trim_end());
+    } else {
+        let _ = writeln!(output, "{}", snippet.trim_end());
+    }
+}
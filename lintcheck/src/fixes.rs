@@ -0,0 +1,181 @@
+//! In `--verify-fixes` mode the suggestions of each lint are applied in isolation, using
+//! `cargo clippy --fix-lint`, to a copy of each crate. The suggestions making the crate fail to
+//! compile or fail its tests are reported along with their diff.
+
+use crate::config::LintcheckConfig;
+use crate::{clippy_project_root, copy_crate, offline_cargo_args, Crate};
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// The number of lines of the output of `cargo test` kept in the report
+const OUTPUT_LINES: usize = 40;
+
+/// The suggestions of a lint that broke a crate
+pub(crate) struct BrokenFix {
+    lint: String,
+    crate_name: String,
+    crate_version: String,
+    /// `doesn't compile` or `breaks tests`
+    problem: &'static str,
+    diff: String,
+    /// The end of the output of the failing `cargo test`
+    output: String,
+}
+
+enum TestOutcome {
+    DoesNotCompile(String),
+    FailsTests(String),
+    Passes,
+}
+
+fn cargo_test(crate_dir: &Path, target_dir: &Path, cargo_args: &[String]) -> TestOutcome {
+    let run = |extra_args: &[&str]| -> Output {
+        Command::new("cargo")
+            .arg("test")
+            .args(extra_args)
+            .args(cargo_args)
+            .env("CARGO_TARGET_DIR", target_dir)
+            .current_dir(crate_dir)
+            .output()
+            .expect("failed to run cargo")
+    };
+
+    let build = run(&["--no-run"]);
+    if !build.status.success() {
+        return TestOutcome::DoesNotCompile(output_tail(&build));
+    }
+    let test = run(&[]);
+    if test.status.success() {
+        TestOutcome::Passes
+    } else {
+        TestOutcome::FailsTests(output_tail(&test))
+    }
+}
+
+fn output_tail(output: &Output) -> String {
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(OUTPUT_LINES)..].join("\n")
+}
+
+/// Applies the suggestions of each of `lints` to a copy of `krate`, and returns the ones that
+/// broke it
+pub(crate) fn verify_fixes(
+    krate: &Crate,
+    lints: &[&str],
+    cargo_clippy_path: &Path,
+    config: &LintcheckConfig,
+    lint_filter: &[String],
+) -> Vec<BrokenFix> {
+    let fixes_dir = clippy_project_root()
+        .join("target/lintcheck/fixes")
+        .join(format!("{}-{}", krate.name, krate.version));
+    let pristine_dir = fixes_dir.join("pristine");
+    let fixed_dir = fixes_dir.join("fixed");
+    let target_dir = clippy_project_root()
+        .join("target/lintcheck/shared_target_dir")
+        .join(format!("fixes_{}", rayon::current_thread_index().unwrap_or_default()));
+    let cargo_args = offline_cargo_args(config);
+    let clippy_args = krate.clippy_args(lint_filter);
+
+    fs::create_dir_all(&fixes_dir).unwrap();
+    copy_crate(&krate.path, &pristine_dir);
+
+    // the suggestions can only be blamed for failing tests if the tests passed without them
+    let tests_pass = match cargo_test(&pristine_dir, &target_dir, &cargo_args) {
+        TestOutcome::DoesNotCompile(_) => {
+            println!(
+                "Not verifying the suggestions for {} {}, it doesn't compile",
+                krate.name, krate.version
+            );
+            return Vec::new();
+        },
+        TestOutcome::FailsTests(_) => false,
+        TestOutcome::Passes => true,
+    };
+
+    let mut broken_fixes = Vec::new();
+    for &lint in lints {
+        copy_crate(&pristine_dir, &fixed_dir);
+        Command::new(cargo_clippy_path)
+            .args(["--", "--fix-lint", lint])
+            .args(&cargo_args)
+            .arg("--")
+            .args(&clippy_args)
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .current_dir(&fixed_dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("failed to run cargo clippy");
+
+        let diff = Command::new("git")
+            .args(["diff", "--no-index", "--no-color", "pristine", "fixed"])
+            .current_dir(&fixes_dir)
+            .output()
+            .expect("failed to run git diff");
+        let diff = String::from_utf8_lossy(&diff.stdout).into_owned();
+        // the lint has no suggestions
+        if diff.is_empty() {
+            continue;
+        }
+
+        let (problem, output) = match cargo_test(&fixed_dir, &target_dir, &cargo_args) {
+            TestOutcome::DoesNotCompile(output) => ("doesn't compile", output),
+            TestOutcome::FailsTests(output) if tests_pass => ("breaks tests", output),
+            TestOutcome::FailsTests(_) | TestOutcome::Passes => continue,
+        };
+        println!("The suggestions of {lint} broke {} {}", krate.name, krate.version);
+        broken_fixes.push(BrokenFix {
+            lint: lint.to_string(),
+            crate_name: krate.name.clone(),
+            crate_version: krate.version.clone(),
+            problem,
+            diff,
+            output,
+        });
+    }
+    broken_fixes
+}
+
+/// Writes a table of the lints whose suggestions broke a crate, followed by the diff of the
+/// suggestions and the errors, to `path`
+pub(crate) fn report(broken_fixes: &[BrokenFix], path: &Path) {
+    let mut broken_fixes: Vec<&BrokenFix> = broken_fixes.iter().collect();
+    broken_fixes.sort_by(|a, b| (&a.lint, &a.crate_name).cmp(&(&b.lint, &b.crate_name)));
+
+    let mut text = String::from("| lint | crate | problem |\n| --- | --- | --- |\n");
+    for fix in &broken_fixes {
+        let _ = writeln!(
+            text,
+            "| `{}` | {} {} | {} |",
+            fix.lint, fix.crate_name, fix.crate_version, fix.problem
+        );
+    }
+    for fix in &broken_fixes {
+        let _ = write!(
+            text,
+            "\n### `{}` in {} {}\n\n```diff\n{}```\n\n",
+            fix.lint, fix.crate_name, fix.crate_version, fix.diff
+        );
+        let _ = write!(
+            text,
+            "<details><summary>cargo test</summary>\n\n```\n{}\n```\n\n</details>\n",
+            fix.output
+        );
+    }
+
+    println!(
+        "\n{} broken suggestions, writing them to {}",
+        broken_fixes.len(),
+        path.display()
+    );
+    fs::write(path, text).unwrap();
+}
@@ -0,0 +1,345 @@
+//! When Clippy panics while checking a crate, the crate is checked again with `RUST_BACKTRACE=1`
+//! to record the backtrace. A copy of the crate is then reduced by removing modules and items for
+//! as long as Clippy keeps panicking at the same place, and what remains is flattened into a single
+//! file that can be added to `tests/ui/crashes`.
+
+use crate::config::LintcheckConfig;
+use crate::{clippy_project_root, copy_crate, offline_cargo_args, Crate};
+
+use std::fmt::Write as _;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use proc_macro2::{LineColumn, Span};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::Item;
+use walkdir::WalkDir;
+
+/// The crate is checked at most this many times while minimizing it
+const MAX_CHECKS: usize = 500;
+
+/// Returns the message of the panic or compiler bug printed by `cargo clippy` to stderr
+pub(crate) fn ice_message(stderr: &str) -> Option<String> {
+    stderr.lines().find_map(|line| {
+        if let Some((_, message)) = line.split_once("panicked at ") {
+            Some(message.to_string())
+        } else {
+            line.strip_prefix("error: internal compiler error: ")
+                .map(ToString::to_string)
+        }
+    })
+}
+
+/// The location in Clippy or rustc of the panic described by `message`. Unlike the rest of the
+/// message, it doesn't depend on the code being checked.
+fn ice_location(message: &str) -> &str {
+    if let Some((_, location)) = message.rsplit_once("', ") {
+        location
+    } else {
+        message.split_once(": ").map_or(message, |(location, _)| location)
+    }
+}
+
+struct Checker<'a> {
+    cargo_clippy_path: &'a Path,
+    cargo_args: Vec<String>,
+    clippy_args: Vec<&'a str>,
+    target_dir: PathBuf,
+    /// Where Clippy panicked in the original crate
+    location: String,
+    checks: usize,
+}
+
+impl Checker<'_> {
+    /// Runs `cargo clippy` on the crate in `crate_dir`, returning its stderr
+    fn check(&mut self, crate_dir: &Path, backtrace: bool) -> String {
+        self.checks += 1;
+        let output = Command::new(self.cargo_clippy_path)
+            .arg("--")
+            .args(&self.cargo_args)
+            .arg("--")
+            .args(&self.clippy_args)
+            .env("CARGO_TARGET_DIR", &self.target_dir)
+            .env("RUST_BACKTRACE", if backtrace { "1" } else { "0" })
+            .current_dir(crate_dir)
+            .output()
+            .expect("failed to run cargo clippy");
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    }
+
+    fn still_panics(&mut self, crate_dir: &Path) -> bool {
+        let stderr = self.check(crate_dir, false);
+        ice_message(&stderr).map_or(false, |message| ice_location(&message) == self.location)
+    }
+}
+
+/// Records the backtrace of the ICE Clippy hit while checking `krate`, then minimizes a copy of
+/// the crate and writes a test reproducing the ICE to `target/lintcheck/ices`
+pub(crate) fn reproduce(
+    krate: &Crate,
+    cargo_clippy_path: &Path,
+    clippy_driver_path: &Path,
+    config: &LintcheckConfig,
+    lint_filter: &[String],
+) {
+    let ice_dir = clippy_project_root()
+        .join("target/lintcheck/ices")
+        .join(format!("{}-{}", krate.name, krate.version));
+    let crate_dir = ice_dir.join("minimized");
+    let mut checker = Checker {
+        cargo_clippy_path,
+        cargo_args: offline_cargo_args(config),
+        clippy_args: krate.clippy_args(lint_filter),
+        target_dir: clippy_project_root().join("target/lintcheck/shared_target_dir/ices"),
+        location: String::new(),
+        checks: 0,
+    };
+
+    fs::create_dir_all(&ice_dir).unwrap();
+    copy_crate(&krate.path, &crate_dir);
+
+    println!(
+        "Checking {} {} again with RUST_BACKTRACE=1 after an ICE",
+        krate.name, krate.version
+    );
+    let stderr = checker.check(&crate_dir, true);
+    let backtrace_path = ice_dir.join("backtrace.txt");
+    fs::write(&backtrace_path, &stderr).unwrap();
+    let Some(message) = ice_message(&stderr) else {
+        println!("Clippy didn't panic again on {} {}", krate.name, krate.version);
+        return;
+    };
+    println!("Wrote the backtrace to {}", backtrace_path.display());
+    checker.location = ice_location(&message).to_string();
+
+    println!("Minimizing {} {}", krate.name, krate.version);
+    let mut files: Vec<PathBuf> = WalkDir::new(crate_dir.join("src"))
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| path.extension().map_or(false, |ext| ext == "rs"))
+        .collect();
+    // the crate root and the modules near it first, removing a module skips its files
+    files.sort_by_key(|path| (path.components().count(), path.clone()));
+    for file in &files {
+        minimize_file(&mut checker, &crate_dir, file);
+    }
+    if checker.checks > MAX_CHECKS {
+        println!("Gave up minimizing after {MAX_CHECKS} checks");
+    }
+
+    let test_name = format!("ice-{}", krate.name);
+    let test_dir = ice_dir.join(&test_name);
+    let test_path = test_dir.join(format!("{test_name}.rs"));
+    let edition = crate_edition(&crate_dir);
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+    fs::write(&test_path, test_file(krate, &checker, &crate_dir, &edition, &message)).unwrap();
+
+    if reproduces(&checker, clippy_driver_path, &test_path, &edition) {
+        println!(
+            "{} reproduces the ICE, copy {} to tests/ui/crashes",
+            test_path.display(),
+            test_dir.display()
+        );
+    } else {
+        println!(
+            "{} doesn't reproduce the ICE on its own, it may need the dependencies of the crate. \
+             The minimized crate is at {}",
+            test_path.display(),
+            crate_dir.display()
+        );
+    }
+}
+
+/// Removes the items of the file at `path` that aren't needed to make Clippy panic, going from the
+/// items of the file to the ones of the modules, `impl`s and traits it contains
+fn minimize_file(checker: &mut Checker<'_>, crate_dir: &Path, path: &Path) {
+    let Ok(mut content) = fs::read_to_string(path) else {
+        return;
+    };
+
+    let mut group = 0;
+    while let Some(items) = group_items(&content, group) {
+        // try removing all the items, then half of them, a quarter, ... then each item on its own
+        let mut chunk_size = items.len();
+        while chunk_size > 0 {
+            let mut start = 0;
+            while let Some(items) = group_items(&content, group).filter(|items| start < items.len()) {
+                if checker.checks > MAX_CHECKS {
+                    fs::write(path, content).unwrap();
+                    return;
+                }
+                let end = (start + chunk_size).min(items.len());
+                let mut reduced = content.clone();
+                reduced.replace_range(items[start].start..items[end - 1].end, "");
+                fs::write(path, &reduced).unwrap();
+                if checker.still_panics(crate_dir) {
+                    content = reduced;
+                } else {
+                    start = end;
+                }
+            }
+            chunk_size /= 2;
+        }
+        group += 1;
+    }
+    fs::write(path, content).unwrap();
+}
+
+/// The byte ranges of the items of the `group`th item list of `content`. The lists are the items of
+/// the file, then the items of each module, `impl` and trait in the order they appear, so removing
+/// items only changes the lists coming after theirs.
+fn group_items(content: &str, group: usize) -> Option<Vec<Range<usize>>> {
+    fn push_groups(content: &str, items: &[Item], groups: &mut Vec<Vec<Range<usize>>>) {
+        groups.push(items.iter().map(|item| byte_range(content, item.span())).collect());
+        for item in items {
+            match item {
+                Item::Mod(module) => {
+                    if let Some((_, items)) = &module.content {
+                        push_groups(content, items, groups);
+                    }
+                },
+                Item::Impl(imp) => {
+                    groups.push(imp.items.iter().map(|item| byte_range(content, item.span())).collect());
+                },
+                Item::Trait(trait_) => {
+                    groups.push(
+                        trait_
+                            .items
+                            .iter()
+                            .map(|item| byte_range(content, item.span()))
+                            .collect(),
+                    );
+                },
+                _ => {},
+            }
+        }
+    }
+
+    let file = syn::parse_file(content).ok()?;
+    let mut groups = Vec::new();
+    push_groups(content, &file.items, &mut groups);
+    groups.into_iter().nth(group)
+}
+
+fn byte_range(content: &str, span: Span) -> Range<usize> {
+    byte_offset(content, span.start())..byte_offset(content, span.end())
+}
+
+fn byte_offset(content: &str, position: LineColumn) -> usize {
+    let line_start: usize = content
+        .split_inclusive('\n')
+        .take(position.line - 1)
+        .map(str::len)
+        .sum();
+    let column: usize = content[line_start..]
+        .chars()
+        .take(position.column)
+        .map(char::len_utf8)
+        .sum();
+    line_start + column
+}
+
+/// Returns the content of the file at `path`, with the `mod name;` declarations replaced by inline
+/// modules holding the content of their file, found in `module_dir`
+fn flatten_modules(path: &Path, module_dir: &Path) -> String {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let Ok(file) = syn::parse_file(&content) else {
+        return content;
+    };
+
+    let mut flattened = String::new();
+    let mut copied = 0;
+    for item in &file.items {
+        let Item::Mod(module) = item else {
+            continue;
+        };
+        // modules with a `#[path]` are left as they are
+        if module.content.is_some() || module.attrs.iter().any(|attr| attr.path.is_ident("path")) {
+            continue;
+        }
+        let name = module.ident.unraw().to_string();
+        let Some(module_path) = [
+            module_dir.join(format!("{name}.rs")),
+            module_dir.join(&name).join("mod.rs"),
+        ]
+        .into_iter()
+        .find(|path| path.is_file()) else {
+            continue;
+        };
+
+        let range = byte_range(&content, module.span());
+        let declaration = &content[range.clone()];
+        flattened.push_str(&content[copied..range.start]);
+        flattened.push_str(declaration.strip_suffix(';').unwrap_or(declaration).trim_end());
+        flattened.push_str(" {\n");
+        flattened.push_str(&flatten_modules(&module_path, &module_dir.join(&name)));
+        flattened.push('}');
+        copied = range.end;
+    }
+    flattened.push_str(&content[copied..]);
+    flattened
+}
+
+fn crate_edition(crate_dir: &Path) -> String {
+    fs::read_to_string(crate_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        .and_then(|manifest| Some(manifest.get("package")?.get("edition")?.as_str()?.to_string()))
+        .unwrap_or_else(|| String::from("2015"))
+}
+
+/// The flags of `clippy-driver` that are needed to reproduce the ICE
+fn test_flags<'a>(checker: &'a Checker<'a>) -> impl Iterator<Item = &'a str> {
+    checker
+        .clippy_args
+        .iter()
+        .copied()
+        .filter(|arg| !arg.starts_with("--cap-lints"))
+}
+
+/// The minimized crate flattened into a UI test
+fn test_file(krate: &Crate, checker: &Checker<'_>, crate_dir: &Path, edition: &str, message: &str) -> String {
+    let src_dir = crate_dir.join("src");
+    let lib_path = src_dir.join("lib.rs");
+    let is_lib = lib_path.is_file();
+    let root_path = if is_lib { lib_path } else { src_dir.join("main.rs") };
+
+    let mut test = format!("// edition:{edition}\n");
+    let flags: Vec<&str> = test_flags(checker).collect();
+    if !flags.is_empty() {
+        let _ = writeln!(test, "// compile-flags: {}", flags.join(" "));
+    }
+    let _ = write!(
+        test,
+        "\n// ICE found by lintcheck in {} {}: {message}\n\n",
+        krate.name, krate.version
+    );
+    test.push_str(flatten_modules(&root_path, &src_dir).trim());
+    test.push('\n');
+    if is_lib {
+        test.push_str("\nfn main() {}\n");
+    }
+    test
+}
+
+/// Whether Clippy panics at the same place when checking the test at `test_path` on its own
+fn reproduces(checker: &Checker<'_>, clippy_driver_path: &Path, test_path: &Path, edition: &str) -> bool {
+    let out_dir = test_path.parent().unwrap().parent().unwrap().join("out");
+    let output = Command::new(clippy_driver_path)
+        .arg(format!("--edition={edition}"))
+        .arg("--emit=metadata")
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .args(test_flags(checker))
+        .arg(test_path)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .expect("failed to run clippy-driver");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    ice_message(&stderr).map_or(false, |message| ice_location(&message) == checker.location)
+}
@@ -8,8 +8,12 @@
 #![allow(clippy::collapsible_else_if)]
 
 mod config;
+mod diff;
 mod driver;
+mod fixes;
+mod ice;
 mod recursive;
+mod timings;
 
 use crate::config::LintcheckConfig;
 use crate::recursive::LintcheckServer;
@@ -80,6 +84,13 @@ enum CrateSource {
         path: PathBuf,
         options: Option<Vec<String>>,
     },
+    /// A crate in the directory created by `cargo vendor`, for `--offline`
+    Vendored {
+        name: String,
+        version: String,
+        path: PathBuf,
+        options: Option<Vec<String>>,
+    },
 }
 
 /// Represents the actual source code of a crate that we ran "cargo clippy" on
@@ -93,7 +104,7 @@ struct Crate {
 }
 
 /// A single warning that clippy issued while checking a `Crate`
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct ClippyWarning {
     crate_name: String,
     file: String,
@@ -101,6 +112,8 @@ struct ClippyWarning {
     column: usize,
     lint_type: String,
     message: String,
+    /// The warning as printed by rustc, with the code snippet
+    rendered: String,
     is_ice: bool,
 }
 
@@ -114,6 +127,7 @@ impl ClippyWarning {
             return None;
         }
 
+        let rendered = diag.rendered.unwrap_or_default();
         let span = diag.spans.into_iter().find(|span| span.is_primary)?;
 
         let file = if let Ok(stripped) = Path::new(&span.file_name).strip_prefix(env!("CARGO_HOME")) {
@@ -132,10 +146,25 @@ impl ClippyWarning {
             column: span.column_start,
             lint_type,
             message: diag.message,
+            rendered,
             is_ice: diag.level == DiagnosticLevel::Ice,
         })
     }
 
+    /// The ICE described by `message` that happened while checking the crate at `crate_path`
+    fn ice(message: String, crate_name: &str, crate_path: &Path) -> Self {
+        Self {
+            crate_name: crate_name.to_owned(),
+            file: crate_path.display().to_string(),
+            line: 0,
+            column: 0,
+            lint_type: String::from("ICE"),
+            message,
+            rendered: String::new(),
+            is_ice: true,
+        }
+    }
+
     fn to_output(&self, markdown: bool) -> String {
         let file_with_pos = format!("{}:{}:{}", &self.file, &self.line, &self.column);
         if markdown {
@@ -236,6 +265,26 @@ impl CrateSource {
                     {
                         eprintln!("Failed to clone {url} into {}", repo_path.display());
                     }
+                } else if !Command::new("git")
+                    .args(["cat-file", "-e", &format!("{commit}^{{commit}}")])
+                    .current_dir(&repo_path)
+                    .status()
+                    .expect("Failed to look up commit")
+                    .success()
+                {
+                    // the commit may have been added to the repo since we cloned it
+                    println!("Fetching {url}");
+                    if !Command::new("git")
+                        .args(["fetch", "--tags"])
+                        .arg(url)
+                        .arg("+refs/heads/*:refs/remotes/origin/*")
+                        .current_dir(&repo_path)
+                        .status()
+                        .expect("Failed to fetch git repo!")
+                        .success()
+                    {
+                        eprintln!("Failed to fetch {url} into {}", repo_path.display());
+                    }
                 }
                 // check out the commit/branch/whatever
                 if !Command::new("git")
@@ -258,39 +307,29 @@ impl CrateSource {
                 }
             },
             CrateSource::Path { name, path, options } => {
-                fn is_cache_dir(entry: &DirEntry) -> bool {
-                    std::fs::read(entry.path().join("CACHEDIR.TAG"))
-                        .map(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
-                        .unwrap_or(false)
-                }
-
-                // copy path into the dest_crate_root but skip directories that contain a CACHEDIR.TAG file.
-                // The target/ directory contains a CACHEDIR.TAG file so it is the most commonly skipped directory
-                // as a result of this filter.
                 let dest_crate_root = PathBuf::from(LINTCHECK_SOURCES).join(name);
-                if dest_crate_root.exists() {
-                    println!("Deleting existing directory at {dest_crate_root:?}");
-                    std::fs::remove_dir_all(&dest_crate_root).unwrap();
-                }
-
-                println!("Copying {path:?} to {dest_crate_root:?}");
-
-                for entry in WalkDir::new(path).into_iter().filter_entry(|e| !is_cache_dir(e)) {
-                    let entry = entry.unwrap();
-                    let entry_path = entry.path();
-                    let relative_entry_path = entry_path.strip_prefix(path).unwrap();
-                    let dest_path = dest_crate_root.join(relative_entry_path);
-                    let metadata = entry_path.symlink_metadata().unwrap();
+                copy_crate(path, &dest_crate_root);
 
-                    if metadata.is_dir() {
-                        std::fs::create_dir(dest_path).unwrap();
-                    } else if metadata.is_file() {
-                        std::fs::copy(entry_path, dest_path).unwrap();
-                    }
+                Crate {
+                    version: String::from("local"),
+                    name: name.clone(),
+                    path: dest_crate_root,
+                    options: options.clone(),
                 }
+            },
+            CrateSource::Vendored {
+                name,
+                version,
+                path,
+                options,
+            } => {
+                std::fs::create_dir_all(LINTCHECK_SOURCES).unwrap();
+                let dest_crate_root = PathBuf::from(LINTCHECK_SOURCES).join(format!("{name}-{version}"));
+                copy_crate(path, &dest_crate_root);
+                remove_dev_dependencies(&dest_crate_root.join("Cargo.toml"));
 
                 Crate {
-                    version: String::from("local"),
+                    version: version.clone(),
                     name: name.clone(),
                     path: dest_crate_root,
                     options: options.clone(),
@@ -300,7 +339,153 @@ impl CrateSource {
     }
 }
 
+/// Copies the crate at `path` to `dest_crate_root`, replacing it if it exists
+fn copy_crate(path: &Path, dest_crate_root: &Path) {
+    fn is_cache_dir(entry: &DirEntry) -> bool {
+        std::fs::read(entry.path().join("CACHEDIR.TAG"))
+            .map(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
+            .unwrap_or(false)
+    }
+
+    // copy path into the dest_crate_root but skip directories that contain a CACHEDIR.TAG file.
+    // The target/ directory contains a CACHEDIR.TAG file so it is the most commonly skipped directory
+    // as a result of this filter.
+    if dest_crate_root.exists() {
+        println!("Deleting existing directory at {dest_crate_root:?}");
+        std::fs::remove_dir_all(dest_crate_root).unwrap();
+    }
+
+    println!("Copying {path:?} to {dest_crate_root:?}");
+
+    for entry in WalkDir::new(path).into_iter().filter_entry(|e| !is_cache_dir(e)) {
+        let entry = entry.unwrap();
+        let entry_path = entry.path();
+        let relative_entry_path = entry_path.strip_prefix(path).unwrap();
+        let dest_path = dest_crate_root.join(relative_entry_path);
+        let metadata = entry_path.symlink_metadata().unwrap();
+
+        if metadata.is_dir() {
+            std::fs::create_dir(dest_path).unwrap();
+        } else if metadata.is_file() {
+            std::fs::copy(entry_path, dest_path).unwrap();
+        }
+    }
+}
+
+/// Removes the dev-dependencies from the manifest of a vendored crate. `cargo vendor` doesn't
+/// vendor the dev-dependencies of dependencies, but Cargo needs them to generate a lockfile.
+fn remove_dev_dependencies(manifest_path: &Path) {
+    let content =
+        std::fs::read_to_string(manifest_path).unwrap_or_else(|_| panic!("Failed to read {}", manifest_path.display()));
+    let mut manifest: toml::Value =
+        toml::from_str(&content).unwrap_or_else(|e| panic!("Failed to parse {}: \n{e}", manifest_path.display()));
+    let manifest_table = manifest.as_table_mut().unwrap();
+    manifest_table.remove("dev-dependencies");
+    if let Some(toml::Value::Table(targets)) = manifest_table.get_mut("target") {
+        for target in targets.values_mut().filter_map(toml::Value::as_table_mut) {
+            target.remove("dev-dependencies");
+        }
+    }
+    std::fs::write(manifest_path, toml::to_string(&manifest).unwrap()).unwrap();
+}
+
+/// Reads the crates vendored in `vendor_dir` by `cargo vendor`
+fn read_vendored_crates(vendor_dir: &Path) -> Vec<CrateSource> {
+    let entries = std::fs::read_dir(vendor_dir).unwrap_or_else(|_| panic!("Failed to read {}", vendor_dir.display()));
+    let mut crate_sources: Vec<CrateSource> = entries
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            let content = std::fs::read_to_string(path.join("Cargo.toml")).ok()?;
+            let manifest: toml::Value = toml::from_str(&content).ok()?;
+            let package = manifest.get("package")?;
+            Some(CrateSource::Vendored {
+                name: package.get("name")?.as_str()?.to_string(),
+                version: package.get("version")?.as_str()?.to_string(),
+                path,
+                options: None,
+            })
+        })
+        .collect();
+    crate_sources.sort();
+    crate_sources
+}
+
+/// Replaces a crates.io source with the crate vendored in `--vendor-dir`, and makes sure that a git
+/// source is a local repository, for `--offline`
+fn offline_source(source: CrateSource, vendored: &[CrateSource]) -> CrateSource {
+    match source {
+        CrateSource::CratesIo { name, version, options } => {
+            let vendored_path = vendored.iter().find_map(|vendored| match vendored {
+                CrateSource::Vendored {
+                    name: vendored_name,
+                    version: vendored_version,
+                    path,
+                    ..
+                } if *vendored_name == name && *vendored_version == version => Some(path.clone()),
+                _ => None,
+            });
+            let Some(path) = vendored_path else {
+                eprintln!("ERROR: {name} {version} can't be downloaded offline, vendor it and pass --vendor-dir");
+                std::process::exit(1);
+            };
+            CrateSource::Vendored {
+                name,
+                version,
+                path,
+                options,
+            }
+        },
+        CrateSource::Git { ref name, ref url, .. } if !is_local_repo(url) => {
+            eprintln!("ERROR: {name} can't be cloned offline from {url}, use a local repository");
+            std::process::exit(1);
+        },
+        source => source,
+    }
+}
+
+fn is_local_repo(url: &str) -> bool {
+    url.starts_with("file://") || Path::new(url).exists()
+}
+
+/// The arguments making Cargo work offline, taking the dependencies from `--vendor-dir`
+fn offline_cargo_args(config: &LintcheckConfig) -> Vec<String> {
+    let mut args = Vec::new();
+    if config.offline {
+        args.push("--offline".to_string());
+    }
+    if let Some(vendor_dir) = &config.vendor_dir {
+        let directory = toml::Value::String(vendor_dir.display().to_string());
+        args.extend([
+            "--config".to_string(),
+            "source.crates-io.replace-with=\"lintcheck-vendored\"".to_string(),
+            "--config".to_string(),
+            format!("source.lintcheck-vendored.directory={directory}"),
+        ]);
+    }
+    args
+}
+
 impl Crate {
+    /// The arguments passed to `clippy-driver` when checking the `Crate`
+    fn clippy_args<'a>(&'a self, lint_filter: &'a [String]) -> Vec<&'a str> {
+        let mut clippy_args = Vec::<&str>::new();
+        if let Some(options) = &self.options {
+            for opt in options {
+                clippy_args.push(opt);
+            }
+        } else {
+            clippy_args.extend(["-Wclippy::pedantic", "-Wclippy::cargo"]);
+        }
+
+        if lint_filter.is_empty() {
+            clippy_args.push("--cap-lints=warn");
+        } else {
+            clippy_args.push("--cap-lints=allow");
+            clippy_args.extend(lint_filter.iter().map(std::string::String::as_str));
+        }
+        clippy_args
+    }
+
     /// Run `cargo clippy` on the `Crate` and collect and return all the lint warnings that clippy
     /// issued
     #[allow(clippy::too_many_arguments)]
@@ -336,26 +521,40 @@ impl Crate {
 
         let shared_target_dir = clippy_project_root().join("target/lintcheck/shared_target_dir");
 
+        let cargo_args = offline_cargo_args(config);
         let mut cargo_clippy_args = if config.fix {
-            vec!["--fix", "--"]
+            vec!["--fix"]
         } else {
-            vec!["--", "--message-format=json", "--"]
+            vec!["--", "--message-format=json"]
         };
+        cargo_clippy_args.extend(cargo_args.iter().map(String::as_str));
+        cargo_clippy_args.push("--");
 
-        let mut clippy_args = Vec::<&str>::new();
-        if let Some(options) = &self.options {
-            for opt in options {
-                clippy_args.push(opt);
-            }
-        } else {
-            clippy_args.extend(["-Wclippy::pedantic", "-Wclippy::cargo"]);
-        }
+        let mut clippy_args = self.clippy_args(lint_filter);
 
-        if lint_filter.is_empty() {
-            clippy_args.push("--cap-lints=warn");
-        } else {
-            clippy_args.push("--cap-lints=allow");
-            clippy_args.extend(lint_filter.iter().map(std::string::String::as_str));
+        let lint_timings_arg = format!("--lint-timings={}", timings::driver_output_path().display());
+        if config.lint_timings_path.is_some() {
+            clippy_args.push(&lint_timings_arg);
+        }
+        let target_dir = shared_target_dir.join(format!("_{thread_index:?}"));
+        if config.lint_timings_path.is_some() && server.is_none() {
+            // Cargo doesn't run Clippy again on a crate checked by a previous run, its dependencies
+            // aren't linted so they can stay
+            let status = Command::new("cargo")
+                .arg("clean")
+                .arg("--quiet")
+                .args(["-p", &self.name])
+                .args(&cargo_args)
+                .current_dir(&self.path)
+                .env("CARGO_TARGET_DIR", &target_dir)
+                .status()
+                .expect("failed to run cargo");
+            if !status.success() {
+                eprintln!(
+                    "\nWARNING: failed to clean {} {}, it may not be timed\n",
+                    self.name, self.version
+                );
+            }
         }
 
         if let Some(server) = server {
@@ -372,6 +571,7 @@ impl Crate {
             let status = Command::new("cargo")
                 .arg("check")
                 .arg("--quiet")
+                .args(&cargo_args)
                 .current_dir(&self.path)
                 .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
                 .env("CARGO_TARGET_DIR", target)
@@ -392,7 +592,7 @@ impl Crate {
 
         let all_output = Command::new(&cargo_clippy_path)
             // use the looping index to create individual target dirs
-            .env("CARGO_TARGET_DIR", shared_target_dir.join(format!("_{thread_index:?}")))
+            .env("CARGO_TARGET_DIR", &target_dir)
             .args(&cargo_clippy_args)
             .current_dir(&self.path)
             .output()
@@ -430,21 +630,30 @@ impl Crate {
         }
 
         // get all clippy warnings and ICEs
-        let warnings: Vec<ClippyWarning> = Message::parse_stream(stdout.as_bytes())
+        let mut warnings: Vec<ClippyWarning> = Message::parse_stream(stdout.as_bytes())
             .filter_map(|msg| match msg {
                 Ok(Message::CompilerMessage(message)) => ClippyWarning::new(message.message, &self.name, &self.version),
                 _ => None,
             })
             .collect();
 
+        // panics are printed to stderr rather than as JSON, while the compiler bugs reported as
+        // JSON are printed to stderr too
+        if !warnings.iter().any(|warning| warning.is_ice) {
+            if let Some(message) = ice::ice_message(&stderr) {
+                warnings.push(ClippyWarning::ice(message, &self.name, &self.path));
+            }
+        }
+
         warnings
     }
 }
 
 /// Builds clippy inside the repo to make sure we have a clippy executable we can use.
-fn build_clippy() {
+fn build_clippy(offline: bool) {
     let status = Command::new("cargo")
         .arg("build")
+        .args(offline.then_some("--offline"))
         .status()
         .expect("Failed to build clippy!");
     if !status.success() {
@@ -454,7 +663,7 @@ fn build_clippy() {
 }
 
 /// Read a `lintcheck_crates.toml` file
-fn read_crates(toml_path: &Path) -> (Vec<CrateSource>, RecursiveOptions) {
+fn read_crates(toml_path: &Path, config: &LintcheckConfig) -> (Vec<CrateSource>, RecursiveOptions) {
     let toml_content: String =
         std::fs::read_to_string(toml_path).unwrap_or_else(|_| panic!("Failed to read {}", toml_path.display()));
     let crate_list: SourceList =
@@ -510,6 +719,18 @@ fn read_crates(toml_path: &Path) -> (Vec<CrateSource>, RecursiveOptions) {
             unreachable!("Failed to translate TomlCrate into CrateSource!");
         }
     }
+    if config.offline {
+        let vendored = config
+            .vendor_dir
+            .as_deref()
+            .map(read_vendored_crates)
+            .unwrap_or_default();
+        crate_sources = crate_sources
+            .into_iter()
+            .map(|source| offline_source(source, &vendored))
+            .collect();
+    }
+
     // sort the crates
     crate_sources.sort();
 
@@ -558,8 +779,13 @@ fn main() {
 
     let config = LintcheckConfig::new();
 
+    if let Some((old_path, new_path)) = &config.diff {
+        diff::print_diff(old_path, new_path, config.markdown);
+        return;
+    }
+
     println!("Compiling clippy...");
-    build_clippy();
+    build_clippy(config.offline);
     println!("Done compiling");
 
     let cargo_clippy_path = fs::canonicalize(format!("target/debug/cargo-clippy{EXE_SUFFIX}")).unwrap();
@@ -581,7 +807,11 @@ fn main() {
     // download and extract the crates, then run clippy on them and collect clippy's warnings
     // flatten into one big list of warnings
 
-    let (crates, recursive_options) = read_crates(&config.sources_toml_path);
+    let (crates, recursive_options) = match (&config.sources_toml_path, &config.vendor_dir) {
+        (Some(toml_path), _) => read_crates(toml_path, &config),
+        (None, Some(vendor_dir)) => (read_vendored_crates(vendor_dir), RecursiveOptions::default()),
+        (None, None) => unreachable!("no crates to check"),
+    };
     let old_stats = read_stats_from_file(&config.lintcheck_results_path);
 
     let counter = AtomicUsize::new(1);
@@ -602,7 +832,8 @@ fn main() {
                 let name = match krate {
                     CrateSource::CratesIo { name, .. }
                     | CrateSource::Git { name, .. }
-                    | CrateSource::Path { name, .. } => name,
+                    | CrateSource::Path { name, .. }
+                    | CrateSource::Vendored { name, .. } => name,
                 };
 
                 name == only_one_crate
@@ -631,6 +862,10 @@ fn main() {
         .build_global()
         .unwrap();
 
+    if config.lint_timings_path.is_some() {
+        let _ = fs::remove_file(timings::driver_output_path());
+    }
+
     let server = config.recursive.then(|| {
         let _ = fs::remove_dir_all("target/lintcheck/shared_target_dir/recursive");
 
@@ -655,6 +890,15 @@ fn main() {
     if let Some(server) = server {
         clippy_warnings.extend(server.warnings());
     }
+    clippy_warnings.sort_by(|a, b| {
+        (&a.crate_name, &a.file, a.line, a.column, &a.lint_type).cmp(&(
+            &b.crate_name,
+            &b.file,
+            b.line,
+            b.column,
+            &b.lint_type,
+        ))
+    });
 
     // if we are in --fix mode, don't change the log files, terminate here
     if config.fix {
@@ -689,14 +933,53 @@ fn main() {
     write!(text, "{}", all_msgs.join("")).unwrap();
     text.push_str("\n\n### ICEs:\n");
     for (cratename, msg) in &ices {
-        let _ = write!(text, "{cratename}: '{msg}'");
+        let _ = writeln!(text, "{cratename}: '{msg}'");
     }
 
     println!("Writing logs to {}", config.lintcheck_results_path.display());
     fs::create_dir_all(config.lintcheck_results_path.parent().unwrap()).unwrap();
     fs::write(&config.lintcheck_results_path, text).unwrap();
 
+    // save the warnings as JSON too, to compare them with another run using `--diff`
+    let json_path = config.lintcheck_results_path.with_extension("json");
+    println!("Writing warnings to {}", json_path.display());
+    fs::write(&json_path, serde_json::to_string_pretty(&clippy_warnings).unwrap()).unwrap();
+
     print_stats(old_stats, new_stats, &config.lint_filter);
+
+    // each crate is checked up to `ice::MAX_CHECKS` times, so only on demand
+    if config.minimize_ices {
+        for krate in crates
+            .iter()
+            .filter(|krate| ices.iter().any(|(name, _)| **name == krate.name))
+        {
+            ice::reproduce(krate, &cargo_clippy_path, &clippy_driver_path, &config, &lint_filter);
+        }
+    } else if !ices.is_empty() && !config.recursive {
+        println!("\nRun lintcheck again with `--minimize-ices` to reduce the crates with ICEs to a reproduction");
+    }
+
+    if let Some(path) = &config.lint_timings_path {
+        timings::report(path);
+    }
+
+    if let Some(path) = &config.verify_fixes_path {
+        let broken_fixes: Vec<fixes::BrokenFix> = crates
+            .par_iter()
+            .flat_map(|krate| {
+                // only the lints that warned in the crate can have suggestions for it
+                let mut lints: Vec<&str> = clippy_warnings
+                    .iter()
+                    .filter(|warning| warning.crate_name == krate.name && warning.lint_type.starts_with("clippy::"))
+                    .map(|warning| warning.lint_type.as_str())
+                    .collect();
+                lints.sort_unstable();
+                lints.dedup();
+                fixes::verify_fixes(krate, &lints, &cargo_clippy_path, &config, &lint_filter)
+            })
+            .collect();
+        fixes::report(&broken_fixes, path);
+    }
 }
 
 /// read the previous stats from the lintcheck-log file
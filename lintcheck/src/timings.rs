@@ -0,0 +1,96 @@
+//! In `--lint-timings` mode every `clippy-driver` appends the time spent in each lint pass while
+//! checking its crate to [`driver_output_path`]. The times are summed over the checked crates and
+//! compared with the ones of the previous run, to catch lints that became slower.
+
+use crate::clippy_project_root;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// The number of passes listed as the slowest ones
+const SHOWN_PASSES: usize = 20;
+
+/// Changes smaller than this are noise
+const MIN_CHANGE: Duration = Duration::from_millis(10);
+
+/// A line written by `clippy-driver --lint-timings=PATH` for a checked crate
+#[derive(Deserialize)]
+struct CrateTimings {
+    passes: Vec<PassTiming>,
+}
+
+#[derive(Deserialize)]
+struct PassTiming {
+    pass: String,
+    kind: String,
+    nanos: u64,
+}
+
+/// The file the drivers append the timings of their crate to
+pub(crate) fn driver_output_path() -> PathBuf {
+    clippy_project_root().join("target/lintcheck/lint_timings.jsonl")
+}
+
+/// Prints the slowest lint passes and the ones that got slower than in the previous run, then
+/// saves the time spent in each pass to `results_path`
+pub(crate) fn report(results_path: &Path) {
+    let Ok(driver_output) = fs::read_to_string(driver_output_path()) else {
+        println!("\nNo lint timings were recorded");
+        return;
+    };
+
+    // total nanoseconds of each pass, keyed by `Pass (kind)`
+    let mut totals: BTreeMap<String, u64> = BTreeMap::new();
+    for line in driver_output.lines() {
+        let timings: CrateTimings =
+            serde_json::from_str(line).unwrap_or_else(|e| panic!("Failed to parse lint timings `{line}`: {e}"));
+        for timing in timings.passes {
+            *totals.entry(format!("{} ({})", timing.pass, timing.kind)).or_default() += timing.nanos;
+        }
+    }
+
+    let old_totals: BTreeMap<String, u64> = fs::read_to_string(results_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let mut passes: Vec<(&String, u64)> = totals.iter().map(|(pass, &nanos)| (pass, nanos)).collect();
+    passes.sort_by(|a, b| b.1.cmp(&a.1));
+
+    println!("\nSlowest lint passes:");
+    for &(pass, nanos) in passes.iter().take(SHOWN_PASSES) {
+        match old_totals.get(pass) {
+            Some(&old_nanos) => println!("{pass} {} => {}", format_nanos(old_nanos), format_nanos(nanos)),
+            None => println!("{pass} {}", format_nanos(nanos)),
+        }
+    }
+
+    let slower: Vec<(&String, u64, u64)> = passes
+        .iter()
+        .filter_map(|&(pass, nanos)| {
+            let old_nanos = *old_totals.get(pass)?;
+            is_slower(old_nanos, nanos).then_some((pass, old_nanos, nanos))
+        })
+        .collect();
+    if !slower.is_empty() {
+        println!("\nLint passes that got more than 20% slower:");
+        for (pass, old_nanos, nanos) in slower {
+            println!("{pass} {} => {}", format_nanos(old_nanos), format_nanos(nanos));
+        }
+    }
+
+    println!("Writing lint timings to {}", results_path.display());
+    fs::write(results_path, serde_json::to_string_pretty(&totals).unwrap()).unwrap();
+}
+
+fn is_slower(old_nanos: u64, nanos: u64) -> bool {
+    Duration::from_nanos(nanos.saturating_sub(old_nanos)) > MIN_CHANGE && nanos * 5 > old_nanos * 6
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.1}ms", Duration::from_nanos(nanos).as_secs_f64() * 1000.0)
+}
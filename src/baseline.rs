@@ -0,0 +1,146 @@
+//! Reading and writing the baseline files of `--baseline` and `--write-baseline`, see
+//! [`clippy_utils::baseline`].
+
+use clippy_utils::baseline::{record_warnings, recorded_warnings, set_known_warnings, BaselineKey};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_session::Session;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The version of the format of baseline files, increased when the fingerprints change.
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Default, Deserialize, Serialize)]
+struct BaselineFile {
+    version: u32,
+    /// The warnings of each checked crate, keyed by the name returned by `baseline_target`.
+    targets: BTreeMap<String, Vec<BaselineEntry>>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct BaselineEntry {
+    lint: String,
+    file: String,
+    fingerprint: String,
+    count: usize,
+}
+
+/// Reads the baseline at `path` and suppresses the warnings it contains.
+///
+/// A warning recorded for several targets, e.g. for a library and for its unit tests, is
+/// suppressed as many times as it was emitted for any single one of them.
+pub fn read_baseline(sess: &Session, path: &Path) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let baseline: BaselineFile = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    if baseline.version != BASELINE_VERSION {
+        return Err(format!(
+            "unsupported baseline version {}, write a new baseline with `--write-baseline`",
+            baseline.version
+        ));
+    }
+
+    let mut known = FxHashMap::default();
+    for entry in baseline.targets.into_values().flatten() {
+        let key = BaselineKey {
+            file: entry.file,
+            lint: entry.lint,
+            fingerprint: entry.fingerprint,
+        };
+        let count = known.entry(key).or_default();
+        *count = entry.count.max(*count);
+    }
+    set_known_warnings(sess, known);
+    Ok(())
+}
+
+/// Starts recording the emitted warnings, to be written with [`write_baseline`].
+pub fn start_baseline(sess: &Session) {
+    record_warnings(sess);
+}
+
+/// Writes the warnings recorded for `target` to the baseline at `path`, replacing the ones
+/// previously recorded for it and keeping the ones of other targets.
+pub fn write_baseline(path: &Path, target: &str) -> io::Result<()> {
+    let entries = recorded_warnings()
+        .into_iter()
+        .map(|(key, count)| BaselineEntry {
+            lint: key.lint,
+            file: key.file,
+            fingerprint: key.fingerprint,
+            count,
+        })
+        .collect::<Vec<_>>();
+
+    let _lock = BaselineLock::acquire(path)?;
+    let mut baseline = match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<BaselineFile>(&content)
+            .ok()
+            .filter(|baseline| baseline.version == BASELINE_VERSION)
+            .unwrap_or_default(),
+        Err(e) if e.kind() == ErrorKind::NotFound => BaselineFile::default(),
+        Err(e) => return Err(e),
+    };
+    baseline.version = BASELINE_VERSION;
+    if entries.is_empty() {
+        baseline.targets.remove(target);
+    } else {
+        baseline.targets.insert(target.to_string(), entries);
+    }
+
+    let mut content = serde_json::to_string_pretty(&baseline).map_err(io::Error::from)?;
+    content.push('\n');
+    fs::write(path, content)
+}
+
+/// Returns the name under which the warnings of the crate being checked are stored, made of the
+/// package name, the crate name and the kind of the crate, e.g. `lib`, `bin` or `test`. The kind
+/// is needed since a library, a binary and the unit tests of a package are crates with the same
+/// name.
+pub fn baseline_target(package: Option<&str>, crate_name: &str, kind: &str) -> String {
+    match package {
+        Some(package) if package != crate_name => format!("{package}/{crate_name} ({kind})"),
+        _ => format!("{crate_name} ({kind})"),
+    }
+}
+
+/// A lock file held while updating a baseline, as Cargo runs several instances of
+/// `clippy-driver` at once.
+struct BaselineLock(PathBuf);
+
+impl BaselineLock {
+    /// How long to wait for the lock before assuming that it was left behind by a process that
+    /// was killed.
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    fn acquire(path: &Path) -> io::Result<Self> {
+        let mut lock = path.as_os_str().to_owned();
+        lock.push(".lock");
+        let lock = PathBuf::from(lock);
+
+        let start = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&lock) {
+                Ok(_) => return Ok(Self(lock)),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if start.elapsed() > Self::TIMEOUT {
+                        let _ = fs::remove_file(&lock);
+                    } else {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for BaselineLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
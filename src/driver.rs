@@ -2,6 +2,7 @@
 #![feature(let_chains)]
 #![feature(once_cell)]
 #![feature(lint_reasons)]
+#![feature(min_specialization)]
 #![cfg_attr(feature = "deny-warnings", deny(warnings))]
 // warn on lints, that are included in `rust-lang/rust`s bootstrap
 #![warn(rust_2018_idioms, unused_lifetimes)]
@@ -10,13 +11,26 @@
 
 // FIXME: switch to something more ergonomic here, once available.
 // (Currently there is no way to opt into sysroot crates without `extern crate`.)
+extern crate rustc_ast;
+extern crate rustc_data_structures;
 extern crate rustc_driver;
 extern crate rustc_errors;
+extern crate rustc_hir;
 extern crate rustc_interface;
+extern crate rustc_lint;
+extern crate rustc_macros;
+extern crate rustc_middle;
+extern crate rustc_serialize;
 extern crate rustc_session;
 extern crate rustc_span;
 
-use rustc_interface::interface;
+mod baseline;
+mod lint_cache;
+mod lint_plugins;
+mod lint_timings;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::Symbol;
 
@@ -24,7 +38,7 @@ use std::borrow::Cow;
 use std::env;
 use std::ops::Deref;
 use std::panic;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::sync::LazyLock;
 
@@ -65,6 +79,36 @@ fn test_arg_value() {
     assert_eq!(arg_value(args, "--foo", |_| true), None);
 }
 
+/// Removes `find_arg` and its value from `args`, returning the value. The parameter is assumed to
+/// be either `--arg=value` or `--arg value`.
+fn take_arg_value(args: &mut Vec<String>, find_arg: &str) -> Option<String> {
+    let pos = args
+        .iter()
+        .position(|arg| arg.split_once('=').map_or(arg.as_str(), |(name, _)| name) == find_arg)?;
+    let arg = args.remove(pos);
+    match arg.split_once('=') {
+        Some((_, value)) => Some(value.to_string()),
+        None if pos < args.len() => Some(args.remove(pos)),
+        None => None,
+    }
+}
+
+#[test]
+fn test_take_arg_value() {
+    let mut args = ["--bar=bar", "--foobar", "123", "--foo"].map(String::from).to_vec();
+
+    assert_eq!(take_arg_value(&mut args, "--baz"), None);
+    assert_eq!(take_arg_value(&mut args, "--foobar"), Some("123".to_string()));
+    assert_eq!(args, ["--bar=bar", "--foo"]);
+    assert_eq!(take_arg_value(&mut args, "--bar"), Some("bar".to_string()));
+    assert_eq!(take_arg_value(&mut args, "--foo"), None);
+    assert!(args.is_empty());
+}
+
+fn is_lint_timings_arg(arg: &str) -> bool {
+    arg == "--lint-timings" || arg.starts_with("--lint-timings=")
+}
+
 fn track_clippy_args(parse_sess: &mut ParseSess, args_env_var: &Option<String>) {
     parse_sess.env_depinfo.get_mut().insert((
         Symbol::intern("CLIPPY_ARGS"),
@@ -74,7 +118,7 @@ fn track_clippy_args(parse_sess: &mut ParseSess, args_env_var: &Option<String>)
 
 /// Track files that may be accessed at runtime in `file_depinfo` so that cargo will re-run clippy
 /// when any of them are modified
-fn track_files(parse_sess: &mut ParseSess, conf_path_string: Option<String>) {
+fn track_files(parse_sess: &mut ParseSess, conf_path_strings: Vec<String>) {
     let file_depinfo = parse_sess.file_depinfo.get_mut();
 
     // Used by `clippy::cargo` lints and to determine the MSRV. `cargo clippy` executes `clippy-driver`
@@ -83,8 +127,8 @@ fn track_files(parse_sess: &mut ParseSess, conf_path_string: Option<String>) {
         file_depinfo.insert(Symbol::intern("Cargo.toml"));
     }
 
-    // `clippy.toml`
-    if let Some(path) = conf_path_string {
+    // `clippy.toml` and the configuration files it inherits from
+    for path in conf_path_strings {
         file_depinfo.insert(Symbol::intern(&path));
     }
 
@@ -103,6 +147,27 @@ fn track_files(parse_sess: &mut ParseSess, conf_path_string: Option<String>) {
     }
 }
 
+/// Returns the name under which the warnings of the crate compiled with `args` are stored in a
+/// baseline, also naming the crate in the output of `--lint-timings`.
+fn baseline_target(args: &[String]) -> String {
+    let crate_name = arg_value(args, "--crate-name", |_| true)
+        .map(str::to_string)
+        .or_else(|| {
+            // without `--crate-name`, rustc names the crate after the input file
+            let input = args.iter().find(|arg| arg.ends_with(".rs"))?;
+            let stem = Path::new(input).file_stem()?.to_str()?;
+            Some(stem.replace('-', "_"))
+        })
+        .unwrap_or_default();
+    let kind = if args.iter().any(|arg| arg == "--test") {
+        "test"
+    } else {
+        arg_value(args, "--crate-type", |_| true).unwrap_or("bin")
+    };
+    let package = env::var("CARGO_PKG_NAME").ok();
+    baseline::baseline_target(package.as_deref(), &crate_name, kind)
+}
+
 struct DefaultCallbacks;
 impl rustc_driver::Callbacks for DefaultCallbacks {}
 
@@ -123,6 +188,16 @@ impl rustc_driver::Callbacks for RustcCallbacks {
 
 struct ClippyCallbacks {
     clippy_args_var: Option<String>,
+    /// The baseline of warnings to suppress, passed with `--baseline`
+    baseline: Option<PathBuf>,
+    /// Whether to record the emitted warnings for `--write-baseline`
+    write_baseline: bool,
+    /// The cache of the results of the crate, with `--lint-cache`
+    lint_cache: Option<lint_cache::LintCache>,
+    /// The cached results to replay instead of checking the crate
+    cached_lints: Option<lint_cache::CachedLints>,
+    /// Whether to measure the time spent in each lint pass, for `--lint-timings`
+    lint_timings: bool,
 }
 
 impl rustc_driver::Callbacks for ClippyCallbacks {
@@ -130,17 +205,42 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
     #[allow(rustc::bad_opt_access)]
     fn config(&mut self, config: &mut interface::Config) {
         let conf_path = clippy_lints::lookup_conf_file();
-        let conf_path_string = if let Ok(Some(path)) = &conf_path {
-            path.to_str().map(String::from)
+        let mut conf_path_strings = if let Ok(Some(path)) = &conf_path {
+            clippy_lints::lookup_inherited_conf_files(path)
+                .iter()
+                .filter_map(|path| path.to_str().map(String::from))
+                .collect()
         } else {
-            None
+            Vec::new()
         };
+        // The warnings to report change with the baseline
+        if let Some(path) = self.baseline.as_ref().and_then(|path| path.to_str()) {
+            conf_path_strings.push(path.to_string());
+        }
+
+        // The configuration is read once, its errors are reported once the session exists
+        let conf = clippy_lints::read_conf(&conf_path);
+
+        // Lint levels from the `[lints]` table come before the ones passed on the command line, so
+        // that `-W`/`-A`/`-D` flags and lint attributes still take precedence over them.
+        config.opts.lint_opts.splice(0..0, conf.conf.lint_level_opts());
+
+        // Replay the results of the previous run if only lint levels changed since, otherwise
+        // record them for the next runs
+        let mut record_lints = false;
+        if let Some(lint_cache) = &self.lint_cache {
+            self.cached_lints = lint_cache.load(&config.opts);
+            record_lints = self.cached_lints.is_none() && lint_cache::LintCache::record(&config.opts);
+        }
 
         let previous = config.register_lints.take();
-        let clippy_args_var = self.clippy_args_var.take();
+        let clippy_args_var = self.clippy_args_var.clone();
+        let baseline = self.baseline.take();
+        let write_baseline = self.write_baseline;
+        let lint_timings = self.lint_timings;
         config.parse_sess_created = Some(Box::new(move |parse_sess| {
             track_clippy_args(parse_sess, &clippy_args_var);
-            track_files(parse_sess, conf_path_string);
+            track_files(parse_sess, conf_path_strings);
         }));
         config.register_lints = Some(Box::new(move |sess, lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
@@ -149,18 +249,67 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
                 (previous)(sess, lint_store);
             }
 
-            let conf = clippy_lints::read_conf(sess, &conf_path);
-            clippy_lints::register_plugins(lint_store, sess, &conf);
-            clippy_lints::register_pre_expansion_lints(lint_store, sess, &conf);
+            if record_lints {
+                lint_cache::LintCache::start_recording();
+            }
+
+            clippy_lints::report_conf_errors(sess, &conf_path, &conf);
+            clippy_lints::register_plugins(lint_store, sess, &conf.conf);
+            clippy_lints::register_pre_expansion_lints(lint_store, sess, &conf.conf);
             clippy_lints::register_renamed(lint_store);
+            let path = conf_path.as_ref().ok().and_then(Option::as_deref);
+            lint_plugins::register_lint_plugins(lint_store, sess, &conf.conf, path);
+            // Once all the lints are registered, including the ones of the plugins and the custom
+            // lints
+            clippy_lints::check_conf_lint_names(sess, lint_store, &conf_path, &conf);
+            if lint_timings {
+                lint_timings::time_lint_passes(lint_store);
+            }
+
+            if let Some(path) = &baseline
+                && let Err(e) = baseline::read_baseline(sess, path)
+            {
+                sess.err(format!("failed to read the baseline `{}`: {e}", path.display()));
+            }
+            if write_baseline {
+                baseline::start_baseline(sess);
+            }
+            // After the baseline, so that the warnings dropped by the `[[overrides]]` aren't
+            // recorded in it
+            clippy_lints::allow_early_lints_by_path(sess, lint_store);
         }));
 
+        // The `[[overrides]]` sections of the configuration set lint levels in some files
+        config.override_queries = Some(|_sess, providers, _extern_providers| {
+            clippy_lints::override_lint_levels(providers);
+        });
+
         // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
         // run on the unoptimized MIR. On the other hand this results in some false negatives. If
         // MIR passes can be enabled / disabled separately, we should figure out, what passes to
         // use for Clippy.
         config.opts.unstable_opts.mir_opt_level = Some(0);
     }
+
+    fn after_parsing<'tcx>(&mut self, compiler: &interface::Compiler, _: &'tcx Queries<'tcx>) -> Compilation {
+        if let Some(cached_lints) = self.cached_lints.take()
+            && let Some(lint_cache) = &self.lint_cache
+            && cached_lints.replay(compiler.session(), lint_cache, self.clippy_args_var.as_deref())
+        {
+            return Compilation::Stop;
+        }
+        Compilation::Continue
+    }
+
+    fn after_analysis<'tcx>(&mut self, _: &interface::Compiler, queries: &'tcx Queries<'tcx>) -> Compilation {
+        if let Some(lint_cache) = &mut self.lint_cache {
+            queries
+                .global_ctxt()
+                .unwrap()
+                .enter(|tcx| lint_cache.finish_recording(tcx));
+        }
+        Compilation::Continue
+    }
 }
 
 fn display_help() {
@@ -175,6 +324,12 @@ Common options:
     -h, --help               Print this message
         --rustc              Pass all args to rustc
     -V, --version            Print version info and exit
+    --write-baseline PATH    Record the emitted warnings in the baseline file at PATH
+    --baseline PATH          Suppress the warnings recorded in the baseline file at PATH
+    --lint-cache             Replay the results of the previous run when only the levels of
+                             Clippy lints changed since
+    --lint-timings[=PATH]    Print the time spent in each lint pass, or append it to the file at
+                             PATH as a line of JSON
 
 Other options are the same as `cargo check`.
 
@@ -300,10 +455,22 @@ pub fn main() {
             exit(0);
         }
 
+        // `--baseline` and `--write-baseline` can be passed directly to `clippy-driver`, or by
+        // `cargo clippy` in `CLIPPY_ARGS`
+        let mut baseline_arg = take_arg_value(&mut orig_args, "--baseline");
+        let mut write_baseline_arg = take_arg_value(&mut orig_args, "--write-baseline");
+
+        // `--lint-timings` as well
+        let mut lint_timings_arg = None;
+        if let Some(pos) = orig_args.iter().position(|arg| is_lint_timings_arg(arg)) {
+            lint_timings_arg = Some(orig_args.remove(pos));
+        }
+
         let mut args: Vec<String> = orig_args.clone();
         pass_sysroot_env_if_given(&mut args, sys_root_env);
 
         let mut no_deps = false;
+        let mut lint_cache = false;
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
         let clippy_args = clippy_args_var
             .as_deref()
@@ -315,6 +482,22 @@ pub fn main() {
                     no_deps = true;
                     None
                 },
+                "--lint-cache" => {
+                    lint_cache = true;
+                    None
+                },
+                _ if s.starts_with("--baseline=") => {
+                    baseline_arg = s.strip_prefix("--baseline=").map(String::from);
+                    None
+                },
+                _ if s.starts_with("--write-baseline=") => {
+                    write_baseline_arg = s.strip_prefix("--write-baseline=").map(String::from);
+                    None
+                },
+                _ if is_lint_timings_arg(s) => {
+                    lint_timings_arg = Some(s.to_string());
+                    None
+                },
                 _ => Some(s.to_string()),
             })
             .chain(vec!["--cfg".into(), r#"feature="cargo-clippy""#.into()])
@@ -332,7 +515,48 @@ pub fn main() {
         let clippy_enabled = !cap_lints_allow && (!no_deps || in_primary_package);
         if clippy_enabled {
             args.extend(clippy_args);
-            rustc_driver::RunCompiler::new(&args, &mut ClippyCallbacks { clippy_args_var }).run()
+            // The cache doesn't know about baselines
+            let lint_cache = if lint_cache && baseline_arg.is_none() && write_baseline_arg.is_none() {
+                lint_cache::LintCache::new(&args, &rustc_tools_util::get_version_info!().to_string())
+            } else {
+                None
+            };
+            let mut callbacks = ClippyCallbacks {
+                clippy_args_var,
+                baseline: baseline_arg.map(PathBuf::from),
+                write_baseline: write_baseline_arg.is_some(),
+                lint_cache,
+                cached_lints: None,
+                lint_timings: lint_timings_arg.is_some(),
+            };
+            let result = rustc_driver::RunCompiler::new(&args, &mut callbacks).run();
+
+            if let Some(lint_cache) = callbacks.lint_cache.take() {
+                // The cache only saves time, the check still succeeded without it
+                let _ = lint_cache.write();
+            }
+
+            if let Some(path) = write_baseline_arg {
+                let target = baseline_target(&orig_args);
+                if let Err(e) = baseline::write_baseline(Path::new(&path), &target) {
+                    eprintln!("error: failed to write the baseline `{path}`: {e}");
+                    exit(1);
+                }
+            }
+
+            if let Some(arg) = lint_timings_arg {
+                let target = baseline_target(&orig_args);
+                match arg.strip_prefix("--lint-timings=") {
+                    Some(path) => {
+                        if let Err(e) = lint_timings::write_lint_timings(Path::new(path), &target) {
+                            eprintln!("error: failed to write the lint timings `{path}`: {e}");
+                            exit(1);
+                        }
+                    },
+                    None => lint_timings::print_lint_timings(&target),
+                }
+            }
+            result
         } else {
             rustc_driver::RunCompiler::new(&args, &mut RustcCallbacks { clippy_args_var }).run()
         }
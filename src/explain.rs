@@ -0,0 +1,250 @@
+//! The output of `cargo clippy --explain LINT`: the documentation of a lint along with its group,
+//! default level, applicability, the Rust versions it depends on and its configuration.
+
+use crate::lint_msrvs::LINT_MSRVS;
+use clippy_lints::conf::{self, lint_conf_keys, lookup_conf_file, value_sources, Conf};
+use clippy_lints::LintInfo;
+use clippy_utils::diagnostics::lint_docs_url;
+use rustc_lint::Level;
+use rustc_semver::RustcVersion;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+/// The output formats of `--explain`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExplainFormat {
+    Text,
+    Json,
+}
+
+impl ExplainFormat {
+    /// Parses the `FORMAT` of `--explain LINT --format FORMAT`.
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The applicability of the suggestions of each lint with suggestions, generated by `cargo
+/// collect-metadata`.
+const LINT_APPLICABILITY: &str = include_str!("../util/gh-pages/lint_applicability.json");
+
+/// What `--explain` shows about a lint.
+struct Explanation {
+    info: &'static LintInfo,
+    /// The lint name, without the `clippy::` prefix.
+    name: String,
+    applicability: Option<String>,
+    /// The versions from [`LINT_MSRVS`] and the MSRV of the project.
+    msrv: Option<(&'static [RustcVersion], Option<String>)>,
+    /// The configuration keys affecting the lint, with their values and the file setting them,
+    /// `None` for defaults.
+    conf: Vec<(String, Value, Option<PathBuf>)>,
+}
+
+/// Prints the documentation of the lint `name`, given without the `clippy::` prefix, with its
+/// configuration in the current directory.
+pub fn explain(name: &str, format: ExplainFormat) {
+    let target = format!("clippy::{}", name.to_ascii_uppercase());
+    let Some(info) = clippy_lints::lints().iter().find(|info| info.lint().name == target) else {
+        println!("unknown lint: {name}");
+        return;
+    };
+    let upper_name = name.to_ascii_uppercase();
+
+    let conf_path = lookup_conf_file().ok().flatten();
+    let conf = conf_path
+        .as_deref()
+        .map(|path| conf::read(path).conf)
+        .unwrap_or_default();
+    let mut sources = conf_path.as_deref().map(value_sources).unwrap_or_default();
+    let keys = lint_conf_keys(&upper_name);
+    let values = conf.values();
+    let conf_values = keys
+        .into_iter()
+        .filter_map(|key| {
+            let (_, value) = values.iter().find(|(name, _)| *name == key)?;
+            let source = sources.remove(&key);
+            Some((key, value.clone(), source))
+        })
+        .collect();
+
+    let explanation = Explanation {
+        info,
+        name: name.to_string(),
+        applicability: lint_applicability(name),
+        msrv: LINT_MSRVS
+            .iter()
+            .find(|(lint, _)| *lint == upper_name)
+            .map(|(_, versions)| (*versions, project_msrv(&conf))),
+        conf: conf_values,
+    };
+    match format {
+        ExplainFormat::Text => print!("{}", explanation.to_text()),
+        ExplainFormat::Json => println!("{}", explanation.to_json()),
+    }
+}
+
+/// Returns the applicability of the suggestions of the lint `name`, `None` if it has no
+/// suggestions or if the metadata collector couldn't resolve it.
+fn lint_applicability(name: &str) -> Option<String> {
+    let applicability: Value = serde_json::from_str(LINT_APPLICABILITY).ok()?;
+    let applicability = applicability.get(name)?.as_str()?;
+    (applicability != "Unresolved").then(|| applicability.to_string())
+}
+
+/// Returns the MSRV set by the `msrv` configuration, or by `rust-version` in the `Cargo.toml` of
+/// the current directory.
+fn project_msrv(conf: &Conf) -> Option<String> {
+    if let Some(msrv) = &conf.msrv {
+        return Some(msrv.clone());
+    }
+    let manifest: toml::Value = toml::from_str(&fs::read_to_string("Cargo.toml").ok()?).ok()?;
+    manifest.get("package")?.get("rust-version")?.as_str().map(String::from)
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Allow | Level::Expect(_) => "allow",
+        Level::Warn | Level::ForceWarn(_) => "warn",
+        Level::Deny => "deny",
+        Level::Forbid => "forbid",
+    }
+}
+
+impl Explanation {
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Lint:          clippy::{}", self.name).unwrap();
+        writeln!(out, "Group:         clippy::{}", self.info.group()).unwrap();
+        writeln!(out, "Default level: {}", level_name(self.info.lint().default_level)).unwrap();
+        writeln!(
+            out,
+            "Applicability: {}",
+            self.applicability.as_deref().unwrap_or("unknown")
+        )
+        .unwrap();
+
+        if let Some((versions, project_msrv)) = &self.msrv {
+            let versions = versions.iter().map(ToString::to_string).collect::<Vec<_>>();
+            write!(out, "MSRV:          requires Rust {}", versions.join(", ")).unwrap();
+            match project_msrv {
+                Some(msrv) => writeln!(out, " (the MSRV of this project is {msrv})"),
+                None => writeln!(out, " (the MSRV of this project is not set)"),
+            }
+            .unwrap();
+        }
+
+        if !self.conf.is_empty() {
+            out.push_str("Configuration:\n");
+            for (key, value, source) in &self.conf {
+                let value = if value.is_null() {
+                    "not set".to_string()
+                } else {
+                    value.to_string()
+                };
+                match source {
+                    Some(file) => writeln!(out, "    {key} = {value} (from {})", file.display()),
+                    None => writeln!(out, "    {key} = {value} (default)"),
+                }
+                .unwrap();
+            }
+        }
+
+        writeln!(out, "Documentation: {}\n", lint_docs_url(&self.name)).unwrap();
+        out.push_str(self.info.explanation());
+        out
+    }
+
+    fn to_json(&self) -> String {
+        let msrv = self.msrv.as_ref().map(|(versions, project_msrv)| {
+            json!({
+                "requires": versions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                "project": project_msrv,
+            })
+        });
+        let conf = self
+            .conf
+            .iter()
+            .map(|(key, value, source)| {
+                let source = source
+                    .as_ref()
+                    .map_or_else(|| json!("default"), |file| json!(file.display().to_string()));
+                json!({ "key": key, "value": value, "source": source })
+            })
+            .collect::<Vec<_>>();
+        let output = json!({
+            "name": self.name,
+            "group": self.info.group(),
+            "default_level": level_name(self.info.lint().default_level),
+            "applicability": self.applicability,
+            "msrv": msrv,
+            "configuration": conf,
+            "docs_url": lint_docs_url(&self.name),
+            "docs": self.info.explanation(),
+        });
+        serde_json::to_string_pretty(&output).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn explanation(name: &str, conf: Vec<(String, Value, Option<PathBuf>)>) -> Explanation {
+        let upper_name = name.to_ascii_uppercase();
+        Explanation {
+            info: clippy_lints::lints()
+                .iter()
+                .find(|info| info.lint().name == format!("clippy::{upper_name}"))
+                .unwrap(),
+            name: name.to_string(),
+            applicability: lint_applicability(name),
+            msrv: LINT_MSRVS
+                .iter()
+                .find(|(lint, _)| *lint == upper_name)
+                .map(|(_, versions)| (*versions, Some(String::from("1.45.0")))),
+            conf,
+        }
+    }
+
+    #[test]
+    fn text_output() {
+        let conf = vec![(
+            String::from("msrv"),
+            Value::from("1.45.0"),
+            Some(PathBuf::from("/project/clippy.toml")),
+        )];
+        let text = explanation("manual_bits", conf).to_text();
+        let header = format!(
+            "Lint:          clippy::manual_bits\n\
+             Group:         clippy::style\n\
+             Default level: warn\n\
+             Applicability: MachineApplicable\n\
+             MSRV:          requires Rust 1.53.0 (the MSRV of this project is 1.45.0)\n\
+             Configuration:\n    \
+             msrv = \"1.45.0\" (from /project/clippy.toml)\n\
+             Documentation: {}\n\n",
+            lint_docs_url("manual_bits"),
+        );
+        assert!(text.starts_with(&header), "unexpected output:\n{text}");
+        assert!(text.ends_with(explanation("manual_bits", Vec::new()).info.explanation()));
+    }
+
+    #[test]
+    fn json_output() {
+        let output: Value = serde_json::from_str(&explanation("needless_return", Vec::new()).to_json()).unwrap();
+        assert_eq!(output["name"], "needless_return");
+        assert_eq!(output["group"], "style");
+        assert_eq!(output["default_level"], "warn");
+        assert_eq!(output["applicability"], "MachineApplicable");
+        assert_eq!(output["msrv"], Value::Null);
+        assert_eq!(output["configuration"], json!([]));
+        assert_eq!(output["docs_url"], lint_docs_url("needless_return"));
+    }
+}
@@ -0,0 +1,316 @@
+//! The suggestions printed by `cargo check --message-format=json`, reviewed and applied by
+//! `cargo clippy --fix --interactive`, `cargo clippy --fix-lint LINT` and
+//! `cargo clippy --fix --unsafe-fixes`.
+
+use crate::sarif::{CargoMessage, Diagnostic};
+use rustc_data_structures::fx::FxHashSet;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::{fs, io};
+
+/// A suggestion to apply.
+pub struct Fix {
+    /// The lint emitting the suggestion, e.g. `clippy::needless_return`.
+    pub lint: String,
+    /// The message of the warning.
+    pub message: String,
+    /// The message of the suggestion.
+    pub help: String,
+    /// Sorted by file and position.
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Replacement {
+    /// The path of the file, relative to the root of the workspace.
+    file: String,
+    /// The byte offsets of the replaced code.
+    start: usize,
+    end: usize,
+    /// The line of `start`, starting at 1.
+    line: usize,
+    text: String,
+}
+
+impl Replacement {
+    fn overlaps(&self, other: &Self) -> bool {
+        self.file == other.file && ((self.start < other.end && other.start < self.end) || self.start == other.start)
+    }
+}
+
+impl Fix {
+    /// Returns whether `self` and `other` change the same code, in which case only one of them can
+    /// be applied.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.replacements
+            .iter()
+            .any(|replacement| other.replacements.iter().any(|other| replacement.overlaps(other)))
+    }
+
+    /// Returns the changes of the fix as a unified diff. `root` is the root of the workspace.
+    pub fn diff(&self, root: &Path) -> io::Result<String> {
+        let mut diff = String::new();
+        for file in self.files() {
+            let content = fs::read_to_string(root.join(file))?;
+            let replacements = self
+                .replacements
+                .iter()
+                .filter(|replacement| replacement.file == file)
+                .collect::<Vec<_>>();
+            writeln!(diff, "--- a/{file}\n+++ b/{file}").unwrap();
+            diff.push_str(&hunks(&content, &replacements).ok_or_else(file_changed)?);
+        }
+        Ok(diff)
+    }
+
+    /// Returns the location of the first change of the fix, as `file:line`.
+    pub fn location(&self) -> String {
+        let first = &self.replacements[0];
+        format!("{}:{}", first.file, first.line)
+    }
+
+    /// Returns the paths of the files changed by the fix, relative to the root of the workspace.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        let mut files = self
+            .replacements
+            .iter()
+            .map(|replacement| replacement.file.as_str())
+            .collect::<Vec<_>>();
+        files.dedup();
+        files.into_iter()
+    }
+}
+
+/// The fixes of the warnings printed by Cargo.
+#[derive(Default)]
+pub struct FixSet {
+    fixes: Vec<Fix>,
+    /// The replacements of the fixes added so far, as Cargo prints a warning once for each target
+    /// checking the file containing it.
+    seen: FxHashSet<Vec<Replacement>>,
+    /// The lint to keep the fixes of, `--fix-lint`.
+    lint: Option<String>,
+    /// Whether to keep the suggestions that may be incorrect, `--unsafe-fixes`.
+    maybe_incorrect: bool,
+    /// The errors printed by Cargo, as rendered by rustc.
+    errors: Vec<String>,
+}
+
+impl FixSet {
+    /// Returns an empty set, only keeping the fixes of `lint` if set. The `clippy::` prefix of
+    /// Clippy lints is optional. Suggestions that may be incorrect are kept if `maybe_incorrect`
+    /// is set, otherwise only machine-applicable ones are.
+    pub fn new(lint: Option<&str>, maybe_incorrect: bool) -> Self {
+        Self {
+            lint: lint.map(lint_name),
+            maybe_incorrect,
+            ..Self::default()
+        }
+    }
+
+    /// Adds the fixes of the diagnostic in a line of the output of
+    /// `cargo check --message-format=json` to the set. Returns the diagnostic as rendered by rustc
+    /// if it's an error, or a warning of the selected lint without a fix.
+    pub fn add_cargo_message(&mut self, line: &str) -> Option<String> {
+        let message = serde_json::from_str::<CargoMessage>(line).ok()?;
+        let diagnostic = message.message.filter(|_| message.reason == "compiler-message")?;
+        let lint = diagnostic.code.as_ref().map(|code| code.code.clone());
+        let selected = self.lint.is_none() || lint == self.lint;
+
+        let mut fixed = false;
+        if diagnostic.level == "warning"
+            && selected
+            && let Some(lint) = lint
+        {
+            for child in &diagnostic.children {
+                let Some(replacements) = suggestion_replacements(child, self.maybe_incorrect) else {
+                    continue;
+                };
+                fixed = true;
+                if self.seen.insert(replacements.clone()) {
+                    self.fixes.push(Fix {
+                        lint: lint.clone(),
+                        message: diagnostic.message.clone(),
+                        help: child.message.clone(),
+                        replacements,
+                    });
+                }
+            }
+        }
+
+        let is_error = diagnostic.level.starts_with("error");
+        if is_error && let Some(rendered) = &diagnostic.rendered {
+            self.errors.push(rendered.clone());
+        }
+        if fixed || !(selected || is_error) {
+            return None;
+        }
+        diagnostic.rendered
+    }
+
+    /// Returns the fixes, in the order of the warnings.
+    pub fn fixes(&self) -> &[Fix] {
+        &self.fixes
+    }
+
+    /// Returns the errors printed by Cargo, as rendered by rustc.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+/// Returns the name of `lint` as it appears in diagnostics, e.g. `clippy::needless_return` for
+/// `needless-return`.
+fn lint_name(lint: &str) -> String {
+    let lint = lint.to_ascii_lowercase().replace('-', "_");
+    let clippy_lint = format!("clippy::{}", lint.strip_prefix("clippy::").unwrap_or(&lint));
+    if clippy_lints::lints()
+        .iter()
+        .any(|info| info.lint().name.eq_ignore_ascii_case(&clippy_lint))
+    {
+        clippy_lint
+    } else {
+        lint
+    }
+}
+
+/// Returns the replacements of a suggestion, or `None` if it isn't machine-applicable, or may be
+/// incorrect without `maybe_incorrect`, or is made of several alternatives.
+fn suggestion_replacements(suggestion: &Diagnostic, maybe_incorrect: bool) -> Option<Vec<Replacement>> {
+    let mut replacements = suggestion
+        .spans
+        .iter()
+        .filter(|span| match span.suggestion_applicability.as_deref() {
+            Some("MachineApplicable") => true,
+            Some("MaybeIncorrect") => maybe_incorrect,
+            _ => false,
+        })
+        .map(|span| {
+            Some(Replacement {
+                file: span.file_name.clone(),
+                start: span.byte_start,
+                end: span.byte_end,
+                line: span.line_start,
+                text: span.suggested_replacement.clone()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    replacements.sort_by(|a, b| (&a.file, a.start).cmp(&(&b.file, b.start)));
+    if replacements.is_empty() || replacements.windows(2).any(|pair| pair[0].overlaps(&pair[1])) {
+        return None;
+    }
+    Some(replacements)
+}
+
+/// Applies `fixes` to the files they change, in the workspace at `root`. A fix overlapping one of
+/// the previous fixes isn't applied, the skipped fixes are returned.
+pub fn apply_fixes<'a>(fixes: &[&'a Fix], root: &Path) -> io::Result<Vec<&'a Fix>> {
+    let mut applied: Vec<&Fix> = Vec::new();
+    let mut skipped = Vec::new();
+    for &fix in fixes {
+        if applied.iter().any(|applied| applied.overlaps(fix)) {
+            skipped.push(fix);
+        } else {
+            applied.push(fix);
+        }
+    }
+
+    let mut files: BTreeMap<&str, Vec<&Replacement>> = BTreeMap::new();
+    for replacement in applied.iter().flat_map(|fix| &fix.replacements) {
+        files.entry(&replacement.file).or_default().push(replacement);
+    }
+    for (file, mut replacements) in files {
+        let path = root.join(file);
+        let mut content = fs::read_to_string(&path)?;
+        replacements.sort_by_key(|replacement| replacement.start);
+        for replacement in replacements.iter().rev() {
+            if content.get(replacement.start..replacement.end).is_none() {
+                return Err(file_changed());
+            }
+            content.replace_range(replacement.start..replacement.end, &replacement.text);
+        }
+        fs::write(&path, content)?;
+    }
+    Ok(skipped)
+}
+
+fn file_changed() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "the file changed since it was checked")
+}
+
+/// Returns the hunks of the diff of applying `replacements` to `content`, without context lines.
+/// `replacements` are sorted and don't overlap.
+fn hunks(content: &str, replacements: &[&Replacement]) -> Option<String> {
+    let mut hunks = String::new();
+    let mut replacements = replacements.iter().peekable();
+    while let Some(first) = replacements.next() {
+        content.get(first.start..first.end)?;
+        let start = content[..first.start].rfind('\n').map_or(0, |pos| pos + 1);
+        let mut end = line_end(content, first.end);
+        let mut new = content[start..first.start].to_string();
+        new.push_str(&first.text);
+        let mut pos = first.end;
+        // Replacements on the same lines are in the same hunk
+        while let Some(next) = replacements.next_if(|next| next.start <= end) {
+            content.get(next.start..next.end)?;
+            new.push_str(&content[pos..next.start]);
+            new.push_str(&next.text);
+            pos = next.end;
+            end = end.max(line_end(content, next.end));
+        }
+        new.push_str(&content[pos..end]);
+
+        let old = &content[start..end];
+        let line = content[..start].matches('\n').count() + 1;
+        writeln!(
+            hunks,
+            "@@ -{line},{} +{line},{} @@",
+            old.split('\n').count(),
+            new.split('\n').count()
+        )
+        .unwrap();
+        for old_line in old.split('\n') {
+            writeln!(hunks, "-{old_line}").unwrap();
+        }
+        for new_line in new.split('\n') {
+            writeln!(hunks, "+{new_line}").unwrap();
+        }
+    }
+    Some(hunks)
+}
+
+/// Returns the position of the end of the line containing `pos`, excluding the line break.
+fn line_end(content: &str, pos: usize) -> usize {
+    content[pos..].find('\n').map_or(content.len(), |offset| pos + offset)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MESSAGE: &str = r#"{"reason":"compiler-message","package_id":"foo 0.1.0","message":{"rendered":"warning: unneeded `return` statement\n","message":"unneeded `return` statement","code":{"code":"clippy::needless_return","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","byte_start":20,"byte_end":28,"line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"text":[],"label":null,"suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[{"message":"remove `return`","code":null,"level":"help","spans":[{"file_name":"src/main.rs","byte_start":20,"byte_end":28,"line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"text":[],"label":null,"suggested_replacement":"1","suggestion_applicability":"MachineApplicable","expansion":null}],"children":[],"rendered":null}]}}"#;
+
+    #[test]
+    fn cargo_message_to_fix() {
+        let mut fixes = FixSet::new(Some("needless-return"), false);
+        assert_eq!(fixes.add_cargo_message(MESSAGE), None);
+        // the same warning for another target
+        fixes.add_cargo_message(MESSAGE);
+        assert_eq!(fixes.fixes().len(), 1);
+        let fix = &fixes.fixes()[0];
+        assert_eq!(fix.help, "remove `return`");
+        assert_eq!(fix.location(), "src/main.rs:2");
+
+        let content = "fn f() -> i32 {\n    return 1;\n}\n";
+        let replacements = fix.replacements.iter().collect::<Vec<_>>();
+        assert_eq!(
+            hunks(content, &replacements).unwrap(),
+            "@@ -2,1 +2,1 @@\n-    return 1;\n+    1;\n"
+        );
+
+        let mut other_lint = FixSet::new(Some("clippy::needless_bool"), false);
+        assert_eq!(other_lint.add_cargo_message(MESSAGE), None);
+        assert!(other_lint.fixes().is_empty());
+    }
+}
@@ -0,0 +1,873 @@
+//! The cache of `--lint-cache`, replaying the diagnostics of a previous check of a crate when only
+//! the levels of Clippy lints changed since.
+//!
+//! A check that can't be replayed records the diagnostics emitted with its lint levels. As long as
+//! the sources, the configuration and the other compiler options don't change, the following checks
+//! emit the recorded diagnostics with the new lint levels instead of checking the crate again. A
+//! check enabling a Clippy lint that was allowed, whose diagnostics weren't recorded, checks the
+//! crate again.
+//!
+//! The diagnostics are stored with their `Encodable` implementation, [`CacheEncoder`] encoding
+//! their spans as offsets in the source files of the crate.
+
+use clippy_lints::conf::{
+    self, expand_lint_group, is_lint_group, lookup_conf_file, lookup_inherited_conf_files, PathOverrides,
+};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexSet};
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_errors::{Diagnostic, DiagnosticId, Level as DiagnosticLevel};
+use rustc_hir::MaybeOwner;
+use rustc_lint::{Level, Lint};
+use rustc_macros::{Decodable, Encodable};
+use rustc_middle::lint::{struct_lint_level, LintLevelSource};
+use rustc_middle::ty::TyCtxt;
+use rustc_serialize::opaque::{MemDecoder, MemEncoder};
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
+use rustc_session::config::Options;
+use rustc_session::Session;
+use rustc_span::source_map::SourceMap;
+use rustc_span::symbol::Symbol;
+use rustc_span::{BytePos, FileName, RealFileName, Span, DUMMY_SP};
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::{env, fs, io};
+
+/// The start of the first line of cache files, followed by the version of their format and the key
+/// of the cache.
+const CACHE_MAGIC: &str = "clippy-lint-cache";
+
+/// The version of the format of cache files, increased when it changes.
+const CACHE_VERSION: u32 = 2;
+
+/// The environment variables read by Clippy while checking a crate.
+const CLIPPY_ENV_VARS: &[&str] = &[
+    "CARGO_MANIFEST_DIR",
+    "CARGO_PKG_RUST_VERSION",
+    "CLIPPY_CONF_DIR",
+    "CLIPPY_DISABLE_DOCS_LINKS",
+];
+
+/// The flags setting lint levels on the command line.
+const LINT_LEVEL_FLAGS: &[&str] = &[
+    "-A",
+    "-W",
+    "-D",
+    "-F",
+    "--allow",
+    "--warn",
+    "--deny",
+    "--forbid",
+    "--force-warn",
+];
+
+/// The contents of a cache file, after its first line.
+#[derive(Encodable, Decodable)]
+struct CacheFile {
+    /// The source files of the crate, with a hash of their contents.
+    inputs: Vec<(String, String)>,
+    /// The environment variables read by the crate, with their values.
+    env: Vec<(String, Option<String>)>,
+    /// The options setting the levels of Clippy lints when the crate was checked, and the level of
+    /// `--cap-lints`, see [`clippy_lint_opts`].
+    lint_opts: Vec<(String, String)>,
+    lint_cap: Option<String>,
+    /// The Clippy lints whose level is set by an attribute or by the `[[overrides]]` of the
+    /// configuration somewhere in the crate.
+    attribute_lints: Vec<String>,
+    /// The source files the spans of `diagnostics` point to.
+    files: Vec<PathBuf>,
+    /// The [`CachedDiagnostic`]s, encoded by [`CacheEncoder`].
+    diagnostics: Vec<u8>,
+}
+
+#[derive(Encodable, Decodable)]
+struct CachedDiagnostic {
+    diagnostic: Diagnostic,
+    /// Whether this is a Clippy lint with a level set on the command line or by default rather
+    /// than by an attribute. The level and the note explaining it, which is removed from
+    /// `diagnostic`, are computed again on replay.
+    command_line_level: bool,
+}
+
+/// The cache of the diagnostics of a crate checked with `cargo check`, stored next to its
+/// metadata.
+pub struct LintCache {
+    path: PathBuf,
+    /// The metadata written by the check, which is left as is when replaying.
+    metadata: PathBuf,
+    /// The dep-info file written by the check, listing the inputs of the crate.
+    dep_info: PathBuf,
+    /// The configuration files, which aren't inputs as the rest of the configuration is part of
+    /// the key and their `[lints]` table only sets lint levels.
+    conf_files: Vec<PathBuf>,
+    /// A hash of the version of Clippy, of the compiler options except the levels of Clippy lints
+    /// and of the configuration except its `[lints]` table.
+    key: String,
+    /// The Clippy lints whose level is set by the `[[overrides]]` of the configuration.
+    overridden_lints: Vec<String>,
+    /// The results of the check, once [`LintCache::finish_recording`] recorded them.
+    recorded: Option<CacheFile>,
+}
+
+impl LintCache {
+    /// Returns the cache of the crate checked with `args` by Clippy `version`, or `None` if it
+    /// isn't checked by `cargo check` or its configuration has errors.
+    pub fn new(args: &[String], version: &str) -> Option<Self> {
+        if arg_value(args, "--emit")? != "dep-info,metadata" {
+            return None;
+        }
+        let out_dir = Path::new(arg_value(args, "--out-dir")?);
+        let crate_name = arg_value(args, "--crate-name")?;
+        let extra_filename = codegen_option(args, "extra-filename").unwrap_or_default();
+
+        let conf_path = lookup_conf_file().ok()?;
+        let conf = conf_path.as_deref().map(conf::read);
+        if conf.as_ref().map_or(false, |conf| !conf.errors.is_empty()) {
+            return None;
+        }
+
+        let mut hasher = StableHasher::new();
+        version.hash(&mut hasher);
+        without_clippy_lint_flags(args).hash(&mut hasher);
+        for (key, value) in conf.as_ref().map(|conf| conf.conf.values()).unwrap_or_default() {
+            if key != "lints" {
+                key.hash(&mut hasher);
+                value.to_string().hash(&mut hasher);
+            }
+        }
+        for var in CLIPPY_ENV_VARS {
+            env::var_os(var).hash(&mut hasher);
+        }
+        let key: u128 = hasher.finish();
+
+        Some(Self {
+            path: out_dir.join(format!("{crate_name}{extra_filename}.clippy-cache")),
+            metadata: out_dir.join(format!("lib{crate_name}{extra_filename}.rmeta")),
+            dep_info: out_dir.join(format!("{crate_name}{extra_filename}.d")),
+            conf_files: conf_path
+                .as_deref()
+                .map(lookup_inherited_conf_files)
+                .unwrap_or_default(),
+            key: format!("{key:032x}"),
+            overridden_lints: conf
+                .map(|conf| PathOverrides::new(&conf.conf.overrides).lint_levels())
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|(_, levels)| levels.into_iter().map(|(lint, _)| lint))
+                .collect(),
+            recorded: None,
+        })
+    }
+
+    /// The first line of the cache file, which has to match to decode the rest.
+    fn header(&self) -> String {
+        format!("{CACHE_MAGIC} {CACHE_VERSION} {}\n", self.key)
+    }
+
+    /// Returns the diagnostics cached for the current sources of the crate, if they can be
+    /// replayed with the lint levels of `opts`.
+    pub fn load(&self, opts: &Options) -> Option<CachedLints> {
+        let levels = CommandLineLevels::new(&opts.lint_opts, opts.lint_cap)?;
+        let content = fs::read(&self.path).ok()?;
+        let cache = CacheFile::decode(&mut MemDecoder::new(content.strip_prefix(self.header().as_bytes())?, 0));
+        let fresh = self.metadata.exists()
+            && self.dep_info.exists()
+            && cache
+                .inputs
+                .iter()
+                .all(|(path, hash)| fs::read(path).map_or(false, |content| hash_content(&content) == *hash))
+            && cache.env.iter().all(|(name, value)| env::var(name).ok() == *value);
+        if !fresh {
+            return None;
+        }
+
+        let lint_opts = cache
+            .lint_opts
+            .iter()
+            .map(|(flag, level)| Some((flag.clone(), Level::from_str(level)?)))
+            .collect::<Option<Vec<_>>>()?;
+        let lint_cap = match &cache.lint_cap {
+            Some(cap) => Some(Level::from_str(cap)?),
+            None => None,
+        };
+        let recorded = CommandLineLevels::new(&lint_opts, lint_cap)?;
+        // The diagnostics of the lints that were allowed weren't recorded, and the ones of the lints
+        // with levels set by attributes can't tell whether their level comes from the command line
+        let attribute_lints: FxHashSet<&str> = cache.attribute_lints.iter().map(String::as_str).collect();
+        let replayable = clippy_lints::lints().iter().map(|info| info.lint()).all(|lint| {
+            (recorded.command_line_level(lint) != Level::Allow || levels.command_line_level(lint) == Level::Allow)
+                && (!attribute_lints.contains(lint.name_lower().as_str()) || recorded.level(lint) == levels.level(lint))
+        });
+        replayable.then_some(CachedLints {
+            files: cache.files,
+            diagnostics: cache.diagnostics,
+            levels,
+        })
+    }
+
+    /// Prepares recording the diagnostics of the check for the next runs. Returns `false` if the
+    /// lint levels of `opts` can't be replayed.
+    pub fn record(opts: &Options) -> bool {
+        if CommandLineLevels::new(&opts.lint_opts, opts.lint_cap).is_none() {
+            return false;
+        }
+        *RECORDING.lock().unwrap() = Some(Recording {
+            lint_opts: clippy_lint_opts(&opts.lint_opts),
+            lint_cap: opts.lint_cap.map(|cap| cap.as_str().to_string()),
+            diagnostics: Vec::new(),
+            complete: true,
+        });
+        true
+    }
+
+    /// Starts recording the emitted diagnostics, after [`LintCache::record`].
+    pub fn start_recording() {
+        let previous = rustc_errors::TRACK_DIAGNOSTICS.swap(&TRACK_DIAGNOSTIC);
+        let _ = PREVIOUS_TRACK_DIAGNOSTIC.set(*previous);
+    }
+
+    /// Stops recording once the crate is analyzed, the diagnostics emitted after it like the count
+    /// of errors don't depend on the crate. The diagnostics aren't kept if some of them can't be
+    /// replayed.
+    pub fn finish_recording(&mut self, tcx: TyCtxt<'_>) {
+        let Some(recording) = RECORDING.lock().unwrap().take() else {
+            return;
+        };
+        if !recording.complete {
+            return;
+        }
+        let Some(mut attribute_lints) = attribute_lints(tcx) else {
+            return;
+        };
+        attribute_lints.extend(self.overridden_lints.iter().cloned());
+
+        let mut diagnostics = Vec::with_capacity(recording.diagnostics.len());
+        for mut diagnostic in recording.diagnostics {
+            let command_line_level = matches!(
+                &diagnostic.code,
+                Some(DiagnosticId::Lint { name, .. }) if clippy_lint(name).is_some() && !attribute_lints.contains(name)
+            );
+            // The last note of a lint explains where its level comes from
+            if command_line_level {
+                match diagnostic.children.pop() {
+                    Some(note) if note.level == DiagnosticLevel::OnceNote && note.span.primary_span().is_none() => {},
+                    _ => return,
+                }
+            }
+            diagnostics.push(CachedDiagnostic {
+                diagnostic,
+                command_line_level,
+            });
+        }
+
+        let mut encoder = CacheEncoder {
+            encoder: MemEncoder::new(),
+            source_map: tcx.sess.source_map(),
+            files: FxIndexSet::default(),
+            replayable: true,
+        };
+        diagnostics.encode(&mut encoder);
+        if !encoder.replayable {
+            return;
+        }
+
+        let mut attribute_lints: Vec<_> = attribute_lints.into_iter().collect();
+        attribute_lints.sort_unstable();
+        self.recorded = Some(CacheFile {
+            inputs: Vec::new(),
+            env: Vec::new(),
+            lint_opts: recording.lint_opts,
+            lint_cap: recording.lint_cap,
+            attribute_lints,
+            files: encoder.files.into_iter().collect(),
+            diagnostics: encoder.encoder.finish(),
+        });
+    }
+
+    /// Writes the diagnostics recorded by [`LintCache::finish_recording`], with the inputs of the
+    /// crate listed in its dep-info file.
+    pub fn write(self) -> io::Result<()> {
+        let Some(mut cache) = self.recorded else {
+            return Ok(());
+        };
+
+        let (files, env) = parse_dep_info(&fs::read_to_string(&self.dep_info)?);
+        for file in files {
+            if self.conf_files.iter().any(|conf_file| conf_file == Path::new(&file)) {
+                continue;
+            }
+            let content = fs::read(&file)?;
+            cache.inputs.push((file, hash_content(&content)));
+        }
+        cache.env = env.into_iter().filter(|(name, _)| name != "CLIPPY_ARGS").collect();
+
+        let mut encoder = MemEncoder::new();
+        cache.encode(&mut encoder);
+        let mut content = self.header().into_bytes();
+        content.extend(encoder.finish());
+        // Another check reading a partially written file could replay a part of the diagnostics
+        let tmp = self.path.with_extension("clippy-cache-tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(tmp, &self.path)
+    }
+}
+
+/// The diagnostics of a [`LintCache`], replayed instead of checking the crate.
+pub struct CachedLints {
+    files: Vec<PathBuf>,
+    diagnostics: Vec<u8>,
+    levels: CommandLineLevels,
+}
+
+impl CachedLints {
+    /// Emits the cached diagnostics with the current lint levels, and updates the outputs of the
+    /// check as if the crate was checked with `clippy_args`, the value of `CLIPPY_ARGS`. Returns
+    /// `false` without emitting anything if a source file can't be loaded.
+    pub fn replay(&self, sess: &Session, cache: &LintCache, clippy_args: Option<&str>) -> bool {
+        let mut files = Vec::with_capacity(self.files.len());
+        for path in &self.files {
+            let Ok(file) = sess.source_map().load_file(path) else {
+                return false;
+            };
+            files.push(file.start_pos);
+        }
+        let mut decoder = CacheDecoder {
+            decoder: MemDecoder::new(&self.diagnostics, 0),
+            files: &files,
+        };
+
+        for diagnostic in Vec::<CachedDiagnostic>::decode(&mut decoder) {
+            diagnostic.emit(sess, &self.levels);
+        }
+        if sess.opts.json_artifact_notifications {
+            sess.diagnostic()
+                .emit_artifact_notification(&cache.metadata, "metadata");
+        }
+        // Without it Cargo would check the crate again on the next run
+        let _ = update_dep_info(&cache.dep_info, clippy_args);
+        true
+    }
+}
+
+impl CachedDiagnostic {
+    fn emit(mut self, sess: &Session, levels: &CommandLineLevels) {
+        let lint = match &self.diagnostic.code {
+            Some(DiagnosticId::Lint { name, .. }) if self.command_line_level => clippy_lint(name),
+            _ => None,
+        };
+        let Some(lint) = lint else {
+            sess.diagnostic().emit_diagnostic(&mut self.diagnostic);
+            return;
+        };
+
+        let (level, source) = levels.level_source(lint);
+        let diagnostic = self.diagnostic;
+        let args = diagnostic
+            .args()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let message = diagnostic.message[0].0.clone();
+        struct_lint_level(sess, lint, level, source, Some(diagnostic.span), message, |diag| {
+            diag.message = diagnostic.message;
+            diag.children = diagnostic.children;
+            diag.suggestions = diagnostic.suggestions;
+            diag.replace_args(args);
+            diag
+        });
+    }
+}
+
+/// The levels of the Clippy lints set on the command line, including the ones of the `[lints]`
+/// table of `clippy.toml`.
+struct CommandLineLevels {
+    /// The level of each lint set on the command line, with the name of the lint or lint group
+    /// it was set for.
+    lints: FxHashMap<String, (Level, String)>,
+    /// The level of the `warnings` lint group, applying to the lints at the warn level.
+    warnings: Option<Level>,
+    /// The level set with `--cap-lints`.
+    cap: Option<Level>,
+}
+
+impl CommandLineLevels {
+    /// Returns `None` if the levels can't be replayed: forbidding a lint or forcing a warning also
+    /// overrides the lint attributes of the crate, and renamed lints aren't resolved.
+    fn new(lint_opts: &[(String, Level)], cap: Option<Level>) -> Option<Self> {
+        let mut lints = FxHashMap::default();
+        let mut warnings = None;
+        for (flag, level) in lint_opts {
+            if flag == "warnings" {
+                warnings = Some(*level);
+            }
+            let Some(name) = flag.strip_prefix("clippy::") else {
+                continue;
+            };
+            if matches!(level, Level::Forbid | Level::ForceWarn(_))
+                || !(is_lint_group(name) || clippy_lint(flag).is_some())
+            {
+                return None;
+            }
+            for lint in expand_lint_group(name) {
+                lints.insert(lint, (*level, flag.clone()));
+            }
+        }
+        Some(Self { lints, warnings, cap })
+    }
+
+    /// Returns the level of `lint` on the command line, without taking the `warnings` lint group
+    /// and `--cap-lints` into account.
+    fn command_line_level(&self, lint: &Lint) -> Level {
+        self.lints
+            .get(&lint.name_lower())
+            .map_or(lint.default_level, |(level, _)| *level)
+    }
+
+    /// Returns the level of `lint`, with the flag setting it and the level of the flag, or `None`
+    /// for the default level.
+    fn level(&self, lint: &Lint) -> (Level, Option<(&str, Level)>) {
+        let (mut level, mut flag) = match self.lints.get(&lint.name_lower()) {
+            Some((level, flag)) => (*level, Some((flag.as_str(), *level))),
+            None => (lint.default_level, None),
+        };
+        if level == Level::Warn
+            && let Some(warnings) = self.warnings
+            && warnings != Level::Warn
+        {
+            level = warnings;
+            flag = Some(("warnings", warnings));
+        }
+        (level.min(self.cap.unwrap_or(Level::Forbid)), flag)
+    }
+
+    /// Returns the level of `lint` and where it comes from, like
+    /// `rustc_middle::lint::reveal_actual_level`.
+    fn level_source(&self, lint: &Lint) -> (Level, LintLevelSource) {
+        let (level, flag) = self.level(lint);
+        let source = match flag {
+            Some((flag, flag_level)) => LintLevelSource::CommandLine(Symbol::intern(flag), flag_level),
+            None => LintLevelSource::Default,
+        };
+        (level, source)
+    }
+}
+
+/// Returns the options of `lint_opts` setting the levels of Clippy lints and of `warnings`, with
+/// the names of the levels.
+fn clippy_lint_opts(lint_opts: &[(String, Level)]) -> Vec<(String, String)> {
+    lint_opts
+        .iter()
+        .filter(|(flag, _)| flag == "warnings" || flag.starts_with("clippy::"))
+        .map(|(flag, level)| (flag.clone(), level.as_str().to_string()))
+        .collect()
+}
+
+/// Returns the Clippy lint named `name`, in the `clippy::lint_name` form.
+fn clippy_lint(name: &str) -> Option<&'static Lint> {
+    static LINTS: OnceLock<FxHashMap<String, &'static Lint>> = OnceLock::new();
+    LINTS
+        .get_or_init(|| {
+            clippy_lints::lints()
+                .iter()
+                .map(|info| (info.lint().name_lower(), info.lint()))
+                .collect()
+        })
+        .get(name)
+        .copied()
+}
+
+/// Returns the Clippy lints whose level is set by an attribute in the crate, or `None` if an
+/// attribute sets the level of `warnings`, which can change the level of any lint at the warn
+/// level, or of a renamed Clippy lint.
+fn attribute_lints(tcx: TyCtxt<'_>) -> Option<FxHashSet<String>> {
+    let mut lints = FxHashSet::default();
+    for owner in tcx.hir().krate().owners.iter() {
+        let MaybeOwner::Owner(info) = owner else {
+            continue;
+        };
+        for attr in info.attrs.map.iter().flat_map(|(_, attrs)| attrs.iter()) {
+            if Level::from_attr(attr).is_none() {
+                continue;
+            }
+            for meta in attr.meta_item_list().unwrap_or_default() {
+                let Some(meta) = meta.meta_item() else {
+                    continue;
+                };
+                match meta.path.segments.as_slice() {
+                    [name] if name.ident.name.as_str() == "warnings" => return None,
+                    [tool, name] if tool.ident.name.as_str() == "clippy" => {
+                        let name = name.ident.name.as_str();
+                        if !is_lint_group(name) && clippy_lint(&format!("clippy::{name}")).is_none() {
+                            return None;
+                        }
+                        lints.extend(expand_lint_group(name));
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+    Some(lints)
+}
+
+/// The diagnostics recorded after [`LintCache::record`].
+struct Recording {
+    /// See [`CacheFile`].
+    lint_opts: Vec<(String, String)>,
+    lint_cap: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+    /// Whether every diagnostic can be replayed.
+    complete: bool,
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+type TrackDiagnostic = fn(&mut Diagnostic, &mut dyn FnMut(&mut Diagnostic));
+
+/// The function that `rustc_errors::TRACK_DIAGNOSTICS` was set to before recording.
+static PREVIOUS_TRACK_DIAGNOSTIC: OnceLock<TrackDiagnostic> = OnceLock::new();
+
+static TRACK_DIAGNOSTIC: TrackDiagnostic = track_diagnostic;
+
+/// Called by the compiler for every diagnostic, `emit` emits it.
+fn track_diagnostic(diagnostic: &mut Diagnostic, emit: &mut dyn FnMut(&mut Diagnostic)) {
+    record_diagnostic(diagnostic);
+    match PREVIOUS_TRACK_DIAGNOSTIC.get() {
+        Some(previous) => previous(diagnostic, emit),
+        None => emit(diagnostic),
+    }
+}
+
+fn record_diagnostic(diagnostic: &Diagnostic) {
+    let mut recording = RECORDING.lock().unwrap();
+    let Some(recording) = recording.as_mut() else {
+        return;
+    };
+    // Future incompatibility warnings are also reported after the check, and the expectations of
+    // `#[expect]` are checked at the end of the check
+    let replayable = !matches!(
+        &diagnostic.code,
+        Some(
+            DiagnosticId::Lint {
+                has_future_breakage: true,
+                ..
+            } | DiagnosticId::Lint {
+                is_force_warn: true,
+                ..
+            }
+        )
+    );
+    match diagnostic.level() {
+        DiagnosticLevel::Allow | DiagnosticLevel::Expect(_) => {},
+        DiagnosticLevel::Error { lint: true }
+        | DiagnosticLevel::Warning(None)
+        | DiagnosticLevel::Note
+        | DiagnosticLevel::OnceNote
+        | DiagnosticLevel::Help
+            if replayable =>
+        {
+            recording.diagnostics.push(diagnostic.clone());
+        },
+        // The crate doesn't compile
+        _ => recording.complete = false,
+    }
+}
+
+/// Encodes the recorded diagnostics, with their spans as offsets in the source files of the crate,
+/// which are loaded again to replay them.
+struct CacheEncoder<'a> {
+    encoder: MemEncoder,
+    source_map: &'a SourceMap,
+    files: FxIndexSet<PathBuf>,
+    /// Whether all the spans point to local source files, as rendering diagnostics in macro
+    /// expansions or in other crates needs more than the file and the offsets.
+    replayable: bool,
+}
+
+impl CacheEncoder<'_> {
+    /// Returns the index of the file of `span` in `files`, and the offsets of `span` in it.
+    fn file_offsets(&mut self, span: Span) -> Option<(usize, u32, u32)> {
+        if span.from_expansion() {
+            return None;
+        }
+        let file = self.source_map.lookup_source_file(span.lo());
+        if file.is_imported() || span.hi() > file.end_pos {
+            return None;
+        }
+        let FileName::Real(RealFileName::LocalPath(path)) = &file.name else {
+            return None;
+        };
+        let (index, _) = self.files.insert_full(path.clone());
+        Some((index, (span.lo() - file.start_pos).0, (span.hi() - file.start_pos).0))
+    }
+}
+
+macro_rules! encoder_methods {
+    ($($name:ident($ty:ty);)*) => {
+        $(fn $name(&mut self, value: $ty) {
+            self.encoder.$name(value)
+        })*
+    }
+}
+
+impl Encoder for CacheEncoder<'_> {
+    encoder_methods! {
+        emit_usize(usize);
+        emit_u128(u128);
+        emit_u64(u64);
+        emit_u32(u32);
+        emit_u16(u16);
+        emit_u8(u8);
+
+        emit_isize(isize);
+        emit_i128(i128);
+        emit_i64(i64);
+        emit_i32(i32);
+        emit_i16(i16);
+        emit_i8(i8);
+
+        emit_bool(bool);
+        emit_f64(f64);
+        emit_f32(f32);
+        emit_char(char);
+        emit_str(&str);
+        emit_raw_bytes(&[u8]);
+    }
+}
+
+impl<'a> Encodable<CacheEncoder<'a>> for Span {
+    fn encode(&self, e: &mut CacheEncoder<'a>) {
+        if self.is_dummy() {
+            return e.emit_u8(0);
+        }
+        match e.file_offsets(*self) {
+            Some((file, lo, hi)) => {
+                e.emit_u8(1);
+                e.emit_usize(file);
+                e.emit_u32(lo);
+                e.emit_u32(hi);
+            },
+            None => {
+                e.replayable = false;
+                e.emit_u8(0);
+            },
+        }
+    }
+}
+
+/// Decodes the diagnostics encoded by [`CacheEncoder`].
+struct CacheDecoder<'a> {
+    decoder: MemDecoder<'a>,
+    /// The start positions of the source files the spans point to, once loaded.
+    files: &'a [BytePos],
+}
+
+macro_rules! decoder_methods {
+    ($($name:ident -> $ty:ty;)*) => {
+        $(fn $name(&mut self) -> $ty {
+            self.decoder.$name()
+        })*
+    }
+}
+
+impl Decoder for CacheDecoder<'_> {
+    decoder_methods! {
+        read_usize -> usize;
+        read_u128 -> u128;
+        read_u64 -> u64;
+        read_u32 -> u32;
+        read_u16 -> u16;
+        read_u8 -> u8;
+
+        read_isize -> isize;
+        read_i128 -> i128;
+        read_i64 -> i64;
+        read_i32 -> i32;
+        read_i16 -> i16;
+        read_i8 -> i8;
+
+        read_bool -> bool;
+        read_f64 -> f64;
+        read_f32 -> f32;
+        read_char -> char;
+        read_str -> &str;
+    }
+
+    fn read_raw_bytes(&mut self, len: usize) -> &[u8] {
+        self.decoder.read_raw_bytes(len)
+    }
+}
+
+impl<'a> Decodable<CacheDecoder<'a>> for Span {
+    fn decode(d: &mut CacheDecoder<'a>) -> Self {
+        if d.read_u8() == 0 {
+            return DUMMY_SP;
+        }
+        let start = d.files[d.read_usize()];
+        let lo = d.read_u32();
+        let hi = d.read_u32();
+        Span::with_root_ctxt(start + BytePos(lo), start + BytePos(hi))
+    }
+}
+
+/// Returns the value of the option `name` in `args`, given as `name=value` or `name value`.
+fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Returns the value of the codegen option `name` in `args`, given with `-C name=value`.
+fn codegen_option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        let option = match arg.strip_prefix("-C") {
+            Some("") => args.next()?,
+            Some(option) => option,
+            None => continue,
+        };
+        if let Some(value) = option.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Returns `args` without the flags setting the levels of Clippy lints.
+fn without_clippy_lint_flags(args: &[String]) -> Vec<&str> {
+    let is_clippy_lint = |lint: &str| lint.replace('-', "_").starts_with("clippy::");
+    let mut kept = Vec::new();
+    let mut args = args.iter().map(String::as_str).peekable();
+    while let Some(arg) = args.next() {
+        if LINT_LEVEL_FLAGS.contains(&arg) {
+            if args.peek().map_or(false, |lint| is_clippy_lint(lint)) {
+                args.next();
+                continue;
+            }
+        } else if LINT_LEVEL_FLAGS.iter().any(|flag| {
+            arg.strip_prefix(flag)
+                .and_then(|rest| {
+                    if flag.starts_with("--") {
+                        rest.strip_prefix('=')
+                    } else {
+                        Some(rest)
+                    }
+                })
+                .map_or(false, is_clippy_lint)
+        }) {
+            continue;
+        }
+        kept.push(arg);
+    }
+    kept
+}
+
+/// Returns the files and the environment variables listed in a dep-info file written by rustc.
+fn parse_dep_info(dep_info: &str) -> (Vec<String>, Vec<(String, Option<String>)>) {
+    let mut files = Vec::new();
+    let mut env = Vec::new();
+    for line in dep_info.lines() {
+        if let Some(var) = line.strip_prefix("# env-dep:") {
+            match var.split_once('=') {
+                Some((name, value)) => env.push((name.to_string(), Some(unescape_env_value(value)))),
+                None => env.push((var.to_string(), None)),
+            }
+        } else if !line.starts_with('#')
+            && let Some(file) = line.strip_suffix(':')
+        {
+            files.push(file.replace("\\ ", " "));
+        }
+    }
+    (files, env)
+}
+
+/// Updates the value of `CLIPPY_ARGS` in the dep-info file at `path`, which Cargo compares to the
+/// current one to know whether to check the crate again.
+fn update_dep_info(path: &Path, clippy_args: Option<&str>) -> io::Result<()> {
+    let var = match clippy_args {
+        Some(value) => format!("# env-dep:CLIPPY_ARGS={}", escape_env_value(value)),
+        None => "# env-dep:CLIPPY_ARGS".to_string(),
+    };
+    let mut dep_info = fs::read_to_string(path)?
+        .lines()
+        .map(|line| {
+            if line == "# env-dep:CLIPPY_ARGS" || line.starts_with("# env-dep:CLIPPY_ARGS=") {
+                var.as_str()
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    dep_info.push('\n');
+    fs::write(path, dep_info)
+}
+
+/// Escapes the value of an environment variable like rustc does in dep-info files.
+fn escape_env_value(value: &str) -> String {
+    value.replace('\\', r"\\").replace('\n', r"\n").replace('\r', r"\r")
+}
+
+fn unescape_env_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+fn hash_content(content: &[u8]) -> String {
+    let mut hasher = StableHasher::new();
+    content.hash(&mut hasher);
+    let hash: u128 = hasher.finish();
+    format!("{hash:032x}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clippy_lint_flags() {
+        let args = "rustc -W clippy::all -Aclippy::style --deny=clippy::perf --warn clippy::needless-return -D warnings -Wunused --cap-lints warn"
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            without_clippy_lint_flags(&args),
+            ["rustc", "-D", "warnings", "-Wunused", "--cap-lints", "warn"]
+        );
+    }
+
+    #[test]
+    fn dep_info() {
+        let dep_info = "target/debug/deps/foo-1234.d: src/lib.rs src/my\\ mod.rs\n\nsrc/lib.rs:\nsrc/my\\ mod.rs:\n\n# env-dep:CLIPPY_ARGS=-D\\nwarnings\n# env-dep:FOO\n";
+        let (files, env) = parse_dep_info(dep_info);
+        assert_eq!(files, ["src/lib.rs", "src/my mod.rs"]);
+        assert_eq!(
+            env,
+            [
+                ("CLIPPY_ARGS".to_string(), Some("-D\nwarnings".to_string())),
+                ("FOO".to_string(), None)
+            ]
+        );
+        assert_eq!(escape_env_value("-D\nwarnings"), "-D\\nwarnings");
+    }
+}
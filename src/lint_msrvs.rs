@@ -0,0 +1,64 @@
+// This file was generated by `cargo dev update_lints`.
+// Use that command to update this file and do not edit by hand.
+// Manual edits will be overwritten.
+
+use clippy_utils::msrvs;
+use rustc_semver::RustcVersion;
+
+/// The Rust versions stabilizing the features that the MSRV-aware lints rely on. A lint with
+/// several versions is partially disabled below the highest one, e.g. for some types or only in
+/// its suggestion.
+#[rustfmt::skip]
+pub const LINT_MSRVS: &[(&str, &[RustcVersion])] = &[
+    ("ALMOST_COMPLETE_RANGE", &[msrvs::RANGE_INCLUSIVE]),
+    ("APPROX_CONSTANT", &[msrvs::LOG10_2, msrvs::TAU]),
+    ("BORROW_AS_PTR", &[msrvs::BORROW_AS_PTR]),
+    ("CAST_ABS_TO_UNSIGNED", &[msrvs::UNSIGNED_ABS]),
+    ("CAST_LOSSLESS", &[msrvs::FROM_BOOL]),
+    ("CAST_SLICE_DIFFERENT_SIZES", &[msrvs::PTR_SLICE_RAW_PARTS]),
+    ("CAST_SLICE_FROM_RAW_PARTS", &[msrvs::PTR_SLICE_RAW_PARTS]),
+    ("CHECKED_CONVERSIONS", &[msrvs::TRY_FROM]),
+    ("CLONED_INSTEAD_OF_COPIED", &[msrvs::OPTION_COPIED, msrvs::ITERATOR_COPIED]),
+    ("COLLAPSIBLE_STR_REPLACE", &[msrvs::PATTERN_TRAIT_CHAR_ARRAY]),
+    ("DEPRECATED_CFG_ATTR", &[msrvs::TOOL_ATTRIBUTES]),
+    ("DERIVABLE_IMPLS", &[msrvs::DEFAULT_ENUM_ATTRIBUTE]),
+    ("ERR_EXPECT", &[msrvs::EXPECT_ERR]),
+    ("EXPLICIT_AUTO_DEREF", &[msrvs::ARRAY_INTO_ITERATOR]),
+    ("EXPLICIT_DEREF_METHODS", &[msrvs::ARRAY_INTO_ITERATOR]),
+    ("FILTER_MAP_NEXT", &[msrvs::ITERATOR_FIND_MAP]),
+    ("FROM_OVER_INTO", &[msrvs::RE_REBALANCING_COHERENCE]),
+    ("IF_THEN_SOME_ELSE_NONE", &[msrvs::BOOL_THEN, msrvs::BOOL_THEN_SOME]),
+    ("INDEX_REFUTABLE_SLICE", &[msrvs::SLICE_PATTERNS]),
+    ("IS_DIGIT_ASCII_RADIX", &[msrvs::IS_ASCII_DIGIT]),
+    ("MANUAL_BITS", &[msrvs::MANUAL_BITS]),
+    ("MANUAL_CLAMP", &[msrvs::CLAMP]),
+    ("MANUAL_IS_ASCII_CHECK", &[msrvs::IS_ASCII_DIGIT, msrvs::IS_ASCII_DIGIT_CONST]),
+    ("MANUAL_LET_ELSE", &[msrvs::LET_ELSE]),
+    ("MANUAL_NON_EXHAUSTIVE", &[msrvs::NON_EXHAUSTIVE]),
+    ("MANUAL_RANGE_CONTAINS", &[msrvs::RANGE_CONTAINS]),
+    ("MANUAL_REM_EUCLID", &[msrvs::REM_EUCLID, msrvs::REM_EUCLID_CONST]),
+    ("MANUAL_RETAIN", &[msrvs::HASH_MAP_RETAIN, msrvs::STRING_RETAIN, msrvs::BTREE_MAP_RETAIN]),
+    ("MANUAL_SPLIT_ONCE", &[msrvs::STR_SPLIT_ONCE]),
+    ("MANUAL_STRIP", &[msrvs::STR_STRIP_PREFIX]),
+    ("MANUAL_STR_REPEAT", &[msrvs::STR_REPEAT]),
+    ("MAP_CLONE", &[msrvs::ITERATOR_COPIED]),
+    ("MAP_UNWRAP_OR", &[msrvs::RESULT_MAP_OR_ELSE]),
+    ("MATCH_LIKE_MATCHES_MACRO", &[msrvs::MATCHES_MACRO]),
+    ("MEM_REPLACE_WITH_DEFAULT", &[msrvs::MEM_TAKE]),
+    ("MISSING_CONST_FOR_FN", &[msrvs::CONST_IF_MATCH]),
+    ("NEEDLESS_BORROW", &[msrvs::ARRAY_INTO_ITERATOR]),
+    ("OPTION_AS_REF_DEREF", &[msrvs::OPTION_AS_DEREF]),
+    ("PTR_AS_PTR", &[msrvs::POINTER_CAST]),
+    ("REDUNDANT_FIELD_NAMES", &[msrvs::FIELD_INIT_SHORTHAND]),
+    ("REDUNDANT_STATIC_LIFETIMES", &[msrvs::STATIC_IN_CONST]),
+    ("REF_BINDING_TO_REFERENCE", &[msrvs::ARRAY_INTO_ITERATOR]),
+    ("SEEK_FROM_CURRENT", &[msrvs::SEEK_FROM_CURRENT]),
+    ("SEEK_TO_START_INSTEAD_OF_REWIND", &[msrvs::SEEK_REWIND]),
+    ("TRANSMUTE_PTR_TO_REF", &[msrvs::POINTER_CAST]),
+    ("UNCHECKED_DURATION_SUBTRACTION", &[msrvs::TRY_FROM]),
+    ("UNINLINED_FORMAT_ARGS", &[msrvs::FORMAT_ARGS_CAPTURE]),
+    ("UNNECESSARY_LAZY_EVALUATIONS", &[msrvs::BOOL_THEN_SOME]),
+    ("UNNECESSARY_TO_OWNED", &[msrvs::ITERATOR_COPIED]),
+    ("UNNESTED_OR_PATTERNS", &[msrvs::OR_PATTERNS]),
+    ("USE_SELF", &[msrvs::TYPE_ALIAS_ENUM_VARIANTS]),
+];
@@ -0,0 +1,156 @@
+//! Loading of the out-of-tree lint plugins of the `[[lint-plugins]]` sections of the
+//! configuration, see `clippy_utils::plugin` for the interface of the plugins.
+
+use clippy_lints::conf::{value_sources, Conf, LintPlugin};
+use clippy_utils::msrvs::Msrv;
+use clippy_utils::plugin::{Registrar, Registry, CLIPPY_COMMIT_SYMBOL, REGISTRAR_SYMBOL, RUSTC_VERSION_SYMBOL};
+use libloading::Library;
+use rustc_lint::LintStore;
+use rustc_session::Session;
+use rustc_span::Symbol;
+use std::ffi::{c_char, CStr};
+use std::mem;
+use std::path::{Path, PathBuf};
+
+/// Loads the lint plugins listed in `conf` and registers their lints in `store`. The paths of the
+/// plugins are relative to the configuration file at `conf_path` listing them.
+pub fn register_lint_plugins(store: &mut LintStore, sess: &Session, conf: &Conf, conf_path: Option<&Path>) {
+    if conf.lint_plugins.is_empty() {
+        return;
+    }
+
+    // with inherited configuration files, the paths are relative to the file setting the key
+    let base_dir = conf_path
+        .and_then(|path| value_sources(path).remove("lint-plugins"))
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let msrv = Msrv::read(&conf.msrv, sess);
+
+    for LintPlugin { path, conf } in &conf.lint_plugins {
+        let path = base_dir.join(path);
+        // re-run Clippy when the plugin is rebuilt
+        if let Some(path) = path.to_str() {
+            sess.parse_sess.file_depinfo.lock().insert(Symbol::intern(path));
+        }
+
+        let registrar = match load(&path) {
+            Ok(registrar) => registrar,
+            Err(e) => {
+                sess.err(format!("failed to load the lint plugin `{}`: {e}", path.display()));
+                continue;
+            },
+        };
+        let conf = toml::to_string(conf).unwrap_or_default();
+        registrar(&mut Registry {
+            store: &mut *store,
+            sess,
+            msrv,
+            conf: &conf,
+        });
+    }
+}
+
+/// Loads the dynamic library at `path` and returns its registrar, checking that the plugin was
+/// built with the same rustc as Clippy and the `clippy_utils` of the same commit.
+fn load(path: &Path) -> Result<Registrar, String> {
+    // Make sure the path contains a `/`, otherwise the library is searched in the system paths
+    let path = if path.is_relative() {
+        PathBuf::from(".").join(path)
+    } else {
+        path.to_path_buf()
+    };
+
+    // SAFETY: loading a library runs its initialization code, plugins are trusted like build
+    // scripts. The versions are NUL-terminated byte arrays, whose layout doesn't depend on the
+    // compiler. The registrar is only read once the versions match, a plugin built with the same
+    // rustc and `clippy_utils` declares it with the type of `clippy_utils`.
+    unsafe {
+        let lib = Library::new(&path).map_err(|e| e.to_string())?;
+
+        let rustc_version = version(&lib, RUSTC_VERSION_SYMBOL)?;
+        let expected_rustc_version = rustc_interface::util::rustc_version_str().unwrap_or_default();
+        if rustc_version.is_empty() || rustc_version != expected_rustc_version {
+            return Err(format!(
+                "it was built with rustc `{rustc_version}`, Clippy uses rustc `{expected_rustc_version}`"
+            ));
+        }
+        let clippy_commit = version(&lib, CLIPPY_COMMIT_SYMBOL)?;
+        let expected_clippy_commit = rustc_tools_util::get_version_info!().commit_hash.unwrap_or_default();
+        let expected_clippy_commit = expected_clippy_commit.trim();
+        if clippy_commit.is_empty() || clippy_commit != expected_clippy_commit {
+            return Err(format!(
+                "it was built with the `clippy_utils` of commit `{clippy_commit}`, Clippy was built from commit \
+                 `{expected_clippy_commit}`"
+            ));
+        }
+
+        let registrar = *lib
+            .get::<Registrar>(REGISTRAR_SYMBOL)
+            .map_err(|e| missing_symbol(REGISTRAR_SYMBOL, &e))?;
+
+        // The lint passes of the plugin live as long as the compiler session, the library can't
+        // ever be unloaded
+        mem::forget(lib);
+        Ok(registrar)
+    }
+}
+
+/// Reads the NUL-terminated version string at `symbol` in `lib`.
+///
+/// # Safety
+///
+/// The symbol must be a NUL-terminated byte array.
+unsafe fn version(lib: &Library, symbol: &[u8]) -> Result<String, String> {
+    let version = lib
+        .get::<*const c_char>(symbol)
+        .map_err(|e| missing_symbol(symbol, &e))?;
+    Ok(CStr::from_ptr(*version).to_string_lossy().into_owned())
+}
+
+fn missing_symbol(symbol: &[u8], error: &libloading::Error) -> String {
+    format!(
+        "it isn't a lint plugin, the symbol `{}` is missing: {error}",
+        String::from_utf8_lossy(symbol)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+    use std::fs;
+    use std::process::Command;
+
+    #[test]
+    fn missing_library() {
+        assert!(load(Path::new(&format!("{DLL_PREFIX}missing{DLL_SUFFIX}"))).is_err());
+    }
+
+    #[test]
+    fn not_a_plugin() {
+        // a library that isn't a lint plugin, the rustc driver Clippy is linked to
+        let sysroot = Command::new(env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+            .args(["--print", "sysroot"])
+            .output()
+            .unwrap()
+            .stdout;
+        let sysroot = PathBuf::from(String::from_utf8(sysroot).unwrap().trim());
+        let lib_dir = sysroot.join(if cfg!(windows) { "bin" } else { "lib" });
+        let path = fs::read_dir(lib_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| {
+                path.file_name().and_then(|name| name.to_str()).map_or(false, |name| {
+                    name.starts_with(&format!("{DLL_PREFIX}rustc_driver-")) && name.ends_with(DLL_SUFFIX)
+                })
+            })
+            .unwrap();
+
+        let error = load(&path).unwrap_err();
+        assert!(
+            error.contains("the symbol `CLIPPY_LINT_PLUGIN_RUSTC_VERSION` is missing"),
+            "{error}"
+        );
+    }
+}
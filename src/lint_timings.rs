@@ -0,0 +1,210 @@
+//! Measuring the time spent in each lint pass, for `--lint-timings`.
+//!
+//! The registered lint passes are wrapped in a [`TimedPass`] timing every call to the inner pass.
+//! The times are per pass rather than per lint: the lints sharing a pass, like the ones of the
+//! `methods` module, are checked by the same calls, so they are timed together under the name of
+//! the pass.
+
+use rustc_ast as ast;
+use rustc_hir as hir;
+use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintPass, LintStore};
+use rustc_span::symbol::Ident;
+use rustc_span::Span;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::{self, Write as _};
+use std::mem;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The time spent in the lint passes dropped so far.
+static TIMINGS: Mutex<Vec<PassTiming>> = Mutex::new(Vec::new());
+
+struct PassTiming {
+    /// `pre-expansion`, `early` or `late`.
+    kind: &'static str,
+    pass: &'static str,
+    elapsed: Duration,
+}
+
+/// A lint pass measuring the time spent in the methods of `pass`.
+struct TimedPass<P: ?Sized> {
+    kind: &'static str,
+    name: &'static str,
+    pass: Box<P>,
+    elapsed: Duration,
+}
+
+impl<P: ?Sized + LintPass> TimedPass<P> {
+    fn boxed(kind: &'static str, pass: Box<P>) -> Box<Self> {
+        Box::new(Self {
+            kind,
+            name: pass.name(),
+            pass,
+            elapsed: Duration::ZERO,
+        })
+    }
+}
+
+impl<P: ?Sized> Drop for TimedPass<P> {
+    fn drop(&mut self) {
+        TIMINGS.lock().unwrap().push(PassTiming {
+            kind: self.kind,
+            pass: self.name,
+            elapsed: self.elapsed,
+        });
+    }
+}
+
+#[allow(rustc::lint_pass_impl_without_macro)]
+impl<P: ?Sized> LintPass for TimedPass<P> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Implements the methods of `EarlyLintPass` or `LateLintPass`, given by
+/// `rustc_lint::early_lint_methods!` or `rustc_lint::late_lint_methods!`, by timing the same
+/// method of the inner pass.
+macro_rules! timed_methods {
+    ([$context:ty], [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => {
+        $(fn $name(&mut self, cx: &$context, $($param: $arg),*) {
+            let start = Instant::now();
+            self.pass.$name(cx, $($param),*);
+            self.elapsed += start.elapsed();
+        })*
+    };
+}
+
+impl EarlyLintPass for TimedPass<dyn EarlyLintPass> {
+    rustc_lint::early_lint_methods!(timed_methods, [EarlyContext<'_>]);
+}
+
+impl<'tcx> LateLintPass<'tcx> for TimedPass<dyn LateLintPass<'tcx> + 'tcx> {
+    rustc_lint::late_lint_methods!(timed_methods, [LateContext<'tcx>]);
+}
+
+/// Wraps the lint passes registered in `store` to measure the time spent in each of them.
+pub fn time_lint_passes(store: &mut LintStore) {
+    for factory in mem::take(&mut store.pre_expansion_passes) {
+        store.register_pre_expansion_pass(move || TimedPass::boxed("pre-expansion", factory()));
+    }
+    for factory in mem::take(&mut store.early_passes) {
+        store.register_early_pass(move || TimedPass::boxed("early", factory()));
+    }
+    for factory in mem::take(&mut store.late_passes) {
+        store.register_late_pass(move |tcx| TimedPass::boxed("late", factory(tcx)));
+    }
+    for factory in mem::take(&mut store.late_module_passes) {
+        store.register_late_mod_pass(move |tcx| TimedPass::boxed("late", factory(tcx)));
+    }
+}
+
+/// Takes the time spent in each lint pass so far, the longest first. The time of a pass created
+/// several times is summed.
+fn take_timings() -> Vec<PassTiming> {
+    let mut timings: Vec<PassTiming> = Vec::new();
+    for timing in mem::take(&mut *TIMINGS.lock().unwrap()) {
+        match timings
+            .iter_mut()
+            .find(|other| other.kind == timing.kind && other.pass == timing.pass)
+        {
+            Some(other) => other.elapsed += timing.elapsed,
+            None => timings.push(timing),
+        }
+    }
+    timings.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then(a.pass.cmp(b.pass)));
+    timings
+}
+
+/// Prints the time spent in each lint pass while checking `target` to stderr.
+pub fn print_lint_timings(target: &str) {
+    let timings = take_timings();
+    if !timings.is_empty() {
+        eprint!("{}", timings_table(target, &timings));
+    }
+}
+
+/// Appends the time spent in each lint pass while checking `target` to the file at `path`, as a
+/// line of JSON.
+pub fn write_lint_timings(path: &Path, target: &str) -> io::Result<()> {
+    let timings = take_timings();
+    if timings.is_empty() {
+        return Ok(());
+    }
+    let passes = timings
+        .iter()
+        .map(|timing| {
+            json!({
+                "pass": timing.pass,
+                "kind": timing.kind,
+                "nanos": u64::try_from(timing.elapsed.as_nanos()).unwrap_or(u64::MAX),
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut line = json!({ "target": target, "passes": passes }).to_string();
+    line.push('\n');
+    // Cargo runs several instances of `clippy-driver` at once, appending each line with a single
+    // write keeps them apart
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+fn timings_table(target: &str, timings: &[PassTiming]) -> String {
+    let total: Duration = timings.iter().map(|timing| timing.elapsed).sum();
+    let mut table = format!("Time spent in the lint passes while checking `{target}`:\n");
+    writeln!(table, "{:>12}  {:>6}  pass", "time", "share").unwrap();
+    for timing in timings {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            timing.elapsed.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        writeln!(
+            table,
+            "{:>10.3}ms  {share:>5.1}%  {} ({})",
+            timing.elapsed.as_secs_f64() * 1000.0,
+            timing.pass,
+            timing.kind
+        )
+        .unwrap();
+    }
+    writeln!(table, "{:>10.3}ms  100.0%  total", total.as_secs_f64() * 1000.0).unwrap();
+    table.push_str("note: the lints of a pass are timed together\n");
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let timings = [
+            PassTiming {
+                kind: "late",
+                pass: "Methods",
+                elapsed: Duration::from_micros(3000),
+            },
+            PassTiming {
+                kind: "early",
+                pass: "DoubleNeg",
+                elapsed: Duration::from_micros(1000),
+            },
+        ];
+        assert_eq!(
+            timings_table("foo (lib)", &timings),
+            "Time spent in the lint passes while checking `foo (lib)`:\n        \
+             time   share  pass\n     \
+             3.000ms   75.0%  Methods (late)\n     \
+             1.000ms   25.0%  DoubleNeg (early)\n     \
+             4.000ms  100.0%  total\n\
+             note: the lints of a pass are timed together\n"
+        );
+    }
+}
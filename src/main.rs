@@ -1,10 +1,25 @@
+#![feature(rustc_private)]
 #![cfg_attr(feature = "deny-warnings", deny(warnings))]
 // warn on lints, that are included in `rust-lang/rust`s bootstrap
 #![warn(rust_2018_idioms, unused_lifetimes)]
+// warn on rustc internal lints
+#![warn(rustc::internal)]
 
-use std::env;
-use std::path::PathBuf;
-use std::process::{self, Command};
+// FIXME: switch to something more ergonomic here, once available.
+// (Currently there is no way to opt into sysroot crates without `extern crate`.)
+extern crate rustc_data_structures;
+extern crate rustc_lint;
+
+mod explain;
+mod fix;
+mod lint_msrvs;
+mod print_conf;
+mod sarif;
+
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, ExitStatus, Stdio};
+use std::{env, fs};
 
 const CARGO_CLIPPY_HELP: &str = r#"Checks a package to catch common mistakes and improve your Rust code.
 
@@ -14,9 +29,27 @@ Usage:
 Common options:
     --no-deps                Run Clippy only on the given crate, without linting the dependencies
     --fix                    Automatically apply lint suggestions. This flag implies `--no-deps`
+    --fix --interactive      Show each suggestion as a diff and ask whether to apply it
+    --fix-lint LINT          Only apply the suggestions of LINT. This flag implies `--fix`
+    --fix --unsafe-fixes     Also apply the suggestions that may be incorrect, reverting the ones
+                             that break the build
     -h, --help               Print this message
     -V, --version            Print version info and exit
-    --explain LINT           Print the documentation for a given lint
+    --explain LINT           Print the documentation for a given lint, with its group, default
+                             level, applicability, MSRV and configuration. Add `--format json`
+                             to get it as JSON
+    --print-config[=FORMAT]  Print the configuration of each package of the workspace and where
+                             each value comes from, without checking them. FORMAT is `toml`
+                             (the default) or `json`
+    --write-baseline PATH    Record the current warnings in the baseline file at PATH, checking
+                             all the packages of the workspace again
+    --baseline PATH          Only report the warnings that are not recorded in the baseline file
+                             at PATH
+    --lint-cache             Reuse the results of the previous run when only the levels of Clippy
+                             lints changed since
+    --lint-timings[=PATH]    Print the time spent in each lint pass for each checked crate, or
+                             append it to the file at PATH as JSON lines
+    --message-format=sarif   Print the diagnostics as a SARIF 2.1.0 log
 
 Other options are the same as `cargo check`.
 
@@ -55,9 +88,19 @@ pub fn main() {
     }
 
     if let Some(pos) = env::args().position(|a| a == "--explain") {
-        if let Some(mut lint) = env::args().nth(pos + 1) {
+        let mut args = env::args().skip(pos + 1).collect::<Vec<_>>();
+        let format = take_format_arg(&mut args).map_or(explain::ExplainFormat::Text, |format| {
+            explain::ExplainFormat::parse(&format).unwrap_or_else(|| {
+                eprintln!("error: unknown `--format` `{format}`, expected `text` or `json`");
+                process::exit(1);
+            })
+        });
+        if let Some(mut lint) = args.into_iter().next() {
             lint.make_ascii_lowercase();
-            clippy_lints::explain(&lint.strip_prefix("clippy::").unwrap_or(&lint).replace('-', "_"));
+            explain::explain(
+                &lint.strip_prefix("clippy::").unwrap_or(&lint).replace('-', "_"),
+                format,
+            );
         } else {
             show_help();
         }
@@ -69,12 +112,72 @@ pub fn main() {
     }
 }
 
+/// Removes `--format FORMAT` or `--format=FORMAT` from `args`, returning `FORMAT`.
+fn take_format_arg(args: &mut Vec<String>) -> Option<String> {
+    let pos = args
+        .iter()
+        .position(|arg| arg == "--format" || arg.starts_with("--format="))?;
+    let arg = args.remove(pos);
+    match arg.strip_prefix("--format=") {
+        Some(format) => Some(format.to_string()),
+        None if pos < args.len() => Some(args.remove(pos)),
+        None => Some(String::new()),
+    }
+}
+
 struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
     clippy_args: Vec<String>,
+    /// Whether to print the diagnostics as a SARIF log, with `--message-format=sarif`
+    sarif: bool,
+    /// Whether to ask before applying each suggestion, with `--fix --interactive`
+    interactive: bool,
+    /// The lint to apply the suggestions of, with `--fix-lint`
+    fix_lint: Option<String>,
+    /// Whether to apply the suggestions that may be incorrect, with `--fix --unsafe-fixes`
+    unsafe_fixes: bool,
+    /// The format to print the configuration in, with `--print-config`
+    print_config: Option<print_conf::ConfFormat>,
+    /// Whether the warnings are recorded in a baseline file, with `--write-baseline`
+    write_baseline: bool,
+    /// The options of `cargo fix` honored when the suggestions are applied by `cargo-clippy`
+    fix_flags: FixFlags,
 }
 
+/// The options of `cargo fix` that `cargo check` doesn't accept, handled by `cargo-clippy` itself
+/// with `--interactive`, `--fix-lint` and `--unsafe-fixes`.
+const CARGO_FIX_FLAGS: &[&str] = &["--allow-dirty", "--allow-staged", "--allow-no-vcs", "--broken-code"];
+
+/// The values of [`CARGO_FIX_FLAGS`].
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FixFlags {
+    /// Apply the suggestions even if the working directory has uncommitted changes
+    allow_dirty: bool,
+    /// Apply the suggestions even if the working directory has staged changes
+    allow_staged: bool,
+    /// Apply the suggestions even if the package isn't in a version control system
+    allow_no_vcs: bool,
+    /// Apply the suggestions even if the code doesn't compile, before or after them
+    broken_code: bool,
+}
+
+impl FixFlags {
+    /// Removes the options of `cargo fix` from `args` and returns them.
+    fn take(args: &mut Vec<String>) -> Self {
+        let flags = Self {
+            allow_dirty: args.iter().any(|arg| arg == "--allow-dirty"),
+            allow_staged: args.iter().any(|arg| arg == "--allow-staged"),
+            allow_no_vcs: args.iter().any(|arg| arg == "--allow-no-vcs"),
+            broken_code: args.iter().any(|arg| arg == "--broken-code"),
+        };
+        args.retain(|arg| !CARGO_FIX_FLAGS.contains(&arg.as_str()));
+        flags
+    }
+}
+
+const ISSUES_URL: &str = "https://github.com/rust-lang/rust-clippy/issues/new";
+
 impl ClippyCmd {
     fn new<I>(mut old_args: I) -> Self
     where
@@ -83,17 +186,92 @@ impl ClippyCmd {
         let mut cargo_subcommand = "check";
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
+        let mut sarif = false;
+        let mut interactive = false;
+        let mut fix_lint = None;
+        let mut unsafe_fixes = false;
+        let mut print_config = None;
+        let mut write_baseline = false;
+        let mut fix_flags = FixFlags::default();
 
-        for arg in old_args.by_ref() {
+        while let Some(arg) = old_args.next() {
             match arg.as_str() {
                 "--fix" => {
                     cargo_subcommand = "fix";
                     continue;
                 },
+                "--interactive" => {
+                    interactive = true;
+                    continue;
+                },
+                "--unsafe-fixes" => {
+                    unsafe_fixes = true;
+                    continue;
+                },
+                "--fix-lint" => {
+                    let Some(lint) = old_args.next() else {
+                        eprintln!("error: `--fix-lint` expects a lint name");
+                        process::exit(1);
+                    };
+                    fix_lint = Some(lint);
+                    continue;
+                },
+                _ if arg.starts_with("--fix-lint=") => {
+                    fix_lint = arg.strip_prefix("--fix-lint=").map(String::from);
+                    continue;
+                },
                 "--no-deps" => {
                     clippy_args.push("--no-deps".into());
                     continue;
                 },
+                "--lint-cache" | "--lint-timings" => {
+                    clippy_args.push(arg);
+                    continue;
+                },
+                _ if arg.starts_with("--lint-timings=") => {
+                    let (flag, path) = arg.split_once('=').unwrap();
+                    clippy_args.push(path_arg(flag, path));
+                    continue;
+                },
+                _ if arg == "--print-config" || arg.starts_with("--print-config=") => {
+                    let format = arg.strip_prefix("--print-config=").unwrap_or("toml");
+                    print_config = Some(print_conf::ConfFormat::parse(format).unwrap_or_else(|| {
+                        eprintln!("error: unknown `--print-config` format `{format}`, expected `toml` or `json`");
+                        process::exit(1);
+                    }));
+                    continue;
+                },
+                "--baseline" | "--write-baseline" => {
+                    write_baseline |= arg == "--write-baseline";
+                    let Some(path) = old_args.next() else {
+                        eprintln!("error: `{arg}` expects a path");
+                        process::exit(1);
+                    };
+                    clippy_args.push(path_arg(&arg, &path));
+                    continue;
+                },
+                _ if arg.starts_with("--baseline=") || arg.starts_with("--write-baseline=") => {
+                    write_baseline |= arg.starts_with("--write-baseline=");
+                    let (flag, path) = arg.split_once('=').unwrap();
+                    clippy_args.push(path_arg(flag, path));
+                    continue;
+                },
+                // the SARIF log is made from the JSON diagnostics
+                "--message-format=sarif" => {
+                    sarif = true;
+                    args.push("--message-format=json".into());
+                    continue;
+                },
+                "--message-format" => {
+                    let format = old_args.next().unwrap_or_default();
+                    if format == "sarif" {
+                        sarif = true;
+                        args.push("--message-format=json".into());
+                    } else {
+                        args.push(format!("--message-format={format}"));
+                    }
+                    continue;
+                },
                 "--" => break,
                 _ => {},
             }
@@ -102,14 +280,45 @@ impl ClippyCmd {
         }
 
         clippy_args.append(&mut (old_args.collect()));
+        if fix_lint.is_some() {
+            cargo_subcommand = "fix";
+        }
+        for (flag, set) in [("--interactive", interactive), ("--unsafe-fixes", unsafe_fixes)] {
+            if set && cargo_subcommand != "fix" {
+                eprintln!("error: `{flag}` can only be used with `--fix`");
+                process::exit(1);
+            }
+        }
+        if interactive && unsafe_fixes {
+            eprintln!("error: `--interactive` and `--unsafe-fixes` can't be used together");
+            process::exit(1);
+        }
         if cargo_subcommand == "fix" && !clippy_args.iter().any(|arg| arg == "--no-deps") {
             clippy_args.push("--no-deps".into());
         }
 
+        // The suggestions are applied by `cargo-clippy` rather than `cargo fix`
+        if interactive || unsafe_fixes || fix_lint.is_some() {
+            if sarif {
+                eprintln!("error: `--message-format=sarif` can't be used with `--fix`");
+                process::exit(1);
+            }
+            cargo_subcommand = "check";
+            fix_flags = FixFlags::take(&mut args);
+            args.push("--message-format=json".into());
+        }
+
         Self {
             cargo_subcommand,
             args,
             clippy_args,
+            sarif,
+            interactive,
+            fix_lint,
+            unsafe_fixes,
+            print_config,
+            write_baseline,
+            fix_flags,
         }
     }
 
@@ -146,11 +355,30 @@ impl ClippyCmd {
     }
 }
 
+/// Returns the argument passing a path to `clippy-driver`. The path is made absolute since
+/// `clippy-driver` doesn't necessarily run in the current directory.
+fn path_arg(flag: &str, path: &str) -> String {
+    let path = env::current_dir().map_or_else(|_| PathBuf::from(path), |dir| dir.join(path));
+    format!("{flag}={}", path.display())
+}
+
 fn process<I>(old_args: I) -> Result<(), i32>
 where
     I: Iterator<Item = String>,
 {
     let cmd = ClippyCmd::new(old_args);
+    if let Some(format) = cmd.print_config {
+        return print_config(&cmd.args, format);
+    }
+    if cmd.write_baseline {
+        clean_workspace_packages(&cmd.args)?;
+    }
+    if cmd.sarif {
+        return process_sarif(cmd);
+    }
+    if cmd.interactive || cmd.unsafe_fixes || cmd.fix_lint.is_some() {
+        return process_fixes(cmd);
+    }
 
     let mut cmd = cmd.into_std_cmd();
 
@@ -167,9 +395,443 @@ where
     }
 }
 
+/// Prints the configuration of the packages of the workspace, read from the output of `cargo
+/// metadata` rather than by checking them, so that it's printed once for the whole workspace even
+/// when the packages are already checked.
+fn print_config(args: &[String], format: print_conf::ConfFormat) -> Result<(), i32> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["metadata", "--no-deps", "--format-version", "1"]);
+    cmd.args(manifest_path_args(args));
+    let output = cmd.stderr(Stdio::inherit()).output().expect("could not run cargo");
+    if !output.status.success() {
+        return Err(output.status.code().unwrap_or(-1));
+    }
+    if print_conf::print_workspace_conf(&String::from_utf8_lossy(&output.stdout), format) {
+        Ok(())
+    } else {
+        Err(1)
+    }
+}
+
+/// Removes the check results of the packages of the workspace, so that they are all checked again
+/// and `--write-baseline` records their warnings. Cargo only runs Clippy on the packages that
+/// changed otherwise, replaying the warnings of the others without recording them.
+fn clean_workspace_packages(args: &[String]) -> Result<(), i32> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["metadata", "--no-deps", "--format-version", "1"]);
+    cmd.args(manifest_path_args(args));
+    let output = cmd.stderr(Stdio::inherit()).output().expect("could not run cargo");
+    if !output.status.success() {
+        return Err(output.status.code().unwrap_or(-1));
+    }
+    let Some(packages) = workspace_packages(&String::from_utf8_lossy(&output.stdout)) else {
+        eprintln!("error: failed to read the output of `cargo metadata`");
+        return Err(1);
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.arg("clean").args(clean_args(args));
+    for package in &packages {
+        cmd.args(["--package", package]);
+    }
+    let status = cmd.status().expect("could not run cargo");
+    if status.success() {
+        Ok(())
+    } else {
+        Err(status.code().unwrap_or(-1))
+    }
+}
+
+/// Returns the `name@version` specs of the packages of the workspace described by `metadata`, the
+/// output of `cargo metadata --no-deps --format-version 1`, or `None` if it can't be read.
+fn workspace_packages(metadata: &str) -> Option<Vec<String>> {
+    let metadata: serde_json::Value = serde_json::from_str(metadata).ok()?;
+    metadata["packages"]
+        .as_array()?
+        .iter()
+        .map(|package| {
+            Some(format!(
+                "{}@{}",
+                package["name"].as_str()?,
+                package["version"].as_str()?
+            ))
+        })
+        .collect()
+}
+
+/// Returns the options in `args` selecting the artifacts that `cargo clean` removes.
+fn clean_args(args: &[String]) -> Vec<String> {
+    const FLAGS: &[&str] = &["--release"];
+    const OPTIONS: &[&str] = &["--manifest-path", "--target-dir", "--target", "--profile"];
+
+    let mut clean_args = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if FLAGS.contains(&arg.as_str())
+            || OPTIONS
+                .iter()
+                .any(|option| arg.strip_prefix(option).map_or(false, |rest| rest.starts_with('=')))
+        {
+            clean_args.push(arg.clone());
+        } else if OPTIONS.contains(&arg.as_str()) {
+            clean_args.push(arg.clone());
+            clean_args.extend(args.next().cloned());
+        }
+    }
+    clean_args
+}
+
+/// Runs Cargo with `--message-format=json`, printing the rendered diagnostics to stderr and a
+/// SARIF log of them to stdout.
+fn process_sarif(cmd: ClippyCmd) -> Result<(), i32> {
+    let root = workspace_root(&cmd.args);
+    let mut child = cmd
+        .into_std_cmd()
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("could not run cargo");
+
+    let mut log = sarif::SarifLog::default();
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.expect("failed to read the output of cargo");
+        if let Some(rendered) = log.add_cargo_message(&line) {
+            eprint!("{rendered}");
+        }
+    }
+    let exit_status = child.wait().expect("failed to wait for cargo?");
+
+    let version_info = rustc_tools_util::get_version_info!();
+    let version = format!("{}.{}.{}", version_info.major, version_info.minor, version_info.patch);
+    println!("{}", log.to_json(&version, root.as_deref()));
+
+    if exit_status.success() {
+        Ok(())
+    } else {
+        Err(exit_status.code().unwrap_or(-1))
+    }
+}
+
+/// Runs Cargo with `--message-format=json` and applies the suggestions of the warnings, only the
+/// ones of `--fix-lint` if given, after asking the user with `--interactive`, and checking that
+/// they compile with `--unsafe-fixes`.
+fn process_fixes(cmd: ClippyCmd) -> Result<(), i32> {
+    let root = workspace_root(&cmd.args).unwrap_or_default();
+    check_version_control(&root, &cmd.fix_flags)?;
+    let interactive = cmd.interactive;
+    let unsafe_fixes = cmd.unsafe_fixes;
+    let broken_code = cmd.fix_flags.broken_code;
+    let fix_lint = cmd.fix_lint.clone();
+    let new_fix_set = || fix::FixSet::new(fix_lint.as_deref(), unsafe_fixes);
+    let mut cargo = cmd.into_std_cmd();
+
+    let mut fixes = new_fix_set();
+    let exit_status = run_cargo_for_fixes(&mut cargo, &mut fixes, true);
+    if !exit_status.success() && !broken_code {
+        eprintln!("error: not applying the suggestions as the code doesn't compile");
+        eprintln!("note: pass `--broken-code` to apply them anyway");
+        return Err(exit_status.code().unwrap_or(-1));
+    }
+    if unsafe_fixes {
+        return apply_checked_fixes(&mut cargo, fixes, &root, broken_code, new_fix_set);
+    }
+
+    let selected = if interactive {
+        review_fixes(fixes.fixes(), &root)
+    } else {
+        fixes.fixes().iter().collect()
+    };
+    match fix::apply_fixes(&selected, &root) {
+        Ok(skipped) => {
+            for fix in &skipped {
+                eprintln!(
+                    "warning: skipped a suggestion of `{}` changing the same code as another one",
+                    fix.lint
+                );
+            }
+            eprintln!("Applied {} suggestions", selected.len() - skipped.len());
+            if !skipped.is_empty() {
+                eprintln!("Run the command again to apply the skipped suggestions");
+            }
+            Ok(())
+        },
+        Err(e) => {
+            eprintln!("error: failed to apply the suggestions: {e}");
+            Err(1)
+        },
+    }
+}
+
+/// Runs Cargo and adds the suggestions of the diagnostics it prints to `fixes`. The diagnostics
+/// without suggestions are printed if `print` is set.
+fn run_cargo_for_fixes(cargo: &mut Command, fixes: &mut fix::FixSet, print: bool) -> ExitStatus {
+    let mut child = cargo.stdout(Stdio::piped()).spawn().expect("could not run cargo");
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.expect("failed to read the output of cargo");
+        if let Some(rendered) = fixes.add_cargo_message(&line).filter(|_| print) {
+            eprint!("{rendered}");
+        }
+    }
+    child.wait().expect("failed to wait for cargo?")
+}
+
+/// Checks that the suggestions can be applied to the files of the workspace at `root` like `cargo
+/// fix` does: they must be in a Git repository without uncommitted changes, unless allowed by
+/// `flags`.
+fn check_version_control(root: &Path, flags: &FixFlags) -> Result<(), i32> {
+    if flags.allow_dirty && flags.allow_no_vcs {
+        return Ok(());
+    }
+    let in_repository = Command::new("git")
+        .current_dir(root)
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .stderr(Stdio::null())
+        .output()
+        .map_or(false, |output| {
+            output.status.success() && output.stdout.starts_with(b"true")
+        });
+    if !in_repository {
+        if flags.allow_no_vcs {
+            return Ok(());
+        }
+        eprintln!(
+            "error: no VCS found for this package and `cargo clippy --fix` can potentially perform \
+            destructive changes; if you'd like to suppress this error pass `--allow-no-vcs`"
+        );
+        return Err(1);
+    }
+    if flags.allow_dirty {
+        return Ok(());
+    }
+
+    let status = Command::new("git")
+        .current_dir(root)
+        .args(["status", "--porcelain", "-z", "--untracked-files=all", "."])
+        .output()
+        .map_err(|e| {
+            eprintln!("error: failed to run `git status`: {e}");
+            1
+        })?;
+    let files = uncommitted_files(&String::from_utf8_lossy(&status.stdout), flags.allow_staged);
+    if files.is_empty() {
+        return Ok(());
+    }
+    eprintln!(
+        "error: the working directory of this package has uncommitted changes, and `cargo clippy --fix` \
+        can potentially perform destructive changes; if you'd like to suppress this error pass \
+        `--allow-dirty`, `--allow-staged`, or commit the changes to these files:\n"
+    );
+    for (path, state) in files {
+        eprintln!("  * {path} ({state})");
+    }
+    Err(1)
+}
+
+/// Returns the files with uncommitted changes in the output of `git status --porcelain -z`, with
+/// whether they are `dirty` or `staged`. A file with changes both in the index and the working
+/// tree is dirty.
+fn uncommitted_files(status: &str, allow_staged: bool) -> Vec<(&str, &'static str)> {
+    let mut files = Vec::new();
+    let mut entries = status.split_terminator('\0');
+    while let Some(entry) = entries.next() {
+        let (Some(states), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        let mut states = states.chars();
+        let (index, worktree) = (states.next().unwrap(), states.next().unwrap());
+        // A renamed or copied file is followed by its original path
+        if matches!(index, 'R' | 'C') {
+            entries.next();
+        }
+        if worktree != ' ' {
+            files.push((path, "dirty"));
+        } else if !allow_staged {
+            files.push((path, "staged"));
+        }
+    }
+    files
+}
+
+/// Applies the suggestions one lint at a time, running Cargo again after each lint and reverting
+/// its suggestions if the code doesn't compile anymore, unless `broken_code` is set.
+fn apply_checked_fixes(
+    cargo: &mut Command,
+    mut fixes: fix::FixSet,
+    root: &Path,
+    broken_code: bool,
+    new_fix_set: impl Fn() -> fix::FixSet,
+) -> Result<(), i32> {
+    let mut checked_lints: Vec<String> = Vec::new();
+    let mut applied = 0;
+    let mut skipped = 0;
+    // The lints whose suggestions were reverted, with the suggestions and the errors they caused
+    let mut reverted = Vec::new();
+    while let Some(lint) = fixes
+        .fixes()
+        .iter()
+        .map(|fix| &fix.lint)
+        .find(|lint| !checked_lints.contains(lint))
+        .cloned()
+    {
+        let batch = fixes.fixes().iter().filter(|fix| fix.lint == lint).collect::<Vec<_>>();
+        let suggestions = batch
+            .iter()
+            .map(|fix| format!("{}: {}", fix.location(), fix.help))
+            .collect::<Vec<_>>();
+        let backup = backup_files(&batch, root).map_err(|e| {
+            eprintln!("error: failed to apply the suggestions: {e}");
+            1
+        })?;
+        let batch_len = batch.len();
+        let batch_skipped = match fix::apply_fixes(&batch, root) {
+            Ok(batch_skipped) => batch_skipped.len(),
+            Err(e) => {
+                eprintln!("error: failed to apply the suggestions of `{lint}`: {e}");
+                // Some of the files may have been changed already
+                let _ = restore_files(&backup);
+                return Err(1);
+            },
+        };
+
+        eprintln!("Checking the suggestions of `{lint}`");
+        let mut checked = new_fix_set();
+        let compiles = run_cargo_for_fixes(cargo, &mut checked, false).success();
+        if compiles || broken_code {
+            if !compiles {
+                eprintln!("warning: kept the suggestions of `{lint}` with `--broken-code`, the code doesn't compile");
+            }
+            applied += batch_len - batch_skipped;
+            skipped += batch_skipped;
+            fixes = checked;
+        } else {
+            restore_files(&backup).map_err(|e| {
+                eprintln!("error: failed to revert the suggestions of `{lint}`: {e}");
+                1
+            })?;
+            reverted.push((lint.clone(), suggestions, checked.errors().to_vec()));
+        }
+        checked_lints.push(lint);
+    }
+
+    eprintln!("Applied {applied} suggestions");
+    if skipped > 0 {
+        eprintln!("Run the command again to apply the {skipped} suggestions changing the same code as others");
+    }
+    for (lint, suggestions, errors) in &reverted {
+        eprintln!("\nwarning: reverted the suggestions of `{lint}`, the code doesn't compile with them:");
+        for suggestion in suggestions {
+            eprintln!("    {suggestion}");
+        }
+        eprintln!("The errors were:");
+        for error in errors {
+            eprint!("{error}");
+        }
+    }
+    if !reverted.is_empty() {
+        eprintln!("\nnote: these suggestions are bugs, please report them at {ISSUES_URL}");
+    }
+    Ok(())
+}
+
+/// Returns the files changed by `fixes` with their contents, to restore them with
+/// [`restore_files`].
+fn backup_files(fixes: &[&fix::Fix], root: &Path) -> io::Result<Vec<(PathBuf, String)>> {
+    let mut paths = fixes
+        .iter()
+        .flat_map(|fix| fix.files())
+        .map(|file| root.join(file))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    paths
+        .into_iter()
+        .map(|path| {
+            let content = fs::read_to_string(&path)?;
+            Ok((path, content))
+        })
+        .collect()
+}
+
+fn restore_files(backup: &[(PathBuf, String)]) -> io::Result<()> {
+    for (path, content) in backup {
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+/// Shows each fix as a diff and asks whether to apply it, returning the accepted fixes.
+fn review_fixes<'a>(fixes: &'a [fix::Fix], root: &Path) -> Vec<&'a fix::Fix> {
+    let mut accepted: Vec<&fix::Fix> = Vec::new();
+    // The lints whose fixes are all accepted
+    let mut accepted_lints: Vec<&str> = Vec::new();
+    for (i, fix) in fixes.iter().enumerate() {
+        if accepted.iter().any(|accepted| accepted.overlaps(fix)) {
+            continue;
+        }
+        if accepted_lints.contains(&fix.lint.as_str()) {
+            accepted.push(fix);
+            continue;
+        }
+        let diff = match fix.diff(root) {
+            Ok(diff) => diff,
+            Err(e) => {
+                eprintln!("warning: skipped a suggestion of `{}`: {e}", fix.lint);
+                continue;
+            },
+        };
+        eprintln!("\n[{}/{}] {}: {}", i + 1, fixes.len(), fix.lint, fix.message);
+        eprintln!("help: {}\n{diff}", fix.help);
+        loop {
+            eprint!(
+                "Apply this suggestion? [y]es, [n]o, [a]ll suggestions of `{}`, [q]uit: ",
+                fix.lint
+            );
+            let mut answer = String::new();
+            // Stop at the end of the input
+            if io::stdin().read_line(&mut answer).map_or(true, |read| read == 0) {
+                return accepted;
+            }
+            match answer.trim() {
+                "y" | "yes" => accepted.push(fix),
+                "n" | "no" => {},
+                "a" | "all" => {
+                    accepted_lints.push(&fix.lint);
+                    accepted.push(fix);
+                },
+                "q" | "quit" => return accepted,
+                _ => continue,
+            }
+            break;
+        }
+    }
+    accepted
+}
+
+/// Returns the root of the workspace checked with the Cargo arguments `args`, which the paths of
+/// the diagnostics are relative to.
+fn workspace_root(args: &[String]) -> Option<PathBuf> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["locate-project", "--workspace", "--message-format=plain"]);
+    cmd.args(manifest_path_args(args));
+    let output = cmd.stderr(Stdio::null()).output().ok()?;
+    let manifest = String::from_utf8(output.stdout).ok()?;
+    let root = PathBuf::from(manifest.trim_end()).parent()?.to_path_buf();
+    output.status.success().then_some(root)
+}
+
+/// Returns the `--manifest-path` option in `args`, to pass it on to other Cargo commands.
+fn manifest_path_args(args: &[String]) -> &[String] {
+    if let Some(pos) = args.iter().position(|arg| arg == "--manifest-path") {
+        args.get(pos..=pos + 1).unwrap_or_default()
+    } else if let Some(pos) = args.iter().position(|arg| arg.starts_with("--manifest-path=")) {
+        &args[pos..=pos]
+    } else {
+        &[]
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ClippyCmd;
+    use super::{clean_args, manifest_path_args, take_format_arg, ClippyCmd, FixFlags};
 
     #[test]
     fn fix() {
@@ -195,6 +857,166 @@ mod tests {
         assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
     }
 
+    #[test]
+    fn print_config() {
+        let args = "cargo clippy --print-config=json"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(Some(print_conf::ConfFormat::Json), cmd.print_config);
+        assert!(!cmd.clippy_args.iter().any(|arg| arg.starts_with("--print-config")));
+        assert!(!cmd.args.iter().any(|arg| arg.starts_with("--print-config")));
+    }
+
+    #[test]
+    fn manifest_path() {
+        let args = |args: &str| args.split_whitespace().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            manifest_path_args(&args("--all-targets --manifest-path foo/Cargo.toml")),
+            args("--manifest-path foo/Cargo.toml")
+        );
+        assert_eq!(
+            manifest_path_args(&args("--manifest-path=foo/Cargo.toml --all-targets")),
+            args("--manifest-path=foo/Cargo.toml")
+        );
+        assert!(manifest_path_args(&args("--all-targets")).is_empty());
+    }
+
+    #[test]
+    fn baseline_paths_are_absolute() {
+        let args = "cargo clippy --write-baseline a.json --baseline=b.json --all-targets"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        let current_dir = std::env::current_dir().unwrap();
+        let write_baseline = format!("--write-baseline={}", current_dir.join("a.json").display());
+        let baseline = format!("--baseline={}", current_dir.join("b.json").display());
+        assert!(cmd.clippy_args.contains(&write_baseline));
+        assert!(cmd.clippy_args.contains(&baseline));
+        assert!(
+            !cmd.args
+                .iter()
+                .any(|arg| arg.contains("baseline") || arg.ends_with(".json"))
+        );
+        assert!(cmd.write_baseline);
+    }
+
+    #[test]
+    fn clean_args_select_artifacts() {
+        let args = "--all-targets --target-dir out --release -p foo --target=x86_64-unknown-linux-gnu --locked"
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            clean_args(&args),
+            ["--target-dir", "out", "--release", "--target=x86_64-unknown-linux-gnu"]
+        );
+    }
+
+    #[test]
+    fn lint_timings_are_passed_to_driver() {
+        let args = "cargo clippy --lint-timings=timings.jsonl"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        let current_dir = std::env::current_dir().unwrap();
+        let lint_timings = format!("--lint-timings={}", current_dir.join("timings.jsonl").display());
+        assert_eq!(cmd.clippy_args, [lint_timings]);
+        assert!(!cmd.args.iter().any(|arg| arg.contains("timings")));
+    }
+
+    #[test]
+    fn sarif_uses_json_diagnostics() {
+        for args in [
+            "cargo clippy --message-format=sarif",
+            "cargo clippy --message-format sarif",
+        ] {
+            let cmd = ClippyCmd::new(args.split_whitespace().map(ToString::to_string));
+            assert!(cmd.sarif);
+            assert!(cmd.args.iter().any(|arg| arg == "--message-format=json"));
+            assert!(!cmd.args.iter().any(|arg| arg.contains("sarif")));
+        }
+
+        let args = "cargo clippy --message-format short"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(!cmd.sarif);
+        assert!(cmd.args.iter().any(|arg| arg == "--message-format=short"));
+    }
+
+    #[test]
+    fn fix_lint_applies_suggestions_itself() {
+        let args = "cargo clippy --fix-lint needless-return --allow-dirty"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!("check", cmd.cargo_subcommand);
+        assert_eq!(cmd.fix_lint.as_deref(), Some("needless-return"));
+        assert!(cmd.clippy_args.iter().any(|arg| arg == "--no-deps"));
+        assert!(cmd.args.iter().any(|arg| arg == "--message-format=json"));
+        assert!(!cmd.args.iter().any(|arg| arg == "--allow-dirty"));
+        assert_eq!(
+            cmd.fix_flags,
+            FixFlags {
+                allow_dirty: true,
+                ..FixFlags::default()
+            }
+        );
+
+        let args = "cargo clippy --fix --interactive --fix-lint=unused_mut"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.interactive);
+        assert_eq!(cmd.fix_lint.as_deref(), Some("unused_mut"));
+
+        let args = "cargo clippy --fix --unsafe-fixes"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.unsafe_fixes);
+        assert_eq!("check", cmd.cargo_subcommand);
+
+        // `cargo fix` checks the options itself
+        let args = "cargo clippy --fix --allow-staged --broken-code"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!("fix", cmd.cargo_subcommand);
+        assert!(cmd.args.iter().any(|arg| arg == "--allow-staged"));
+        assert_eq!(cmd.fix_flags, FixFlags::default());
+    }
+
+    #[test]
+    fn uncommitted_files() {
+        let status = " M src/lib.rs\0M  src/main.rs\0MM build.rs\0?? new.rs\0R  renamed.rs\0old.rs\0";
+        assert_eq!(
+            super::uncommitted_files(status, false),
+            [
+                ("src/lib.rs", "dirty"),
+                ("src/main.rs", "staged"),
+                ("build.rs", "dirty"),
+                ("new.rs", "dirty"),
+                ("renamed.rs", "staged"),
+            ]
+        );
+        assert_eq!(
+            super::uncommitted_files(status, true),
+            [("src/lib.rs", "dirty"), ("build.rs", "dirty"), ("new.rs", "dirty")]
+        );
+        assert!(super::uncommitted_files("", false).is_empty());
+    }
+
+    #[test]
+    fn explain_format() {
+        for args in ["--format json needless_return", "needless_return --format=json"] {
+            let mut args = args.split_whitespace().map(ToString::to_string).collect::<Vec<_>>();
+            assert_eq!(take_format_arg(&mut args).as_deref(), Some("json"));
+            assert_eq!(args, ["needless_return"]);
+        }
+    }
+
     #[test]
     fn check() {
         let args = "cargo clippy".split_whitespace().map(ToString::to_string);
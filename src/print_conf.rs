@@ -0,0 +1,343 @@
+//! Prints the configuration in effect for the packages of a workspace, for `--print-config`.
+
+use clippy_lints::conf::{
+    lookup_conf_file_from, lookup_inherited_conf_files, read, value_sources, Conf, ConfError, TryConf,
+};
+use clippy_utils::msrvs::{Msrv, MsrvSource};
+use rustc_semver::RustcVersion;
+use serde_json::{json, Value};
+use std::env;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// The output formats of `--print-config`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfFormat {
+    Toml,
+    Json,
+}
+
+impl ConfFormat {
+    /// Parses the `FORMAT` of `--print-config=FORMAT`.
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The configuration in effect for a package, with the origin of each value.
+struct ResolvedConf {
+    /// The configuration file followed by the files it inherits from.
+    files: Vec<PathBuf>,
+    msrv: Option<String>,
+    msrv_source: MsrvSource,
+    /// The configuration keys with their values and the file setting them, `None` for defaults.
+    values: Vec<(String, Value, Option<PathBuf>)>,
+}
+
+/// Prints the configuration in effect for each package of the workspace described by `metadata`,
+/// the output of `cargo metadata --no-deps --format-version 1`, as a single document. The
+/// configuration files are looked up like `clippy-driver` does, so that nothing has to be
+/// compiled.
+///
+/// Returns `false` if the metadata or a configuration file can't be read, the errors are printed
+/// to stderr.
+pub fn print_workspace_conf(metadata: &str, format: ConfFormat) -> bool {
+    let metadata: Value = match serde_json::from_str(metadata) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            eprintln!("error: failed to read the output of `cargo metadata`: {e}");
+            return false;
+        },
+    };
+    let mut success = true;
+    let mut packages = Vec::new();
+    for package in metadata["packages"].as_array().map_or(&[][..], Vec::as_slice) {
+        let (Some(name), Some(manifest_dir)) = (
+            package["name"].as_str(),
+            package["manifest_path"].as_str().and_then(|path| Path::new(path).parent()),
+        ) else {
+            continue;
+        };
+        let (resolved, ok) = resolve_conf(manifest_dir, package["rust_version"].as_str());
+        success &= ok;
+        packages.push((name.to_string(), resolved));
+    }
+    packages.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let root = metadata["workspace_root"].as_str().unwrap_or(".");
+    match format {
+        ConfFormat::Toml => print!("{}", workspace_toml(root, &packages)),
+        ConfFormat::Json => println!("{}", workspace_json(root, &packages)),
+    }
+    success
+}
+
+/// Reads the configuration of the package in `manifest_dir`, whose `Cargo.toml` sets
+/// `rust_version`. Returns `false` with it if the configuration has errors, which are printed to
+/// stderr.
+fn resolve_conf(manifest_dir: &Path, rust_version: Option<&str>) -> (ResolvedConf, bool) {
+    let dir = env::var_os("CLIPPY_CONF_DIR").map_or_else(|| manifest_dir.to_path_buf(), PathBuf::from);
+    let mut success = true;
+    let path = match lookup_conf_file_from(dir) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("error: error finding Clippy's configuration file: {e}");
+            success = false;
+            None
+        },
+    };
+
+    let conf = match &path {
+        Some(path) => {
+            // the unknown lints can be the lints of the lint plugins, which are only loaded by
+            // `clippy-driver`
+            let TryConf {
+                conf, errors, warnings, ..
+            } = read(path);
+            let file = |error: &ConfError| error.file.clone().unwrap_or_else(|| path.clone());
+            for error in &errors {
+                let file = file(error);
+                eprintln!(
+                    "error: error reading Clippy's configuration file `{}`: {error}",
+                    file.display()
+                );
+            }
+            for warning in &warnings {
+                let file = file(warning);
+                eprintln!(
+                    "warning: in Clippy's configuration file `{}`: {warning}",
+                    file.display()
+                );
+            }
+            success &= errors.is_empty();
+            conf
+        },
+        None => Conf::default(),
+    };
+    if let Some(msrv) = &conf.msrv
+        && RustcVersion::parse(msrv).is_err()
+    {
+        eprintln!("error: error reading Clippy's configuration file. `{msrv}` is not a valid Rust version");
+        success = false;
+    }
+
+    let mut sources = path.as_deref().map(value_sources).unwrap_or_default();
+    let mut values = conf
+        .values()
+        .into_iter()
+        .map(|(name, value)| {
+            let source = sources.remove(&name);
+            (name, value, source)
+        })
+        .collect::<Vec<_>>();
+    values.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+    let (msrv, msrv_source) = Msrv::initial(conf.msrv.as_deref(), rust_version);
+    let resolved = ResolvedConf {
+        files: path.as_deref().map(lookup_inherited_conf_files).unwrap_or_default(),
+        msrv: msrv.map(|version| version.to_string()),
+        msrv_source,
+        values,
+    };
+    (resolved, success)
+}
+
+/// Returns the configuration of the `packages` of the workspace at `root` as a TOML document, with
+/// a `[packages.<name>]` table for each package.
+fn workspace_toml(root: &str, packages: &[(String, ResolvedConf)]) -> String {
+    let mut out = format!("# Clippy configuration of the workspace at {root}\n");
+    for (name, resolved) in packages {
+        out.push_str("\n[packages.");
+        write_toml_key(&mut out, name);
+        out.push_str("]\n");
+        out.push_str(&resolved.to_toml());
+    }
+    out
+}
+
+/// Returns the configuration of the `packages` of the workspace at `root` as a JSON document.
+fn workspace_json(root: &str, packages: &[(String, ResolvedConf)]) -> String {
+    let packages = packages
+        .iter()
+        .map(|(name, resolved)| (name.clone(), resolved.to_json()))
+        .collect::<serde_json::Map<_, _>>();
+    let output = json!({
+        "workspace_root": root,
+        "packages": packages,
+    });
+    serde_json::to_string_pretty(&output).unwrap()
+}
+
+impl ResolvedConf {
+    fn to_toml(&self) -> String {
+        let mut out = String::new();
+        match self.files.split_first() {
+            Some((file, inherited)) => {
+                writeln!(out, "# configuration file: {}", file.display()).unwrap();
+                for file in inherited {
+                    writeln!(out, "# inherits from: {}", file.display()).unwrap();
+                }
+            },
+            None => out.push_str("# no configuration file found, using the default configuration\n"),
+        }
+        match (&self.msrv, self.msrv_source) {
+            (Some(msrv), MsrvSource::ClippyToml) => writeln!(out, "# MSRV: {msrv} (from `msrv` in clippy.toml)"),
+            (Some(msrv), _) => writeln!(out, "# MSRV: {msrv} (from `rust-version` in Cargo.toml)"),
+            (None, _) => writeln!(out, "# MSRV: not set"),
+        }
+        .unwrap();
+
+        for (name, value, source) in &self.values {
+            match source {
+                Some(file) => writeln!(out, "\n# from {}", file.display()).unwrap(),
+                None => out.push_str("\n# default\n"),
+            }
+            if value.is_null() {
+                writeln!(out, "# `{name}` is not set").unwrap();
+            } else {
+                write!(out, "{name} = ").unwrap();
+                write_toml_value(&mut out, value);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    fn to_json(&self) -> Value {
+        let msrv_source = match self.msrv_source {
+            MsrvSource::ClippyToml => Some("clippy.toml"),
+            MsrvSource::CargoToml => Some("Cargo.toml"),
+            MsrvSource::Unset => None,
+        };
+        let values = self
+            .values
+            .iter()
+            .map(|(name, value, source)| {
+                let source = source
+                    .as_ref()
+                    .map_or_else(|| json!("default"), |file| json!(file.display().to_string()));
+                (name.clone(), json!({ "value": value, "source": source }))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        json!({
+            "config_file": self.files.first(),
+            "inherited_files": self.files.get(1..).unwrap_or_default(),
+            "msrv": { "version": self.msrv, "source": msrv_source },
+            "values": values,
+        })
+    }
+}
+
+/// Writes `value` as an inline TOML value.
+fn write_toml_value(out: &mut String, value: &Value) {
+    match value {
+        // `None` values are left out of tables and arrays
+        Value::Null => {},
+        Value::Bool(value) => write!(out, "{value}").unwrap(),
+        Value::Number(value) => write!(out, "{value}").unwrap(),
+        // the escapes of JSON strings are all valid in TOML basic strings
+        Value::String(_) => write!(out, "{value}").unwrap(),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().filter(|value| !value.is_null()).enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_toml_value(out, value);
+            }
+            out.push(']');
+        },
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, value)) in map.iter().filter(|(_, value)| !value.is_null()).enumerate() {
+                out.push_str(if i > 0 { ", " } else { " " });
+                write_toml_key(out, key);
+                out.push_str(" = ");
+                write_toml_value(out, value);
+            }
+            out.push_str(if map.is_empty() { "}" } else { " }" });
+        },
+    }
+}
+
+/// Writes `key` as a TOML key, quoted if it isn't a bare key.
+fn write_toml_key(out: &mut String, key: &str) {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        out.push_str(key);
+    } else {
+        write!(out, "{}", Value::String(key.to_string())).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn packages() -> Vec<(String, ResolvedConf)> {
+        let conf = |files: Vec<PathBuf>, msrv: Option<&str>, msrv_source, source: Option<&str>| ResolvedConf {
+            files,
+            msrv: msrv.map(String::from),
+            msrv_source,
+            values: vec![(
+                String::from("too-many-lines-threshold"),
+                json!(80),
+                source.map(PathBuf::from),
+            )],
+        };
+        vec![
+            (
+                String::from("app"),
+                conf(
+                    vec![PathBuf::from("/ws/app/clippy.toml"), PathBuf::from("/ws/clippy.toml")],
+                    Some("1.60.0"),
+                    MsrvSource::CargoToml,
+                    Some("/ws/clippy.toml"),
+                ),
+            ),
+            (String::from("core.rs"), conf(Vec::new(), None, MsrvSource::Unset, None)),
+        ]
+    }
+
+    #[test]
+    fn toml_document() {
+        assert_eq!(
+            workspace_toml("/ws", &packages()),
+            "# Clippy configuration of the workspace at /ws\n\
+             \n\
+             [packages.app]\n\
+             # configuration file: /ws/app/clippy.toml\n\
+             # inherits from: /ws/clippy.toml\n\
+             # MSRV: 1.60.0 (from `rust-version` in Cargo.toml)\n\
+             \n\
+             # from /ws/clippy.toml\n\
+             too-many-lines-threshold = 80\n\
+             \n\
+             [packages.\"core.rs\"]\n\
+             # no configuration file found, using the default configuration\n\
+             # MSRV: not set\n\
+             \n\
+             # default\n\
+             too-many-lines-threshold = 80\n"
+        );
+    }
+
+    #[test]
+    fn json_document() {
+        let output: Value = serde_json::from_str(&workspace_json("/ws", &packages())).unwrap();
+        assert_eq!(output["workspace_root"], "/ws");
+        assert_eq!(output["packages"]["app"]["config_file"], "/ws/app/clippy.toml");
+        assert_eq!(output["packages"]["app"]["inherited_files"], json!(["/ws/clippy.toml"]));
+        assert_eq!(
+            output["packages"]["app"]["msrv"],
+            json!({ "version": "1.60.0", "source": "Cargo.toml" })
+        );
+        assert_eq!(
+            output["packages"]["core.rs"]["values"]["too-many-lines-threshold"],
+            json!({ "value": 80, "source": "default" })
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Conversion of the diagnostics printed by `cargo check --message-format=json` to a [SARIF 2.1.0]
+//! log, for `cargo clippy --message-format=sarif`.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use clippy_utils::diagnostics::lint_docs_url;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_lint::Level;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::Path;
+
+/// The base URI of the relative paths in the log, the root of the workspace.
+const SRCROOT: &str = "%SRCROOT%";
+
+/// A line of the output of `cargo check --message-format=json`.
+#[derive(Deserialize)]
+pub(crate) struct CargoMessage {
+    pub reason: String,
+    pub message: Option<Diagnostic>,
+}
+
+/// A diagnostic emitted by rustc, see the [JSON output] documentation.
+///
+/// [JSON output]: https://doc.rust-lang.org/rustc/json.html
+#[derive(Deserialize)]
+pub(crate) struct Diagnostic {
+    pub message: String,
+    pub code: Option<DiagnosticCode>,
+    pub level: String,
+    pub spans: Vec<DiagnosticSpan>,
+    pub children: Vec<Diagnostic>,
+    pub rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DiagnosticCode {
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DiagnosticSpan {
+    pub file_name: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub is_primary: bool,
+    pub label: Option<String>,
+    pub suggested_replacement: Option<String>,
+    pub suggestion_applicability: Option<String>,
+}
+
+/// A SARIF log of the diagnostics printed by Cargo.
+#[derive(Default)]
+pub struct SarifLog {
+    /// The ids of the rules of the results, in order of appearance.
+    rules: Vec<String>,
+    results: Vec<Value>,
+    /// The results added so far, as Cargo prints a diagnostic once for each target checking the
+    /// file containing it.
+    seen: FxHashSet<String>,
+}
+
+impl SarifLog {
+    /// Adds the diagnostic in a line of the output of `cargo check --message-format=json` to the
+    /// log, and returns the diagnostic as rendered by rustc. Lines that aren't diagnostics are
+    /// ignored.
+    pub fn add_cargo_message(&mut self, line: &str) -> Option<String> {
+        let message = serde_json::from_str::<CargoMessage>(line).ok()?;
+        let diagnostic = message.message.filter(|_| message.reason == "compiler-message")?;
+        if let Some(mut result) = sarif_result(&diagnostic)
+            && self.seen.insert(result.to_string())
+        {
+            if let Some(code) = &diagnostic.code {
+                let index = match self.rules.iter().position(|rule| *rule == code.code) {
+                    Some(index) => index,
+                    None => {
+                        self.rules.push(code.code.clone());
+                        self.rules.len() - 1
+                    },
+                };
+                result["ruleIndex"] = json!(index);
+            }
+            self.results.push(result);
+        }
+        diagnostic.rendered
+    }
+
+    /// Returns the log as JSON. `version` is the version of Clippy and `root` the root of the
+    /// workspace, which the paths of the diagnostics are relative to.
+    pub fn to_json(&self, version: &str, root: Option<&Path>) -> String {
+        let mut run = json!({
+            "tool": {
+                "driver": {
+                    "name": "clippy",
+                    "informationUri": "https://github.com/rust-lang/rust-clippy",
+                    "version": version,
+                    "rules": self.rules.iter().map(|id| sarif_rule(id)).collect::<Vec<_>>(),
+                },
+            },
+            // rustc counts columns in characters
+            "columnKind": "unicodeCodePoints",
+            "results": self.results,
+        });
+        if let Some(root) = root {
+            let mut uri = file_uri(&root.display().to_string());
+            if !uri.ends_with('/') {
+                uri.push('/');
+            }
+            run["originalUriBaseIds"] = json!({ SRCROOT: { "uri": uri } });
+        }
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [run],
+        });
+        serde_json::to_string_pretty(&log).unwrap()
+    }
+}
+
+/// Returns the metadata of the rule `id`, which is a lint name. Only Clippy lints have more than an
+/// id.
+fn sarif_rule(id: &str) -> Value {
+    let Some(info) = clippy_lints::lints()
+        .iter()
+        .find(|info| info.lint().name.eq_ignore_ascii_case(id))
+    else {
+        return json!({ "id": id });
+    };
+    let name = id.trim_start_matches("clippy::");
+    let level = match info.lint().default_level {
+        Level::Allow | Level::Expect(_) => "none",
+        Level::Warn | Level::ForceWarn(_) => "warning",
+        Level::Deny | Level::Forbid => "error",
+    };
+    json!({
+        "id": id,
+        "name": name,
+        "shortDescription": { "text": info.lint().desc },
+        "help": { "text": info.explanation(), "markdown": info.explanation() },
+        "helpUri": lint_docs_url(name),
+        "defaultConfiguration": { "enabled": level != "none", "level": level },
+        "properties": { "tags": [info.group()] },
+    })
+}
+
+/// Returns the result for `diagnostic`, or `None` if it doesn't point to any code, like the
+/// "aborting due to previous error" message.
+fn sarif_result(diagnostic: &Diagnostic) -> Option<Value> {
+    let primary = diagnostic.spans.iter().find(|span| span.is_primary)?;
+    let level = match diagnostic.level.as_str() {
+        "error" | "error: internal compiler error" => "error",
+        "warning" => "warning",
+        _ => "note",
+    };
+
+    let mut related = diagnostic
+        .spans
+        .iter()
+        .filter(|span| !span.is_primary)
+        .map(|span| sarif_location(span, span.label.as_deref()))
+        .collect::<Vec<_>>();
+    let mut fixes = Vec::new();
+    for child in &diagnostic.children {
+        let replacements = child
+            .spans
+            .iter()
+            .filter(|span| span.suggestion_applicability.as_deref() == Some("MachineApplicable"))
+            .filter_map(|span| Some((span, span.suggested_replacement.as_deref()?)))
+            .collect::<Vec<_>>();
+        if replacements.is_empty() {
+            related.extend(
+                child
+                    .spans
+                    .iter()
+                    .filter(|span| span.suggested_replacement.is_none())
+                    .map(|span| sarif_location(span, Some(span.label.as_deref().unwrap_or(&child.message)))),
+            );
+        } else {
+            fixes.push(sarif_fix(&child.message, &replacements));
+        }
+    }
+
+    let mut result = json!({
+        "level": level,
+        "message": { "text": diagnostic.message },
+        "locations": [sarif_location(primary, primary.label.as_deref())],
+    });
+    if let Some(code) = &diagnostic.code {
+        result["ruleId"] = json!(code.code);
+    }
+    if !related.is_empty() {
+        result["relatedLocations"] = json!(related);
+    }
+    if !fixes.is_empty() {
+        result["fixes"] = json!(fixes);
+    }
+    Some(result)
+}
+
+/// Returns a fix replacing the code of each span by the given text.
+fn sarif_fix(description: &str, replacements: &[(&DiagnosticSpan, &str)]) -> Value {
+    let mut changes: Vec<(&str, Vec<Value>)> = Vec::new();
+    for &(span, text) in replacements {
+        let replacement = json!({
+            "deletedRegion": sarif_region(span),
+            "insertedContent": { "text": text },
+        });
+        match changes.iter_mut().find(|(file, _)| *file == span.file_name) {
+            Some((_, replacements)) => replacements.push(replacement),
+            None => changes.push((&span.file_name, vec![replacement])),
+        }
+    }
+    let changes = changes
+        .into_iter()
+        .map(|(file, replacements)| {
+            json!({
+                "artifactLocation": sarif_artifact_location(file),
+                "replacements": replacements,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "description": { "text": description },
+        "artifactChanges": changes,
+    })
+}
+
+fn sarif_location(span: &DiagnosticSpan, message: Option<&str>) -> Value {
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": sarif_artifact_location(&span.file_name),
+            "region": sarif_region(span),
+        },
+    });
+    if let Some(message) = message.filter(|message| !message.is_empty()) {
+        location["message"] = json!({ "text": message });
+    }
+    location
+}
+
+fn sarif_region(span: &DiagnosticSpan) -> Value {
+    json!({
+        "startLine": span.line_start,
+        "startColumn": span.column_start,
+        "endLine": span.line_end,
+        "endColumn": span.column_end,
+    })
+}
+
+/// Paths are relative to the root of the workspace, except for files outside of it.
+fn sarif_artifact_location(file: &str) -> Value {
+    if Path::new(file).is_absolute() {
+        json!({ "uri": file_uri(file) })
+    } else {
+        json!({ "uri": encode_uri_path(&file.replace('\\', "/")), "uriBaseId": SRCROOT })
+    }
+}
+
+fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    // Windows paths start with a drive letter rather than a slash
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    format!("file://{separator}{}", encode_uri_path(&path))
+}
+
+/// Percent-encodes the characters of `path` that aren't allowed in the path of a URI.
+fn encode_uri_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            write!(encoded, "%{byte:02X}").unwrap();
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MESSAGE: &str = r#"{"reason":"compiler-message","package_id":"foo 0.1.0","message":{"rendered":"warning: unneeded `return` statement\n","message":"unneeded `return` statement","code":{"code":"clippy::needless_return","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","byte_start":20,"byte_end":29,"line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"text":[],"label":null,"suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[{"message":"remove `return`","code":null,"level":"help","spans":[{"file_name":"src/main.rs","byte_start":20,"byte_end":29,"line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"text":[],"label":null,"suggested_replacement":"1","suggestion_applicability":"MachineApplicable","expansion":null}],"children":[],"rendered":null}]}}"#;
+
+    #[test]
+    fn cargo_message_to_sarif() {
+        let mut log = SarifLog::default();
+        assert_eq!(
+            log.add_cargo_message(MESSAGE).as_deref(),
+            Some("warning: unneeded `return` statement\n")
+        );
+        // the same diagnostic for another target
+        log.add_cargo_message(MESSAGE);
+        assert_eq!(
+            log.add_cargo_message(r#"{"reason":"build-finished","success":true}"#),
+            None
+        );
+
+        let log: Value = serde_json::from_str(&log.to_json("0.1.69", Some(Path::new("/work space")))).unwrap();
+        let run = &log["runs"][0];
+        assert_eq!(run["originalUriBaseIds"][SRCROOT]["uri"], "file:///work%20space/");
+
+        let rule = &run["tool"]["driver"]["rules"][0];
+        assert_eq!(rule["id"], "clippy::needless_return");
+        assert_eq!(rule["properties"]["tags"][0], "style");
+        assert_eq!(rule["defaultConfiguration"]["level"], "warning");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleIndex"], 0);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["region"]["startColumn"], 5);
+        let replacement = &results[0]["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["insertedContent"]["text"], "1");
+    }
+}
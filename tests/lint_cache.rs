@@ -0,0 +1,114 @@
+#![feature(once_cell)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::SystemTime;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn test_lint_cache_replays_with_new_levels() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("lint_cache_test");
+    let cwd = root.join("tests/lint_cache_test");
+
+    // Make sure we start with a clean state
+    Command::new("cargo")
+        .current_dir(&cwd)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("clean")
+        .output()
+        .unwrap();
+
+    let clippy = |args: &[&str]| {
+        let output = Command::new(&*CARGO_CLIPPY_PATH)
+            .current_dir(&cwd)
+            .env("CARGO_INCREMENTAL", "0")
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .arg("clippy")
+            .args(args)
+            .output()
+            .unwrap();
+        println!("status: {}", output.status);
+        println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+        println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert!(output.status.success());
+        output
+    };
+
+    // The first run checks the crate, and records the warnings of the enabled lints
+    let first = warnings(&clippy(&["--lint-cache", "--", "-W", "clippy::doc_markdown"]));
+    assert!(first.contains("warning: unneeded `return` statement"));
+    assert!(first.contains("warning: item in documentation is missing backticks"));
+    let cache = cache_file(&target_dir).expect("the results of the first run weren't cached");
+    let recorded = modified(&cache);
+
+    // The second run only allows a lint, so the recorded warnings are replayed with the new levels
+    let replayed = warnings(&clippy(&[
+        "--lint-cache",
+        "--",
+        "-W",
+        "clippy::doc_markdown",
+        "-A",
+        "clippy::needless_return",
+    ]));
+    assert!(!replayed.contains("warning: unneeded `return` statement"));
+    assert!(replayed.contains("warning: item in documentation is missing backticks"));
+    assert_eq!(modified(&cache), recorded, "the crate was checked again");
+
+    // The replayed warnings are the ones of a run checking the crate with the same levels
+    let checked = warnings(&clippy(&[
+        "--",
+        "-W",
+        "clippy::doc_markdown",
+        "-A",
+        "clippy::needless_return",
+    ]));
+    assert_eq!(replayed, checked);
+
+    // The warnings of a lint that was allowed weren't recorded, so enabling it checks the crate again
+    let enabled = warnings(&clippy(&[
+        "--lint-cache",
+        "--",
+        "-W",
+        "clippy::doc_markdown",
+        "-W",
+        "clippy::missing_inline_in_public_items",
+    ]));
+    assert!(enabled.contains("warning: missing `#[inline]` for a function"));
+    assert_ne!(modified(&cache), recorded, "the recorded warnings were replayed");
+}
+
+/// Returns the warnings of a run of Clippy, without the progress of Cargo.
+fn warnings(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            !line.starts_with("Checking") && !line.starts_with("Finished") && !line.starts_with("Compiling")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the path of the cache file of the crate, if one was written.
+fn cache_file(target_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(target_dir.join("debug/deps"))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| name.ends_with(".clippy-cache"))
+        })
+}
+
+fn modified(path: &Path) -> SystemTime {
+    fs::metadata(path).unwrap().modified().unwrap()
+}
@@ -0,0 +1,4 @@
+/// Returns the answer of DeepThought.
+pub fn answer() -> u32 {
+    return 42;
+}
@@ -0,0 +1,392 @@
+//! Compiles the examples in the documentation of every lint with Clippy, checking that the code
+//! before `Use instead:` triggers the lint and the code after it doesn't trigger any lint, so that
+//! the documentation can't drift from the behavior of the lints. The `toml` code blocks of the
+//! examples are the `clippy.toml` they are compiled with.
+//!
+//! Set `TESTNAME` to a comma separated list of lint names to only check some lints.
+
+#![feature(is_sorted)]
+#![feature(once_cell)]
+#![cfg_attr(feature = "deny-warnings", deny(warnings))]
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use regex::Regex;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use test_utils::IS_RUSTC_TEST_SUITE;
+use walkdir::WalkDir;
+
+mod test_utils;
+
+/// Lints whose documented examples can't behave as documented, with the reason
+static KNOWN_EXCEPTIONS: &[&str] = &[
+    // the body of the example is elided, the function is shorter than `too-many-lines-threshold`
+    "too_many_lines",
+];
+
+/// The lint groups whose examples are not checked, `cargo` lints need a whole crate and `internal`
+/// lints are only available with the `internal` feature
+const IGNORED_GROUPS: &[&str] = &["cargo", "internal", "internal_warn"];
+
+/// The attributes of a code block that rustdoc doesn't compile, or that isn't Rust code, cause it
+/// to be ignored. Only the attributes listed here are allowed on a checked code block.
+const CHECKED_ATTRIBUTES: &[&str] = &["", "rust", "no_run", "should_panic"];
+
+/// The top level of a diagnostic emitted with `--error-format=json`, capturing its code and level
+static DIAGNOSTIC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r#"^\{"message":"((?:[^"\\]|\\.)*)","#,
+        r#""code":(?:null|\{"code":"([^"]+)","explanation":(?:null|"(?:[^"\\]|\\.)*")\}),"#,
+        r#""level":"([^"]+)""#,
+    ))
+    .unwrap()
+});
+
+struct Lint {
+    name: String,
+    group: String,
+    /// The file and line of the `declare_clippy_lint!`
+    location: String,
+    examples: Vec<Example>,
+    /// The `clippy.toml` configuration from the `toml` code blocks of the examples
+    conf: String,
+}
+
+struct Example {
+    code: String,
+    edition: &'static str,
+    /// Whether the example comes before `Use instead:`, and should trigger the lint
+    bad: bool,
+}
+
+#[test]
+fn lint_doc_examples() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    assert!(KNOWN_EXCEPTIONS.iter().is_sorted());
+
+    let filters: Vec<String> = env::var("TESTNAME")
+        .map(|filters| filters.split(',').map(ToString::to_string).collect())
+        .unwrap_or_default();
+    let lints: Vec<Lint> = declared_lints()
+        .into_iter()
+        .filter(|lint| !IGNORED_GROUPS.contains(&lint.group.as_str()))
+        .filter(|lint| filters.is_empty() || filters.contains(&lint.name))
+        .collect();
+
+    let current_exe_path = env::current_exe().unwrap();
+    let profile_path = current_exe_path.parent().unwrap().parent().unwrap();
+    let driver_path = profile_path.join(if cfg!(windows) {
+        "clippy-driver.exe"
+    } else {
+        "clippy-driver"
+    });
+    let build_dir = profile_path.join("test").join("lint_doc_examples");
+    fs::create_dir_all(&build_dir).unwrap();
+    // the `clippy.toml` of this repository would otherwise change the behavior of the lints
+    fs::write(build_dir.join("clippy.toml"), "").unwrap();
+
+    let next = AtomicUsize::new(0);
+    let failures = Mutex::new(Vec::new());
+    let passing_exceptions = Mutex::new(Vec::new());
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    thread::scope(|scope| {
+        for thread in 0..threads {
+            let thread_dir = build_dir.join(thread.to_string());
+            let (next, failures, passing_exceptions, lints, driver_path) =
+                (&next, &failures, &passing_exceptions, &lints, &driver_path);
+            scope.spawn(move || {
+                fs::create_dir_all(&thread_dir).unwrap();
+                while let Some(lint) = lints.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let problems = check_lint(lint, driver_path, &thread_dir);
+                    let is_exception = KNOWN_EXCEPTIONS.binary_search(&lint.name.as_str()).is_ok();
+                    if problems.is_empty() {
+                        if is_exception {
+                            passing_exceptions.lock().unwrap().push(lint.name.clone());
+                        }
+                    } else if !is_exception {
+                        failures.lock().unwrap().push((lint.location.clone(), problems));
+                    }
+                }
+            });
+        }
+    });
+
+    let mut failures = failures.into_inner().unwrap();
+    failures.sort();
+    let mut message = String::new();
+    for (location, problems) in &failures {
+        let _ = writeln!(message, "{location}:");
+        for problem in problems {
+            let _ = writeln!(message, "    {}", problem.replace('\n', "\n    "));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "The examples in the documentation of {} lints don't behave as documented:\n\n{message}\n\
+        Please fix the examples, the `toml` code blocks of the examples are their `clippy.toml`. If an \
+        example can't behave as documented, add the lint to `KNOWN_EXCEPTIONS` in \
+        `tests/lint_doc_examples.rs` with the reason.",
+        failures.len()
+    );
+
+    let mut passing_exceptions = passing_exceptions.into_inner().unwrap();
+    passing_exceptions.sort();
+    assert!(
+        passing_exceptions.is_empty(),
+        "The examples of these lints behave as documented, please remove them from \
+        `KNOWN_EXCEPTIONS` in `tests/lint_doc_examples.rs`: {}",
+        passing_exceptions.join(", ")
+    );
+}
+
+/// Collects the lints declared with `declare_clippy_lint!` in `clippy_lints/src` along with the
+/// examples in their documentation
+fn declared_lints() -> Vec<Lint> {
+    let mut lints = Vec::new();
+    for entry in WalkDir::new("clippy_lints/src") {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| ext != "rs") {
+            continue;
+        }
+        let content = fs::read_to_string(path).unwrap();
+        let mut lines = content.lines().enumerate();
+        while let Some((index, line)) = lines.next() {
+            if line.trim() != "declare_clippy_lint! {" {
+                continue;
+            }
+            let mut docs = Vec::new();
+            let mut name = None;
+            for (_, line) in lines.by_ref() {
+                let line = line.trim();
+                if let Some(doc) = line.strip_prefix("///") {
+                    docs.push(doc.strip_prefix(' ').unwrap_or(doc));
+                } else if let Some(rest) = line.strip_prefix("pub ") {
+                    name = Some(rest.trim_end_matches(',').to_lowercase());
+                    break;
+                }
+            }
+            let (Some(name), Some((_, group))) = (name, lines.next()) else {
+                continue;
+            };
+            let (examples, conf) = examples(&docs);
+            lints.push(Lint {
+                name,
+                group: group.trim().trim_end_matches(',').to_string(),
+                location: format!("{}:{}", path.display(), index + 1),
+                examples,
+                conf,
+            });
+        }
+    }
+    lints.sort_by(|a, b| a.name.cmp(&b.name));
+    lints
+}
+
+/// Extracts the code blocks of the `### Example` section of the documentation of a lint that
+/// rustdoc would compile, and the configuration of its `toml` code blocks
+fn examples(docs: &[&str]) -> (Vec<Example>, String) {
+    let mut examples = Vec::new();
+    let mut conf = String::new();
+    let mut in_examples = false;
+    let mut bad = true;
+    // the lines of the code block being read, with its kind, or `None` if it's not checked
+    let mut block: Option<(Vec<&str>, Option<BlockKind>)> = None;
+    for &line in docs {
+        if let Some((code, kind)) = &mut block {
+            if line.trim_start().starts_with("```") {
+                match *kind {
+                    Some(BlockKind::Rust(edition)) => examples.push(Example {
+                        code: unhide(code),
+                        edition,
+                        bad,
+                    }),
+                    Some(BlockKind::Toml) => {
+                        for line in code {
+                            let _ = writeln!(conf, "{line}");
+                        }
+                    },
+                    None => {},
+                }
+                block = None;
+            } else {
+                code.push(line);
+            }
+        } else if let Some(header) = line.strip_prefix("### ") {
+            in_examples = header.trim_start().starts_with("Example");
+            bad = true;
+        } else if in_examples {
+            if line.contains("Use instead") {
+                bad = false;
+            } else if let Some(info) = line.trim_start().strip_prefix("```") {
+                block = Some((Vec::new(), block_kind(info)));
+            }
+        }
+    }
+    (examples, conf)
+}
+
+enum BlockKind {
+    /// Rust code compiled with the edition
+    Rust(&'static str),
+    /// The `clippy.toml` configuration of the examples
+    Toml,
+}
+
+/// Returns the kind of a code block with the attributes `info`, or `None` if the code block isn't
+/// compiled by rustdoc
+fn block_kind(info: &str) -> Option<BlockKind> {
+    if info.trim() == "toml" {
+        return Some(BlockKind::Toml);
+    }
+    let mut edition = "2021";
+    for attribute in info.split(',').map(str::trim) {
+        match attribute {
+            "edition2015" => edition = "2015",
+            "edition2018" => edition = "2018",
+            "edition2021" => edition = "2021",
+            attribute if CHECKED_ATTRIBUTES.contains(&attribute) => {},
+            _ => return None,
+        }
+    }
+    Some(BlockKind::Rust(edition))
+}
+
+/// Removes the `# ` hiding lines of a code block from the documentation
+fn unhide(code: &[&str]) -> String {
+    let mut unhidden = String::new();
+    for line in code {
+        let trimmed = line.trim_start();
+        if trimmed == "#" {
+            unhidden.push('\n');
+        } else if let Some(hidden) = trimmed.strip_prefix("# ") {
+            let _ = writeln!(unhidden, "{hidden}");
+        } else {
+            let _ = writeln!(unhidden, "{line}");
+        }
+    }
+    unhidden
+}
+
+/// Compiles the examples of `lint` and returns a description of each example that doesn't behave
+/// as documented
+fn check_lint(lint: &Lint, driver_path: &Path, dir: &Path) -> Vec<String> {
+    let conf_dir = if lint.conf.is_empty() {
+        dir.parent().unwrap().to_path_buf()
+    } else {
+        let conf_dir = dir.join(&lint.name);
+        fs::create_dir_all(&conf_dir).unwrap();
+        fs::write(conf_dir.join("clippy.toml"), &lint.conf).unwrap();
+        conf_dir
+    };
+
+    let mut problems = Vec::new();
+    for (index, example) in lint.examples.iter().enumerate() {
+        let (source, crate_type) = source(&example.code);
+        let path = dir.join(format!("{}_{index}.rs", lint.name));
+        fs::write(&path, source).unwrap();
+
+        let output = Command::new(driver_path)
+            .args(["--edition", example.edition])
+            .args(["--crate-type", crate_type])
+            .args(["--crate-name", "lint_doc_example"])
+            .args(["--emit=metadata", "--error-format=json"])
+            .arg("--out-dir")
+            .arg(dir)
+            .args(["-W", &format!("clippy::{}", lint.name)])
+            .arg(&path)
+            .env("CLIPPY_CONF_DIR", &conf_dir)
+            .env("CLIPPY_DISABLE_DOCS_LINKS", "true")
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let mut errors = Vec::new();
+        let mut emitted_lints = Vec::new();
+        for captures in stderr.lines().filter_map(|line| DIAGNOSTIC.captures(line)) {
+            match (captures.get(2).map(|code| code.as_str()), &captures[3]) {
+                (Some(code), _) if code.starts_with("clippy::") => {
+                    if !emitted_lints.contains(&code) {
+                        emitted_lints.push(code);
+                    }
+                },
+                (_, "error") if !captures[1].starts_with("aborting due to") => errors.push(&captures[1]),
+                _ => {},
+            }
+        }
+
+        let expected = format!("clippy::{}", lint.name);
+        let problem = if !errors.is_empty() {
+            Some(format!("doesn't compile: {}", errors.join(", ")))
+        } else if !output.status.success() && emitted_lints.is_empty() {
+            Some(format!("Clippy failed:\n{}", stderr.trim_end()))
+        } else if example.bad && !emitted_lints.contains(&expected.as_str()) {
+            Some(format!("doesn't trigger `{}`", lint.name))
+        } else if let Some(other) = emitted_lints.iter().find(|&&code| !example.bad || code != expected) {
+            Some(format!("triggers `{}`", other.trim_start_matches("clippy::")))
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            let kind = if example.bad {
+                "example"
+            } else {
+                "`Use instead` example"
+            };
+            problems.push(format!("{kind} #{} {problem}:\n{}", index + 1, example.code.trim_end()));
+        }
+    }
+    problems
+}
+
+/// Returns the source compiled for an example and its crate type. Like rustdoc, an example that
+/// isn't made of items is wrapped in a `main` function.
+fn source(code: &str) -> (String, &'static str) {
+    if let Ok(file) = syn::parse_file(code) {
+        // a macro call like `println!("..");` parses as an item, but is meant as a statement
+        if !file
+            .items
+            .iter()
+            .any(|item| matches!(item, syn::Item::Macro(item) if item.ident.is_none()))
+        {
+            // an example with a `main` function is a binary, like for rustdoc
+            let has_main = file
+                .items
+                .iter()
+                .any(|item| matches!(item, syn::Item::Fn(item) if item.sig.ident == "main"));
+            return (code.to_string(), if has_main { "bin" } else { "lib" });
+        }
+    }
+
+    // inner attributes must stay at the start of the crate
+    let mut source = String::new();
+    let mut lines = code.lines().peekable();
+    while let Some(line) = lines.next_if(|line| line.trim_start().starts_with("#![")) {
+        let _ = writeln!(source, "{line}");
+    }
+    source.push_str("fn main() {\n");
+    for line in lines {
+        let _ = writeln!(source, "{line}");
+    }
+    source.push_str("}\n");
+    (source, "bin")
+}
+
+#[test]
+fn known_exceptions_are_lints() {
+    let lints = declared_lints();
+    for exception in KNOWN_EXCEPTIONS {
+        assert!(
+            lints.iter().any(|lint| lint.name == *exception),
+            "`{exception}` in `KNOWN_EXCEPTIONS` is not a lint"
+        );
+    }
+}
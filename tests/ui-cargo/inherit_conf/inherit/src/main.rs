@@ -0,0 +1,12 @@
+#![warn(clippy::disallowed_names)]
+
+fn main() {
+    // `tata` is disallowed by the member configuration
+    let tata = 1;
+    // `toto` is disallowed by the inherited workspace configuration
+    let toto = 2;
+    // `foo` is part of the default configuration
+    let foo = 3;
+    // `titi` is okay
+    let titi = 4;
+}
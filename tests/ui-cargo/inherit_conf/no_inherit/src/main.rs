@@ -0,0 +1,9 @@
+#![warn(clippy::disallowed_names)]
+
+fn main() {
+    // `tata` is disallowed by the member configuration
+    let tata = 1;
+    // the workspace configuration is not inherited, `toto` and `foo` are okay
+    let toto = 2;
+    let foo = 3;
+}
@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+struct Counter;
+
+impl Counter {
+    fn count(&self) -> usize {
+        0
+    }
+}
+
+async fn handler() {
+    std::thread::sleep(Duration::from_millis(10));
+    // not run in the async context
+    let _ = || std::thread::sleep(Duration::from_millis(10));
+}
+
+fn not_async() {
+    std::thread::sleep(Duration::from_millis(10));
+    let _ = async {
+        std::thread::sleep(Duration::from_millis(10));
+    };
+}
+
+fn main() {
+    let _ = std::env::var("HOME");
+    let name = "HOME";
+    let _ = std::env::var(name);
+
+    let _ = [1, 2].iter().count();
+    let _ = Counter.count();
+}
+
+#[allow(clippy::iterator_count)]
+fn allowed() {
+    let _ = [1, 2].iter().count();
+}
+
+fn exit() {
+    // allowed in the `[lints]` table
+    std::process::exit(0);
+}
@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+mod secrets {
+    pub struct SecretString(pub String);
+
+    impl Clone for SecretString {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl std::fmt::Debug for SecretString {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("<redacted>")
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct ApiKey(pub SecretString);
+
+    pub mod tokens {
+        #[derive(Clone, Copy)]
+        pub struct Token(pub u64);
+    }
+}
+
+#[derive(Clone)]
+struct Public(String);
+
+#[derive(Debug)]
+struct Credentials {
+    user: String,
+    password: Option<secrets::SecretString>,
+}
+
+#[derive(Debug)]
+struct Borrowed<'a>(&'a secrets::SecretString);
+
+#[derive(Debug)]
+struct Settings {
+    user: String,
+}
+
+mod resources {
+    pub struct Handle;
+
+    impl Drop for Handle {
+        fn drop(&mut self) {}
+    }
+}
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+mod config {
+    use std::env::VarError;
+
+    pub fn env_var(key: &str) -> Result<String, VarError> {
+        std::env::var(key)
+    }
+
+    pub mod defaults {
+        pub fn home() -> Option<std::ffi::OsString> {
+            std::env::var_os("HOME")
+        }
+    }
+}
+
+mod cache {
+    use std::collections::HashMap;
+
+    pub struct Cache(pub HashMap<u32, u32>);
+}
+
+mod configuration {
+    pub fn user() -> Option<String> {
+        std::env::var("USER").ok()
+    }
+}
+
+macro_rules! home {
+    () => {
+        std::env::var("HOME")
+    };
+}
+
+fn main() {
+    let _ = config::env_var("HOME");
+    let _ = config::defaults::home();
+    let _ = configuration::user();
+
+    let _ = std::env::var("HOME");
+    let _ = std::env::var_os("HOME");
+    let _ = ["HOME"].map(std::env::var);
+    let _ = vec![1].leak();
+    // the replacement isn't suggested in the macro
+    let _ = home!();
+
+    let _ = cache::Cache(Default::default()).0;
+    let _: std::collections::HashMap<u32, u32> = Default::default();
+}
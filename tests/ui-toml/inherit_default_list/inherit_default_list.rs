@@ -0,0 +1,8 @@
+#![deny(clippy::disallowed_script_idents)]
+#![allow(dead_code)]
+
+fn main() {
+    let counter = 10; // OK, Latin is allowed by default
+    let счётчик = 10; // OK, Cyrillic is allowed by the configuration
+    let カウンタ = 10;
+}
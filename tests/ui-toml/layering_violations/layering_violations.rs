@@ -0,0 +1,59 @@
+mod infra {
+    pub struct Database;
+
+    impl Database {
+        pub fn connect() -> Self {
+            Database
+        }
+
+        pub fn query(&self) -> u32 {
+            0
+        }
+    }
+
+    pub fn database() -> Database {
+        Database
+    }
+}
+
+mod domain {
+    use crate::infra::Database;
+
+    pub fn count(db: &Database) -> u32 {
+        db.query()
+    }
+
+    pub fn load() -> String {
+        std::fs::read_to_string("users.txt").unwrap_or_default()
+    }
+
+    pub mod rules {
+        pub fn connect() -> u32 {
+            let db = crate::infra::Database::connect();
+            db.query()
+        }
+    }
+}
+
+mod api {
+    pub fn handler() -> u32 {
+        crate::domain::count(&crate::infra::database())
+    }
+
+    pub fn is_local() -> bool {
+        std::net::Ipv4Addr::LOCALHOST.is_loopback()
+    }
+
+    pub fn is_ready() -> bool {
+        // defined in `core` and reexported in `std`
+        std::sync::atomic::AtomicBool::new(true).into_inner()
+    }
+}
+
+fn main() {
+    let _ = api::handler();
+    let _ = api::is_local();
+    let _ = api::is_ready();
+    let _ = domain::load();
+    let _ = domain::rules::connect();
+}
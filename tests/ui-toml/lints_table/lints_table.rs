@@ -0,0 +1,13 @@
+fn main() {
+    // `cast_lossless` is enabled through the `pedantic` group
+    let _ = 1u8 as u32;
+    // `needless_pass_by_value` is part of `pedantic`, but allowed individually
+    takes_string(String::new());
+    let opt = Some(0);
+    // `unwrap_used` is a restriction lint enabled individually
+    let _ = opt.unwrap();
+}
+
+fn takes_string(s: String) -> usize {
+    s.len()
+}
@@ -0,0 +1,8 @@
+// `unwrap_used` and `expect_used` are allowed by default, the overrides enable them in `strict/`
+mod strict;
+
+fn main() {
+    let _ = Some(0).unwrap();
+    let _ = Some(0).expect("");
+    strict::strict_fn();
+}
@@ -0,0 +1,6 @@
+// the later override allows `unwrap_used` in this file, `expect_used` is still warned
+
+pub fn lenient_fn() {
+    let _ = Some(0).unwrap();
+    let _ = Some(0).expect("");
+}
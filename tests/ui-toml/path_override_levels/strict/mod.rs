@@ -0,0 +1,16 @@
+// `unwrap_used` is denied and `expect_used` is warned in this directory
+
+mod lenient;
+
+pub fn strict_fn() {
+    let _ = Some(0).unwrap();
+    let _ = Some(0).expect("");
+    allowed();
+    lenient::lenient_fn();
+}
+
+// the lint attributes of the file take precedence
+#[allow(clippy::unwrap_used)]
+fn allowed() {
+    let _ = Some(0).unwrap();
+}
@@ -0,0 +1,21 @@
+// `unwrap_used` is allowed and `too_many_arguments` has a higher threshold in this directory
+
+pub fn generated_fn() {
+    let _ = Some(0).unwrap();
+    many_args(0, 0, 0, 0, 0, 0, 0, 0);
+    expected();
+    warned();
+}
+
+fn many_args(_: u8, _: u8, _: u8, _: u8, _: u8, _: u8, _: u8, _: u8) {}
+
+// the expectation is fulfilled, the lint attributes of the file take precedence
+#[expect(clippy::unwrap_used)]
+fn expected() {
+    let _ = Some(0).unwrap();
+}
+
+#[warn(clippy::unwrap_used)]
+fn warned() {
+    let _ = Some(0).unwrap();
+}
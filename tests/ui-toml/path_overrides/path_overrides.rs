@@ -0,0 +1,12 @@
+#![feature(lint_reasons)]
+#![warn(clippy::unwrap_used, clippy::mod_module_files)]
+
+// `mod_module_files` is allowed in `generated/mod.rs`
+mod generated;
+
+fn main() {
+    let _ = Some(0).unwrap();
+    generated::generated_fn();
+}
+
+fn many_args(_: u8, _: u8, _: u8, _: u8, _: u8, _: u8, _: u8, _: u8) {}